@@ -0,0 +1,26 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+pub fn many_mixin_includes(c: &mut Criterion) {
+    c.bench_function("many_mixin_includes", |b| {
+        b.iter(|| {
+            grass::from_string(
+                black_box(include_str!("many_mixin_includes.scss").to_string()),
+                &Default::default(),
+            )
+        })
+    });
+}
+
+pub fn many_function_calls(c: &mut Criterion) {
+    c.bench_function("many_function_calls", |b| {
+        b.iter(|| {
+            grass::from_string(
+                black_box(include_str!("many_function_calls.scss").to_string()),
+                &Default::default(),
+            )
+        })
+    });
+}
+
+criterion_group!(benches, many_mixin_includes, many_function_calls,);
+criterion_main!(benches);
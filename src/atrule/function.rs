@@ -1,13 +1,16 @@
-use std::hash::{Hash, Hasher};
+use std::{
+    hash::{Hash, Hasher},
+    rc::Rc,
+};
 
 use codemap::Span;
 
-use crate::{args::FuncArgs, Token};
+use crate::{args::FuncArgs, parse::AstStmt};
 
 #[derive(Debug, Clone)]
 pub(crate) struct Function {
     pub args: FuncArgs,
-    pub body: Vec<Token>,
+    pub body: Rc<[AstStmt]>,
     pub declared_at_root: bool,
     pos: Span,
 }
@@ -27,10 +30,10 @@ impl PartialEq for Function {
 impl Eq for Function {}
 
 impl Function {
-    pub fn new(args: FuncArgs, body: Vec<Token>, declared_at_root: bool, pos: Span) -> Self {
+    pub fn new(args: FuncArgs, body: Vec<AstStmt>, declared_at_root: bool, pos: Span) -> Self {
         Function {
             args,
-            body,
+            body: body.into(),
             pos,
             declared_at_root,
         }
@@ -1,3 +1,5 @@
+use codemap::Span;
+
 use crate::parse::Stmt;
 
 #[derive(Debug, Clone)]
@@ -8,6 +10,8 @@ pub(crate) struct Keyframes {
     pub rule: String,
     pub name: String,
     pub body: Vec<Stmt>,
+    /// The span of the at-rule's name, including the `@`
+    pub span: Span,
 }
 
 #[derive(Debug, Clone)]
@@ -1,12 +1,26 @@
 use std::fmt;
 
-use crate::{parse::Stmt, selector::Selector};
+use codemap::Span;
+
+use crate::{
+    error::SassResult,
+    parse::Parser,
+    selector::Selector,
+    utils::{is_name_start, read_until_closing_paren},
+    {parse::Stmt, Token},
+};
 
 #[derive(Debug, Clone)]
 pub(crate) struct MediaRule {
     pub super_selector: Selector,
-    pub query: String,
+    pub query: Vec<MediaQuery>,
     pub body: Vec<Stmt>,
+    /// Whether `query` has been merged with the queries of an enclosing
+    /// `@media` rule, in which case this rule is emitted after that rule
+    /// rather than within it
+    pub is_merged: bool,
+    /// The span of `@media`
+    pub span: Span,
 }
 
 #[derive(Debug, Eq, PartialEq, Clone)]
@@ -25,21 +39,32 @@ pub(crate) struct MediaQuery {
     pub features: Vec<String>,
 }
 
-#[allow(dead_code)]
+/// The result of intersecting two media queries
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub(crate) enum MediaQueryMergeResult {
+    /// The queries have no intersection, so no media could match both
+    Empty,
+
+    /// The queries have an intersection, but it can't be represented as a
+    /// single media query
+    Unrepresentable,
+
+    /// The intersection of the two queries
+    Success(MediaQuery),
+}
+
 impl MediaQuery {
-    pub fn is_condition(&self) -> bool {
+    pub const fn is_condition(&self) -> bool {
         self.modifier.is_none() && self.media_type.is_none()
     }
 
     pub fn matches_all_types(&self) -> bool {
-        self.media_type.is_none()
-            || self
-                .media_type
-                .as_ref()
-                .map_or(false, |v| v.to_ascii_lowercase() == "all")
+        self.media_type
+            .as_ref()
+            .map_or(true, |v| v.eq_ignore_ascii_case("all"))
     }
 
-    pub fn condition(features: Vec<String>) -> Self {
+    pub const fn condition(features: Vec<String>) -> Self {
         Self {
             modifier: None,
             media_type: None,
@@ -47,23 +72,304 @@ impl MediaQuery {
         }
     }
 
-    #[allow(dead_code, unused_variables)]
-    pub fn merge(other: &Self) -> Self {
-        todo!()
+    /// Returns a query that matches the intersection of the media matched
+    /// by `self` and `other`
+    pub fn merge(&self, other: &Self) -> MediaQueryMergeResult {
+        let our_modifier = self.modifier.as_ref().map(|m| m.to_ascii_lowercase());
+        let our_type = self.media_type.as_ref().map(|t| t.to_ascii_lowercase());
+        let their_modifier = other.modifier.as_ref().map(|m| m.to_ascii_lowercase());
+        let their_type = other.media_type.as_ref().map(|t| t.to_ascii_lowercase());
+
+        if our_type.is_none() && their_type.is_none() {
+            return MediaQueryMergeResult::Success(Self::condition(
+                self.features
+                    .iter()
+                    .chain(&other.features)
+                    .cloned()
+                    .collect(),
+            ));
+        }
+
+        let our_not = our_modifier.as_deref() == Some("not");
+        let their_not = their_modifier.as_deref() == Some("not");
+
+        let (modifier, media_type, features) = if our_not != their_not {
+            if our_type == their_type {
+                let (negative_features, positive_features) = if our_not {
+                    (&self.features, &other.features)
+                } else {
+                    (&other.features, &self.features)
+                };
+
+                // If the negative features are a subset of the positive
+                // features, the query is empty. For example, `not screen and
+                // (color)` has no intersection with `screen and (color) and
+                // (grid)`.
+                //
+                // However, `not screen and (color)` *does* intersect with
+                // `screen and (grid)`, because it means `not (screen and
+                // (color))` and so it allows a screen with no color but with a
+                // grid.
+                if negative_features
+                    .iter()
+                    .all(|feature| positive_features.contains(feature))
+                {
+                    return MediaQueryMergeResult::Empty;
+                }
+
+                return MediaQueryMergeResult::Unrepresentable;
+            } else if self.matches_all_types() || other.matches_all_types() {
+                return MediaQueryMergeResult::Unrepresentable;
+            }
+
+            if our_not {
+                (&other.modifier, &other.media_type, other.features.clone())
+            } else {
+                (&self.modifier, &self.media_type, self.features.clone())
+            }
+        } else if our_not {
+            // CSS has no way of representing "neither screen nor print".
+            if our_type != their_type {
+                return MediaQueryMergeResult::Unrepresentable;
+            }
+
+            let (more_features, fewer_features) = if self.features.len() > other.features.len() {
+                (&self.features, &other.features)
+            } else {
+                (&other.features, &self.features)
+            };
+
+            // If one set of features is a superset of the other, use those
+            // features because they're strictly narrower.
+            if !fewer_features
+                .iter()
+                .all(|feature| more_features.contains(feature))
+            {
+                return MediaQueryMergeResult::Unrepresentable;
+            }
+
+            (&self.modifier, &self.media_type, more_features.clone())
+        } else if self.matches_all_types() {
+            // Omit the type if either input query did, since that indicates
+            // that they aren't targeting a browser that requires "all and".
+            let media_type = if other.matches_all_types() && our_type.is_none() {
+                &None
+            } else {
+                &other.media_type
+            };
+            (
+                &other.modifier,
+                media_type,
+                self.features
+                    .iter()
+                    .chain(&other.features)
+                    .cloned()
+                    .collect(),
+            )
+        } else if other.matches_all_types() {
+            (
+                &self.modifier,
+                &self.media_type,
+                self.features
+                    .iter()
+                    .chain(&other.features)
+                    .cloned()
+                    .collect(),
+            )
+        } else if our_type != their_type {
+            return MediaQueryMergeResult::Empty;
+        } else {
+            (
+                if self.modifier.is_some() {
+                    &self.modifier
+                } else {
+                    &other.modifier
+                },
+                &self.media_type,
+                self.features
+                    .iter()
+                    .chain(&other.features)
+                    .cloned()
+                    .collect(),
+            )
+        };
+
+        MediaQueryMergeResult::Success(Self {
+            modifier: if media_type.is_some() {
+                modifier.clone()
+            } else {
+                None
+            },
+            media_type: media_type.clone(),
+            features,
+        })
+    }
+
+    /// Returns the queries that match the intersection of the media matched
+    /// by `queries1` and `queries2`
+    ///
+    /// Returns `None` if the intersection can't be represented.
+    pub fn merge_lists(queries1: &[Self], queries2: &[Self]) -> Option<Vec<Self>> {
+        let mut queries = Vec::new();
+        for query1 in queries1 {
+            for query2 in queries2 {
+                match query1.merge(query2) {
+                    MediaQueryMergeResult::Empty => {}
+                    MediaQueryMergeResult::Unrepresentable => return None,
+                    MediaQueryMergeResult::Success(query) => queries.push(query),
+                }
+            }
+        }
+        Some(queries)
     }
 }
 
-impl fmt::Display for MediaQuery {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+impl MediaQuery {
+    pub fn serialize(&self, is_compressed: bool) -> String {
+        let mut buf = String::new();
         if let Some(modifier) = &self.modifier {
-            f.write_str(modifier)?;
+            buf.push_str(modifier);
+            buf.push(' ');
         }
         if let Some(media_type) = &self.media_type {
-            f.write_str(media_type)?;
-            if !&self.features.is_empty() {
-                f.write_str(" and ")?;
+            buf.push_str(media_type);
+            if !self.features.is_empty() {
+                buf.push_str(" and ");
             }
         }
-        f.write_str(&self.features.join(" and "))
+        if is_compressed {
+            buf.push_str(
+                &self
+                    .features
+                    .iter()
+                    .map(|feature| feature.replacen(": ", ":", 1))
+                    .collect::<Vec<String>>()
+                    .join("and "),
+            );
+        } else {
+            buf.push_str(&self.features.join(" and "));
+        }
+        buf
+    }
+
+    /// Serialize `queries` as the parameters of an `@media` rule
+    pub fn serialize_list(queries: &[Self], is_compressed: bool) -> String {
+        queries
+            .iter()
+            .map(|query| query.serialize(is_compressed))
+            .collect::<Vec<String>>()
+            .join(if is_compressed { "," } else { ", " })
+    }
+}
+
+impl fmt::Display for MediaQuery {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.serialize(false))
+    }
+}
+
+/// Parses a media query list from the plain CSS that remains after
+/// interpolation and expressions within the query have been evaluated
+pub(crate) struct MediaQueryParser<'a, 'b> {
+    parser: &'a mut Parser<'b>,
+
+    span: Span,
+}
+
+impl<'a, 'b> MediaQueryParser<'a, 'b> {
+    pub const fn new(parser: &'a mut Parser<'b>, span: Span) -> Self {
+        Self { parser, span }
+    }
+
+    pub fn parse(mut self) -> SassResult<Vec<MediaQuery>> {
+        let mut queries = Vec::new();
+        loop {
+            self.parser.whitespace();
+            queries.push(self.parse_media_query()?);
+            if !self.parser.consume_char_if_exists(',') {
+                break;
+            }
+        }
+
+        if self.parser.toks.peek().is_some() {
+            return Err(("expected no more input.", self.span).into());
+        }
+
+        Ok(queries)
+    }
+
+    fn parse_media_query(&mut self) -> SassResult<MediaQuery> {
+        let mut modifier = None;
+        let mut media_type = None;
+
+        if !matches!(self.parser.toks.peek(), Some(Token { kind: '(', .. })) {
+            let identifier1 = self.parser.parse_identifier()?.node;
+            self.parser.whitespace();
+
+            if !self.looking_at_identifier() {
+                // For example, "@media screen {"
+                return Ok(MediaQuery {
+                    modifier: None,
+                    media_type: Some(identifier1),
+                    features: Vec::new(),
+                });
+            }
+
+            let identifier2 = self.parser.parse_identifier()?.node;
+            self.parser.whitespace();
+
+            if identifier2.eq_ignore_ascii_case("and") {
+                // For example, "@media screen and ..."
+                media_type = Some(identifier1);
+            } else {
+                modifier = Some(identifier1);
+                media_type = Some(identifier2);
+                if self.parser.scan_identifier("and")? {
+                    // For example, "@media only screen and ..."
+                    self.parser.whitespace();
+                } else {
+                    // For example, "@media only screen {"
+                    return Ok(MediaQuery {
+                        modifier,
+                        media_type,
+                        features: Vec::new(),
+                    });
+                }
+            }
+        }
+
+        // We've consumed either `IDENTIFIER "and"`, `IDENTIFIER IDENTIFIER "and"`,
+        // or no text.
+        let mut features = Vec::new();
+        loop {
+            self.parser.whitespace();
+            self.parser.expect_char('(')?;
+            let feature = read_until_closing_paren(self.parser.toks)?;
+            if !matches!(feature.last(), Some(Token { kind: ')', .. })) {
+                return Err(("expected \")\".", self.span).into());
+            }
+            features.push(format!(
+                "({}",
+                feature.into_iter().map(|tok| tok.kind).collect::<String>()
+            ));
+            self.parser.whitespace();
+            if !self.parser.scan_identifier("and")? {
+                break;
+            }
+        }
+
+        Ok(MediaQuery {
+            modifier,
+            media_type,
+            features,
+        })
+    }
+
+    fn looking_at_identifier(&mut self) -> bool {
+        match self.parser.toks.peek() {
+            Some(Token { kind: '-', .. }) => true,
+            Some(Token { kind, .. }) => is_name_start(*kind),
+            None => false,
+        }
     }
 }
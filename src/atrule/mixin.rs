@@ -1,9 +1,9 @@
-use std::fmt;
+use std::{fmt, rc::Rc};
 
 use crate::{
     args::{CallArgs, FuncArgs},
     error::SassResult,
-    parse::{Parser, Stmt},
+    parse::{AstStmt, Parser, Stmt},
     Token,
 };
 
@@ -33,7 +33,7 @@ impl fmt::Debug for Mixin {
 impl Mixin {
     pub fn new_user_defined(
         args: FuncArgs,
-        body: Vec<Token>,
+        body: Vec<AstStmt>,
         accepts_content_block: bool,
         declared_at_root: bool,
     ) -> Self {
@@ -49,7 +49,7 @@ impl Mixin {
 #[derive(Debug, Clone)]
 pub(crate) struct UserDefinedMixin {
     pub args: FuncArgs,
-    pub body: Vec<Token>,
+    pub body: Rc<[AstStmt]>,
     pub accepts_content_block: bool,
     pub declared_at_root: bool,
 }
@@ -57,13 +57,13 @@ pub(crate) struct UserDefinedMixin {
 impl UserDefinedMixin {
     pub fn new(
         args: FuncArgs,
-        body: Vec<Token>,
+        body: Vec<AstStmt>,
         accepts_content_block: bool,
         declared_at_root: bool,
     ) -> Self {
         Self {
             args,
-            body,
+            body: body.into(),
             accepts_content_block,
             declared_at_root,
         }
@@ -1,6 +1,6 @@
 pub(crate) use function::Function;
 pub(crate) use kind::AtRuleKind;
-pub(crate) use supports::SupportsRule;
+pub(crate) use supports::{SupportsCondition, SupportsOperator, SupportsRule};
 pub(crate) use unknown::UnknownAtRule;
 
 mod function;
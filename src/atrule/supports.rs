@@ -1,7 +1,112 @@
+use std::fmt;
+
+use codemap::Span;
+
 use crate::parse::Stmt;
 
 #[derive(Debug, Clone)]
 pub(crate) struct SupportsRule {
-    pub params: String,
+    pub condition: SupportsCondition,
     pub body: Vec<Stmt>,
+    /// The span of `@supports`
+    pub span: Span,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) enum SupportsOperator {
+    And,
+    Or,
+}
+
+impl fmt::Display for SupportsOperator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::And => f.write_str("and"),
+            Self::Or => f.write_str("or"),
+        }
+    }
+}
+
+/// The condition of an `@supports` rule, after any Sass expressions within
+/// it have been evaluated
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum SupportsCondition {
+    /// Two conditions joined by `and` or `or`
+    Operation {
+        left: Box<SupportsCondition>,
+        operator: SupportsOperator,
+        right: Box<SupportsCondition>,
+    },
+
+    /// A condition negated by `not`
+    Negation(Box<SupportsCondition>),
+
+    /// An interpolated condition, such as `#{$condition}`
+    Interpolation(String),
+
+    /// A declaration, such as `(display: flex)`
+    Declaration {
+        name: String,
+        value: String,
+        /// Custom properties are emitted without a space after the colon, as
+        /// any whitespace is already part of their value
+        is_custom_property: bool,
+    },
+
+    /// A function call, such as `selector(a > b)`
+    Function { name: String, args: String },
+
+    /// Any other parenthesized condition, which is emitted as written
+    Anything(String),
+}
+
+impl SupportsCondition {
+    /// Serialize this condition as an operand of `operator`, or of `not` if
+    /// `operator` is `None`
+    fn parenthesize(&self, operator: Option<SupportsOperator>) -> String {
+        match self {
+            SupportsCondition::Negation(..) => format!("({})", self),
+            SupportsCondition::Operation {
+                operator: inner, ..
+            } if operator != Some(*inner) => {
+                format!("({})", self)
+            }
+            _ => self.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for SupportsCondition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SupportsCondition::Operation {
+                left,
+                operator,
+                right,
+            } => write!(
+                f,
+                "{} {} {}",
+                left.parenthesize(Some(*operator)),
+                operator,
+                right.parenthesize(Some(*operator))
+            ),
+            SupportsCondition::Negation(condition) => {
+                write!(f, "not {}", condition.parenthesize(None))
+            }
+            SupportsCondition::Interpolation(condition) => f.write_str(condition),
+            SupportsCondition::Declaration {
+                name,
+                value,
+                is_custom_property,
+            } => write!(
+                f,
+                "({}:{}{})",
+                name,
+                if *is_custom_property { "" } else { " " },
+                value
+            ),
+            SupportsCondition::Function { name, args } => write!(f, "{}({})", name, args),
+            SupportsCondition::Anything(contents) => write!(f, "({})", contents),
+        }
+    }
 }
@@ -1,3 +1,5 @@
+use codemap::Span;
+
 use crate::{parse::Stmt, selector::Selector};
 
 #[derive(Debug, Clone)]
@@ -6,4 +8,6 @@ pub(crate) struct UnknownAtRule {
     pub super_selector: Selector,
     pub params: String,
     pub body: Vec<Stmt>,
+    /// The span of the at-rule's name, including the `@`
+    pub span: Span,
 }
@@ -9,7 +9,7 @@ use crate::{
     error::SassResult,
     parse::Parser,
     unit::Unit,
-    value::{Number, Value},
+    value::{Division, Number, Value},
 };
 
 fn inner_hsl(name: &'static str, mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
@@ -170,7 +170,7 @@ fn inner_hsl(name: &'static str, mut args: CallArgs, parser: &mut Parser<'_>) ->
         let alpha = match args.default_arg(
             3,
             "alpha",
-            Value::Dimension(Some(Number::one()), Unit::None, true),
+            Value::Dimension(Some(Number::one()), Unit::None, Division::Computed),
         )? {
             Value::Dimension(Some(n), Unit::None, _) => n,
             Value::Dimension(Some(n), Unit::Percent, _) => n / Number::from(100),
@@ -223,7 +223,11 @@ pub(crate) fn hsla(args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value>
 pub(crate) fn hue(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
     args.max_args(1)?;
     match args.get_err(0, "color")? {
-        Value::Color(c) => Ok(Value::Dimension(Some(c.hue()), Unit::Deg, true)),
+        Value::Color(c) => Ok(Value::Dimension(
+            Some(c.hue()),
+            Unit::Deg,
+            Division::Computed,
+        )),
         v => Err((
             format!("$color: {} is not a color.", v.inspect(args.span())?),
             args.span(),
@@ -235,7 +239,11 @@ pub(crate) fn hue(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Val
 pub(crate) fn saturation(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
     args.max_args(1)?;
     match args.get_err(0, "color")? {
-        Value::Color(c) => Ok(Value::Dimension(Some(c.saturation()), Unit::Percent, true)),
+        Value::Color(c) => Ok(Value::Dimension(
+            Some(c.saturation()),
+            Unit::Percent,
+            Division::Computed,
+        )),
         v => Err((
             format!("$color: {} is not a color.", v.inspect(args.span())?),
             args.span(),
@@ -247,7 +255,11 @@ pub(crate) fn saturation(mut args: CallArgs, parser: &mut Parser<'_>) -> SassRes
 pub(crate) fn lightness(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
     args.max_args(1)?;
     match args.get_err(0, "color")? {
-        Value::Color(c) => Ok(Value::Dimension(Some(c.lightness()), Unit::Percent, true)),
+        Value::Color(c) => Ok(Value::Dimension(
+            Some(c.lightness()),
+            Unit::Percent,
+            Division::Computed,
+        )),
         v => Err((
             format!("$color: {} is not a color.", v.inspect(args.span())?),
             args.span(),
@@ -458,7 +470,7 @@ pub(crate) fn invert(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<
     let weight = match args.default_arg(
         1,
         "weight",
-        Value::Dimension(Some(Number::from(100)), Unit::Percent, true),
+        Value::Dimension(Some(Number::from(100)), Unit::Percent, Division::Computed),
     )? {
         Value::Dimension(Some(n), u, _) => bound!(args, "weight", n, u, 0, 100) / Number::from(100),
         Value::Dimension(None, ..) => todo!(),
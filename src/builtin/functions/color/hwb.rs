@@ -0,0 +1,242 @@
+use num_traits::{One, Signed};
+
+use codemap::Span;
+
+use crate::{
+    args::CallArgs,
+    color::Color,
+    common::ListSeparator,
+    error::SassResult,
+    parse::Parser,
+    unit::Unit,
+    value::{Division, Number, Value},
+};
+
+/// Validate `value` as a percentage between 0% and 100%,
+/// returning it as a number between 0 and 1
+fn percentage(value: Value, name: &str, span: Span) -> SassResult<Number> {
+    match value {
+        Value::Dimension(Some(n), Unit::Percent, _) => {
+            if n > Number::from(100) || n.is_negative() {
+                return Err((
+                    format!("${}: Expected {}% to be within 0% and 100%.", name, n),
+                    span,
+                )
+                    .into());
+            }
+            Ok(n / Number::from(100))
+        }
+        v @ Value::Dimension(None, ..) => Err((
+            format!(
+                "${}: Expected {} to be a finite number.",
+                name,
+                v.inspect(span)?
+            ),
+            span,
+        )
+            .into()),
+        v @ Value::Dimension(..) => Err((
+            format!(
+                "${}: Expected {} to have unit \"%\".",
+                name,
+                v.inspect(span)?
+            ),
+            span,
+        )
+            .into()),
+        v => Err((
+            format!("${}: {} is not a number.", name, v.inspect(span)?),
+            span,
+        )
+            .into()),
+    }
+}
+
+fn inner_hwb(
+    hue: Value,
+    whiteness: Value,
+    blackness: Value,
+    alpha: Value,
+    span: Span,
+) -> SassResult<Value> {
+    let hue = match hue {
+        Value::Dimension(Some(n), ..) => n,
+        v @ Value::Dimension(None, ..) => {
+            return Err((
+                format!("$hue: Expected {} to be a finite number.", v.inspect(span)?),
+                span,
+            )
+                .into())
+        }
+        v => return Err((format!("$hue: {} is not a number.", v.inspect(span)?), span).into()),
+    };
+
+    let whiteness = percentage(whiteness, "whiteness", span)?;
+    let blackness = percentage(blackness, "blackness", span)?;
+
+    let alpha = match alpha {
+        Value::Dimension(Some(n), Unit::None, _) => n,
+        Value::Dimension(Some(n), Unit::Percent, _) => n / Number::from(100),
+        v @ Value::Dimension(None, ..) => {
+            return Err((
+                format!(
+                    "$alpha: Expected {} to be a finite number.",
+                    v.inspect(span)?
+                ),
+                span,
+            )
+                .into())
+        }
+        v @ Value::Dimension(..) => {
+            return Err((
+                format!(
+                    "$alpha: Expected {} to have no units or \"%\".",
+                    v.inspect(span)?
+                ),
+                span,
+            )
+                .into())
+        }
+        v => {
+            return Err((
+                format!("$alpha: {} is not a number.", v.inspect(span)?),
+                span,
+            )
+                .into())
+        }
+    };
+
+    Ok(Value::Color(Box::new(Color::from_hwba(
+        hue, whiteness, blackness, alpha,
+    ))))
+}
+
+pub(crate) fn hwb(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
+    args.max_args(4)?;
+
+    if args.is_empty() {
+        return Err(("Missing argument $channels.", args.span()).into());
+    }
+
+    if args.len() == 1 {
+        let (channels, alpha) = match args.get_err(0, "channels")? {
+            Value::List(mut v, ListSeparator::Slash, ..) if v.len() == 2 => {
+                let alpha = v.pop();
+                (v.pop(), alpha)
+            }
+            Value::List(v, ListSeparator::Slash, ..) => {
+                return Err((
+                    format!(
+                        "Only 2 slash-separated elements allowed, but {} were passed.",
+                        v.len()
+                    ),
+                    args.span(),
+                )
+                    .into())
+            }
+            v => (Some(v), None),
+        };
+
+        let mut channels = match channels {
+            Some(Value::List(_, ListSeparator::Comma, ..)) => {
+                return Err(("$channels must be a space-separated list.", args.span()).into())
+            }
+            Some(Value::List(v, ..)) => v,
+            Some(v) => vec![v],
+            None => Vec::new(),
+        };
+
+        // `120 30% 40% / 0.5` separates the last channel from the alpha
+        // with `/`, rather than dividing them
+        let alpha = match (alpha, channels.pop()) {
+            (None, Some(Value::Dimension(_, _, Division::Slash(operands)))) => {
+                let (blackness, alpha) = *operands;
+                channels.push(blackness);
+                Some(alpha)
+            }
+            (alpha, last) => {
+                channels.extend(last);
+                alpha
+            }
+        };
+
+        if channels.len() > 3 {
+            return Err((
+                format!(
+                    "Only 3 elements allowed, but {} were passed.",
+                    channels.len()
+                ),
+                args.span(),
+            )
+                .into());
+        }
+
+        let mut channels = channels.into_iter();
+
+        let hue = match channels.next() {
+            Some(v) => v,
+            None => return Err(("Missing element $hue.", args.span()).into()),
+        };
+        let whiteness = match channels.next() {
+            Some(v) => v,
+            None => return Err(("Missing element $whiteness.", args.span()).into()),
+        };
+        let blackness = match channels.next() {
+            Some(v) => v,
+            None => return Err(("Missing element $blackness.", args.span()).into()),
+        };
+
+        return inner_hwb(
+            hue,
+            whiteness,
+            blackness,
+            alpha.unwrap_or_else(|| {
+                Value::Dimension(Some(Number::one()), Unit::None, Division::Computed)
+            }),
+            args.span(),
+        );
+    }
+
+    let hue = args.get_err(0, "hue")?;
+    let whiteness = args.get_err(1, "whiteness")?;
+    let blackness = args.get_err(2, "blackness")?;
+    let alpha = args.default_arg(
+        3,
+        "alpha",
+        Value::Dimension(Some(Number::one()), Unit::None, Division::Computed),
+    )?;
+
+    inner_hwb(hue, whiteness, blackness, alpha, args.span())
+}
+
+pub(crate) fn whiteness(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
+    args.max_args(1)?;
+    match args.get_err(0, "color")? {
+        Value::Color(c) => Ok(Value::Dimension(
+            Some(c.whiteness()),
+            Unit::Percent,
+            Division::Computed,
+        )),
+        v => Err((
+            format!("$color: {} is not a color.", v.inspect(args.span())?),
+            args.span(),
+        )
+            .into()),
+    }
+}
+
+pub(crate) fn blackness(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
+    args.max_args(1)?;
+    match args.get_err(0, "color")? {
+        Value::Color(c) => Ok(Value::Dimension(
+            Some(c.blackness()),
+            Unit::Percent,
+            Division::Computed,
+        )),
+        v => Err((
+            format!("$color: {} is not a color.", v.inspect(args.span())?),
+            args.span(),
+        )
+            .into()),
+    }
+}
@@ -1,8 +1,13 @@
 use super::{Builtin, GlobalFunctionMap};
 
 use crate::{
-    args::CallArgs, common::QuoteKind, error::SassResult, parse::Parser, unit::Unit, value::Number,
-    value::Value,
+    args::CallArgs,
+    common::QuoteKind,
+    error::SassResult,
+    parse::Parser,
+    unit::Unit,
+    value::Number,
+    value::{Division, Value},
 };
 
 /// Check if `s` matches the regex `^[a-zA-Z]+\s*=`
@@ -38,7 +43,11 @@ mod test {
 pub(crate) fn alpha(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
     if args.len() <= 1 {
         match args.get_err(0, "color")? {
-            Value::Color(c) => Ok(Value::Dimension(Some(c.alpha()), Unit::None, true)),
+            Value::Color(c) => Ok(Value::Dimension(
+                Some(c.alpha()),
+                Unit::None,
+                Division::Computed,
+            )),
             Value::String(s, QuoteKind::None) if is_ms_filter(&s) => {
                 Ok(Value::String(format!("alpha({})", s), QuoteKind::None))
             }
@@ -72,7 +81,11 @@ pub(crate) fn alpha(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<V
 pub(crate) fn opacity(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
     args.max_args(1)?;
     match args.get_err(0, "color")? {
-        Value::Color(c) => Ok(Value::Dimension(Some(c.alpha()), Unit::None, true)),
+        Value::Color(c) => Ok(Value::Dimension(
+            Some(c.alpha()),
+            Unit::None,
+            Division::Computed,
+        )),
         Value::Dimension(Some(num), unit, _) => Ok(Value::String(
             format!("opacity({}{})", num, unit),
             QuoteKind::None,
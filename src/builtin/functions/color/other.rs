@@ -2,6 +2,8 @@ use super::{Builtin, GlobalFunctionMap};
 
 use num_traits::{One, Signed, Zero};
 
+use codemap::Span;
+
 use crate::{
     args::CallArgs,
     color::Color,
@@ -48,6 +50,78 @@ macro_rules! opt_hsl {
     };
 }
 
+macro_rules! opt_percent {
+    ($args:ident, $name:ident, $arg:literal, $low:literal, $high:literal) => {
+        let $name = match $args.default_named_arg($arg, Value::Null)? {
+            Value::Dimension(Some(n), Unit::Percent, _) => {
+                Some(bound!($args, $arg, n, Unit::Percent, $low, $high) / Number::from(100))
+            }
+            v @ Value::Dimension(None, ..) => {
+                return Err((
+                    format!(
+                        "${}: Expected {} to be a finite number.",
+                        $arg,
+                        v.inspect($args.span())?
+                    ),
+                    $args.span(),
+                )
+                    .into())
+            }
+            v @ Value::Dimension(..) => {
+                return Err((
+                    format!(
+                        "${}: Expected {} to have unit \"%\".",
+                        $arg,
+                        v.inspect($args.span())?
+                    ),
+                    $args.span(),
+                )
+                    .into())
+            }
+            Value::Null => None,
+            v => {
+                return Err((
+                    format!("${}: {} is not a number.", $arg, v.inspect($args.span())?),
+                    $args.span(),
+                )
+                    .into())
+            }
+        };
+    };
+}
+
+/// Colors may only be modified through the parameters of a single color
+/// model at a time, with `$hue` shared between HSL and HWB
+fn check_color_model(
+    has_rgb: bool,
+    has_hue: bool,
+    has_sl: bool,
+    has_wb: bool,
+    span: Span,
+) -> SassResult<()> {
+    if has_rgb && (has_hue || has_sl || has_wb) {
+        return Err((
+            format!(
+                "RGB parameters may not be passed along with {} parameters.",
+                if has_wb { "HWB" } else { "HSL" }
+            ),
+            span,
+        )
+            .into());
+    }
+
+    if has_sl && has_wb {
+        return Err((
+            "HSL parameters may not be passed along with HWB parameters.",
+            span,
+        )
+            .into());
+    }
+
+    Ok(())
+}
+
+#[allow(clippy::cognitive_complexity)]
 pub(crate) fn change_color(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
     if args.positional_arg(1).is_some() {
         return Err((
@@ -73,15 +147,6 @@ pub(crate) fn change_color(mut args: CallArgs, parser: &mut Parser<'_>) -> SassR
     opt_rgba!(args, green, "green", 0, 255);
     opt_rgba!(args, blue, "blue", 0, 255);
 
-    if red.is_some() || green.is_some() || blue.is_some() {
-        return Ok(Value::Color(Box::new(Color::from_rgba(
-            red.unwrap_or_else(|| color.red()),
-            green.unwrap_or_else(|| color.green()),
-            blue.unwrap_or_else(|| color.blue()),
-            alpha.unwrap_or_else(|| color.alpha()),
-        ))));
-    }
-
     let hue = match args.default_named_arg("hue", Value::Null)? {
         Value::Dimension(Some(n), ..) => Some(n),
         Value::Dimension(None, ..) => todo!(),
@@ -97,8 +162,35 @@ pub(crate) fn change_color(mut args: CallArgs, parser: &mut Parser<'_>) -> SassR
 
     opt_hsl!(args, saturation, "saturation", 0, 100);
     opt_hsl!(args, luminance, "lightness", 0, 100);
+    opt_percent!(args, whiteness, "whiteness", 0, 100);
+    opt_percent!(args, blackness, "blackness", 0, 100);
+
+    let has_rgb = red.is_some() || green.is_some() || blue.is_some();
+    let has_sl = saturation.is_some() || luminance.is_some();
+    let has_wb = whiteness.is_some() || blackness.is_some();
 
-    if hue.is_some() || saturation.is_some() || luminance.is_some() {
+    check_color_model(has_rgb, hue.is_some(), has_sl, has_wb, args.span())?;
+
+    if has_rgb {
+        return Ok(Value::Color(Box::new(Color::from_rgba(
+            red.unwrap_or_else(|| color.red()),
+            green.unwrap_or_else(|| color.green()),
+            blue.unwrap_or_else(|| color.blue()),
+            alpha.unwrap_or_else(|| color.alpha()),
+        ))));
+    }
+
+    if has_wb {
+        let (this_hue, this_whiteness, this_blackness, this_alpha) = color.as_hwba();
+        return Ok(Value::Color(Box::new(Color::from_hwba(
+            hue.unwrap_or(this_hue),
+            whiteness.unwrap_or(this_whiteness),
+            blackness.unwrap_or(this_blackness),
+            alpha.unwrap_or(this_alpha),
+        ))));
+    }
+
+    if hue.is_some() || has_sl {
         // Color::as_hsla() returns more exact values than Color::hue(), etc.
         let (this_hue, this_saturation, this_luminance, this_alpha) = color.as_hsla();
         return Ok(Value::Color(Box::new(Color::from_hsla(
@@ -116,6 +208,7 @@ pub(crate) fn change_color(mut args: CallArgs, parser: &mut Parser<'_>) -> SassR
     }))
 }
 
+#[allow(clippy::cognitive_complexity)]
 pub(crate) fn adjust_color(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
     let color = match args.get_err(0, "color")? {
         Value::Color(c) => c,
@@ -133,15 +226,6 @@ pub(crate) fn adjust_color(mut args: CallArgs, parser: &mut Parser<'_>) -> SassR
     opt_rgba!(args, green, "green", -255, 255);
     opt_rgba!(args, blue, "blue", -255, 255);
 
-    if red.is_some() || green.is_some() || blue.is_some() {
-        return Ok(Value::Color(Box::new(Color::from_rgba(
-            color.red() + red.unwrap_or_else(Number::zero),
-            color.green() + green.unwrap_or_else(Number::zero),
-            color.blue() + blue.unwrap_or_else(Number::zero),
-            color.alpha() + alpha.unwrap_or_else(Number::zero),
-        ))));
-    }
-
     let hue = match args.default_named_arg("hue", Value::Null)? {
         Value::Dimension(Some(n), ..) => Some(n),
         Value::Dimension(None, ..) => todo!(),
@@ -157,8 +241,35 @@ pub(crate) fn adjust_color(mut args: CallArgs, parser: &mut Parser<'_>) -> SassR
 
     opt_hsl!(args, saturation, "saturation", -100, 100);
     opt_hsl!(args, luminance, "lightness", -100, 100);
+    opt_percent!(args, whiteness, "whiteness", -100, 100);
+    opt_percent!(args, blackness, "blackness", -100, 100);
 
-    if hue.is_some() || saturation.is_some() || luminance.is_some() {
+    let has_rgb = red.is_some() || green.is_some() || blue.is_some();
+    let has_sl = saturation.is_some() || luminance.is_some();
+    let has_wb = whiteness.is_some() || blackness.is_some();
+
+    check_color_model(has_rgb, hue.is_some(), has_sl, has_wb, args.span())?;
+
+    if has_rgb {
+        return Ok(Value::Color(Box::new(Color::from_rgba(
+            color.red() + red.unwrap_or_else(Number::zero),
+            color.green() + green.unwrap_or_else(Number::zero),
+            color.blue() + blue.unwrap_or_else(Number::zero),
+            color.alpha() + alpha.unwrap_or_else(Number::zero),
+        ))));
+    }
+
+    if has_wb {
+        let (this_hue, this_whiteness, this_blackness, this_alpha) = color.as_hwba();
+        return Ok(Value::Color(Box::new(Color::from_hwba(
+            this_hue + hue.unwrap_or_else(Number::zero),
+            this_whiteness + whiteness.unwrap_or_else(Number::zero),
+            this_blackness + blackness.unwrap_or_else(Number::zero),
+            this_alpha + alpha.unwrap_or_else(Number::zero),
+        ))));
+    }
+
+    if hue.is_some() || has_sl {
         // Color::as_hsla() returns more exact values than Color::hue(), etc.
         let (this_hue, this_saturation, this_luminance, this_alpha) = color.as_hsla();
         return Ok(Value::Color(Box::new(Color::from_hsla(
@@ -199,42 +310,22 @@ pub(crate) fn scale_color(mut args: CallArgs, parser: &mut Parser<'_>) -> SassRe
         }
     };
 
-    macro_rules! opt_scale_arg {
-        ($args:ident, $name:ident, $arg:literal, $low:literal, $high:literal) => {
-            let $name = match $args.default_named_arg($arg, Value::Null)? {
-                Value::Dimension(Some(n), Unit::Percent, _) => {
-                    Some(bound!($args, $arg, n, Unit::Percent, $low, $high) / Number::from(100))
-                }
-                Value::Dimension(None, ..) => todo!(),
-                v @ Value::Dimension(..) => {
-                    return Err((
-                        format!(
-                            "${}: Expected {} to have unit \"%\".",
-                            $arg,
-                            v.inspect($args.span())?
-                        ),
-                        $args.span(),
-                    )
-                        .into())
-                }
-                Value::Null => None,
-                v => {
-                    return Err((
-                        format!("${}: {} is not a number.", $arg, v.inspect($args.span())?),
-                        $args.span(),
-                    )
-                        .into())
-                }
-            };
-        };
-    }
+    opt_percent!(args, alpha, "alpha", -100, 100);
+    opt_percent!(args, red, "red", -100, 100);
+    opt_percent!(args, green, "green", -100, 100);
+    opt_percent!(args, blue, "blue", -100, 100);
+    opt_percent!(args, saturation, "saturation", -100, 100);
+    opt_percent!(args, luminance, "lightness", -100, 100);
+    opt_percent!(args, whiteness, "whiteness", -100, 100);
+    opt_percent!(args, blackness, "blackness", -100, 100);
+
+    let has_rgb = red.is_some() || green.is_some() || blue.is_some();
+    let has_sl = saturation.is_some() || luminance.is_some();
+    let has_wb = whiteness.is_some() || blackness.is_some();
 
-    opt_scale_arg!(args, alpha, "alpha", -100, 100);
-    opt_scale_arg!(args, red, "red", -100, 100);
-    opt_scale_arg!(args, green, "green", -100, 100);
-    opt_scale_arg!(args, blue, "blue", -100, 100);
+    check_color_model(has_rgb, false, has_sl, has_wb, span)?;
 
-    if red.is_some() || green.is_some() || blue.is_some() {
+    if has_rgb {
         return Ok(Value::Color(Box::new(Color::from_rgba(
             scale(
                 color.red(),
@@ -259,10 +350,29 @@ pub(crate) fn scale_color(mut args: CallArgs, parser: &mut Parser<'_>) -> SassRe
         ))));
     }
 
-    opt_scale_arg!(args, saturation, "saturation", -100, 100);
-    opt_scale_arg!(args, luminance, "lightness", -100, 100);
+    if has_wb {
+        let (this_hue, this_whiteness, this_blackness, this_alpha) = color.as_hwba();
+        return Ok(Value::Color(Box::new(Color::from_hwba(
+            this_hue,
+            scale(
+                this_whiteness,
+                whiteness.unwrap_or_else(Number::zero),
+                Number::one(),
+            ),
+            scale(
+                this_blackness,
+                blackness.unwrap_or_else(Number::zero),
+                Number::one(),
+            ),
+            scale(
+                this_alpha,
+                alpha.unwrap_or_else(Number::zero),
+                Number::one(),
+            ),
+        ))));
+    }
 
-    if saturation.is_some() || luminance.is_some() {
+    if has_sl {
         // Color::as_hsla() returns more exact values than Color::hue(), etc.
         let (this_hue, this_saturation, this_luminance, this_alpha) = color.as_hsla();
         return Ok(Value::Color(Box::new(Color::from_hsla(
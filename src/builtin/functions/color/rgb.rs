@@ -9,7 +9,7 @@ use crate::{
     error::SassResult,
     parse::Parser,
     unit::Unit,
-    value::{Number, Value},
+    value::{Division, Number, Value},
 };
 
 /// name: Either `rgb` or `rgba` depending on the caller
@@ -310,7 +310,7 @@ fn inner_rgb(name: &'static str, mut args: CallArgs, parser: &mut Parser<'_>) ->
         let alpha = match args.default_arg(
             3,
             "alpha",
-            Value::Dimension(Some(Number::one()), Unit::None, true),
+            Value::Dimension(Some(Number::one()), Unit::None, Division::Computed),
         )? {
             Value::Dimension(Some(n), Unit::None, _) => n,
             Value::Dimension(Some(n), Unit::Percent, _) => n / Number::from(100),
@@ -361,7 +361,11 @@ pub(crate) fn rgba(args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value>
 pub(crate) fn red(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
     args.max_args(1)?;
     match args.get_err(0, "color")? {
-        Value::Color(c) => Ok(Value::Dimension(Some(c.red()), Unit::None, true)),
+        Value::Color(c) => Ok(Value::Dimension(
+            Some(c.red()),
+            Unit::None,
+            Division::Computed,
+        )),
         v => Err((
             format!("$color: {} is not a color.", v.inspect(args.span())?),
             args.span(),
@@ -373,7 +377,11 @@ pub(crate) fn red(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Val
 pub(crate) fn green(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
     args.max_args(1)?;
     match args.get_err(0, "color")? {
-        Value::Color(c) => Ok(Value::Dimension(Some(c.green()), Unit::None, true)),
+        Value::Color(c) => Ok(Value::Dimension(
+            Some(c.green()),
+            Unit::None,
+            Division::Computed,
+        )),
         v => Err((
             format!("$color: {} is not a color.", v.inspect(args.span())?),
             args.span(),
@@ -385,7 +393,11 @@ pub(crate) fn green(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<V
 pub(crate) fn blue(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
     args.max_args(1)?;
     match args.get_err(0, "color")? {
-        Value::Color(c) => Ok(Value::Dimension(Some(c.blue()), Unit::None, true)),
+        Value::Color(c) => Ok(Value::Dimension(
+            Some(c.blue()),
+            Unit::None,
+            Division::Computed,
+        )),
         v => Err((
             format!("$color: {} is not a color.", v.inspect(args.span())?),
             args.span(),
@@ -421,7 +433,7 @@ pub(crate) fn mix(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Val
     let weight = match args.default_arg(
         2,
         "weight",
-        Value::Dimension(Some(Number::from(50)), Unit::None, true),
+        Value::Dimension(Some(Number::from(50)), Unit::None, Division::Computed),
     )? {
         Value::Dimension(Some(n), u, _) => bound!(args, "weight", n, u, 0, 100) / Number::from(100),
         Value::Dimension(None, ..) => todo!(),
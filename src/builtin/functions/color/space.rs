@@ -0,0 +1,438 @@
+use super::{Builtin, GlobalFunctionMap};
+
+use num_traits::One;
+
+use codemap::Span;
+
+use crate::{
+    args::CallArgs,
+    color::{finite_number, Color, ColorSpace, GamutMethod},
+    common::{ListSeparator, QuoteKind},
+    error::SassResult,
+    parse::Parser,
+    unit::Unit,
+    value::{Division, Number, Value},
+};
+
+/// A function call to return as plain CSS, such as when one of the
+/// channels is `var(--foo)`
+fn css_function(name: &str, channels: &Value, alpha: &Value, span: Span) -> SassResult<Value> {
+    let mut string = format!("{}({}", name, channels.to_css_string(span)?);
+    if *alpha != Value::Null {
+        string.push_str(" / ");
+        string.push_str(&alpha.to_css_string(span)?);
+    }
+    string.push(')');
+    Ok(Value::String(string, QuoteKind::None))
+}
+
+/// Parse `value` as channel `index` of `space`, scaled as described
+/// in `color::space`
+fn parse_channel(space: ColorSpace, index: usize, value: Value, span: Span) -> SassResult<f64> {
+    let name = space.channel_names()[index];
+
+    let (number, unit) = match &value {
+        // a missing channel, which is treated as 0 by conversions
+        Value::String(s, QuoteKind::None) if s.eq_ignore_ascii_case("none") => return Ok(f64::NAN),
+        Value::Dimension(Some(n), u, _) if n.is_finite() => (n.clone(), u.clone()),
+        Value::Dimension(..) => {
+            return Err((
+                format!(
+                    "${}: Expected {} to be a finite number.",
+                    name,
+                    value.inspect(span)?
+                ),
+                span,
+            )
+                .into())
+        }
+        v => {
+            return Err((
+                format!("${}: {} is not a number.", name, v.inspect(span)?),
+                span,
+            )
+                .into())
+        }
+    };
+
+    if name == "hue" {
+        let degrees = match unit {
+            Unit::None => number,
+            Unit::Deg | Unit::Grad | Unit::Rad | Unit::Turn => number.convert(&unit, &Unit::Deg),
+            _ => {
+                return Err((
+                    format!(
+                        "$hue: Expected {} to have an angle unit (deg, grad, rad, turn).",
+                        value.inspect(span)?
+                    ),
+                    span,
+                )
+                    .into())
+            }
+        };
+        return Ok(degrees.as_float().unwrap_or(0.0).rem_euclid(360.0));
+    }
+
+    // the value that 100% corresponds to
+    let percent_reference = match (space, index) {
+        (ColorSpace::Lab, 0) | (ColorSpace::Lch, 0) => 100.0,
+        (ColorSpace::Lab, _) => 125.0,
+        (ColorSpace::Lch, _) => 150.0,
+        (ColorSpace::Oklab, 0) | (ColorSpace::Oklch, 0) => 1.0,
+        (ColorSpace::Oklab, _) | (ColorSpace::Oklch, _) => 0.4,
+        _ => 1.0,
+    };
+
+    let channel = match unit {
+        Unit::None => number.as_float(),
+        Unit::Percent => (number / Number::from(100))
+            .as_float()
+            .map(|n| n * percent_reference),
+        _ => {
+            return Err((
+                format!(
+                    "${}: Expected {} to have no units or \"%\".",
+                    name,
+                    value.inspect(span)?
+                ),
+                span,
+            )
+                .into())
+        }
+    }
+    .unwrap_or(0.0);
+
+    Ok(match (space, name) {
+        (ColorSpace::Lab, "lightness") | (ColorSpace::Lch, "lightness") => {
+            channel.clamp(0.0, 100.0)
+        }
+        (ColorSpace::Oklab, "lightness") | (ColorSpace::Oklch, "lightness") => {
+            channel.clamp(0.0, 1.0)
+        }
+        (_, "chroma") => channel.max(0.0),
+        _ => channel,
+    })
+}
+
+fn parse_alpha(alpha: Value, span: Span) -> SassResult<Number> {
+    match alpha {
+        Value::Null => Ok(Number::one()),
+        v @ Value::Dimension(None, ..) => Err((
+            format!(
+                "$alpha: Expected {} to be a finite number.",
+                v.inspect(span)?
+            ),
+            span,
+        )
+            .into()),
+        Value::Dimension(Some(n), Unit::None, _) => Ok(n),
+        Value::Dimension(Some(n), Unit::Percent, _) => Ok(n / Number::from(100)),
+        v @ Value::Dimension(..) => Err((
+            format!(
+                "$alpha: Expected {} to have no units or \"%\".",
+                v.inspect(span)?
+            ),
+            span,
+        )
+            .into()),
+        v => Err((
+            format!("$alpha: {} is not a number.", v.inspect(span)?),
+            span,
+        )
+            .into()),
+    }
+}
+
+/// space: `None` for `color()`, where the space is the first channel
+fn inner_space(
+    name: &'static str,
+    space: Option<ColorSpace>,
+    mut args: CallArgs,
+) -> SassResult<Value> {
+    args.max_args(1)?;
+    let span = args.span();
+
+    let (channels, alpha) = match args.get_err(0, "channels")? {
+        Value::List(mut v, ListSeparator::Slash, ..) if v.len() == 2 => {
+            let alpha = v.pop().unwrap_or(Value::Null);
+            (v.pop().unwrap_or(Value::Null), alpha)
+        }
+        Value::List(v, ListSeparator::Slash, ..) => {
+            return Err((
+                format!(
+                    "Only 2 slash-separated elements allowed, but {} were passed.",
+                    v.len()
+                ),
+                span,
+            )
+                .into())
+        }
+        v => (v, Value::Null),
+    };
+
+    let mut elements = match &channels {
+        Value::List(_, ListSeparator::Comma, ..) => {
+            return Err(("$channels must be a space-separated list.", span).into())
+        }
+        Value::List(v, ..) => v.clone(),
+        v => vec![v.clone()],
+    }
+    .into_iter();
+
+    if elements.as_slice().iter().any(Value::is_special_function) || alpha.is_special_function() {
+        return css_function(name, &channels, &alpha, span);
+    }
+
+    let space = match space {
+        Some(space) => space,
+        None => match elements.next() {
+            Some(Value::String(s, ..)) => match ColorSpace::from_name(&s) {
+                Some(ColorSpace::DisplayP3) => ColorSpace::DisplayP3,
+                // the other predefined spaces aren't supported, and
+                // are emitted as plain CSS
+                _ => return css_function(name, &channels, &alpha, span),
+            },
+            Some(v) => {
+                return Err((
+                    format!("$space: {} is not a string.", v.inspect(span)?),
+                    span,
+                )
+                    .into())
+            }
+            None => return Err(("Missing element $space.", span).into()),
+        },
+    };
+
+    if elements.len() > 3 {
+        return Err((
+            format!(
+                "Only 3 elements allowed, but {} were passed.",
+                elements.len()
+            ),
+            span,
+        )
+            .into());
+    }
+
+    let mut parsed = [0.0; 3];
+
+    for (index, channel_name) in space.channel_names().iter().enumerate() {
+        let value = match elements.next() {
+            Some(v) => v,
+            None => return Err((format!("Missing element ${}.", channel_name), span).into()),
+        };
+        parsed[index] = parse_channel(space, index, value, span)?;
+    }
+
+    Ok(Value::Color(Box::new(Color::from_space(
+        space,
+        parsed,
+        parse_alpha(alpha, span)?,
+    ))))
+}
+
+pub(crate) fn lab(args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
+    inner_space("lab", Some(ColorSpace::Lab), args)
+}
+
+pub(crate) fn lch(args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
+    inner_space("lch", Some(ColorSpace::Lch), args)
+}
+
+pub(crate) fn oklab(args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
+    inner_space("oklab", Some(ColorSpace::Oklab), args)
+}
+
+pub(crate) fn oklch(args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
+    inner_space("oklch", Some(ColorSpace::Oklch), args)
+}
+
+pub(crate) fn color(args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
+    inner_space("color", None, args)
+}
+
+fn color_arg(args: &mut CallArgs) -> SassResult<Box<Color>> {
+    match args.get_err(0, "color")? {
+        Value::Color(c) => Ok(c),
+        v => Err((
+            format!("$color: {} is not a color.", v.inspect(args.span())?),
+            args.span(),
+        )
+            .into()),
+    }
+}
+
+fn parse_space(space: Value, span: Span) -> SassResult<ColorSpace> {
+    match space {
+        Value::String(s, ..) => ColorSpace::from_name(&s)
+            .ok_or_else(|| (format!("$space: Unknown color space \"{}\".", s), span).into()),
+        v => Err((
+            format!("$space: {} is not a string.", v.inspect(span)?),
+            span,
+        )
+            .into()),
+    }
+}
+
+/// The space named by the optional argument `$space`, or the space
+/// of `color` if none is given
+fn optional_space_arg(
+    args: &mut CallArgs,
+    position: usize,
+    color: &Color,
+) -> SassResult<ColorSpace> {
+    match args.default_arg(position, "space", Value::Null)? {
+        Value::Null => Ok(color.space()),
+        v => parse_space(v, args.span()),
+    }
+}
+
+pub(crate) fn to_space(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
+    args.max_args(2)?;
+    let color = color_arg(&mut args)?;
+    let space = parse_space(args.get_err(1, "space")?, args.span())?;
+    Ok(Value::Color(Box::new(color.to_space(space))))
+}
+
+pub(crate) fn channel(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
+    args.max_args(3)?;
+    let color = color_arg(&mut args)?;
+    let channel = match args.get_err(1, "channel")? {
+        Value::String(s, ..) => s,
+        v => {
+            return Err((
+                format!("$channel: {} is not a string.", v.inspect(args.span())?),
+                args.span(),
+            )
+                .into())
+        }
+    };
+    let space = optional_space_arg(&mut args, 2, &color)?;
+
+    if channel == "alpha" {
+        return Ok(Value::Dimension(
+            Some(color.alpha()),
+            Unit::None,
+            Division::Computed,
+        ));
+    }
+
+    let index = match space
+        .channel_names()
+        .iter()
+        .position(|name| *name == channel)
+    {
+        Some(index) => index,
+        None => {
+            return Err((
+                format!(
+                    "$channel: Color {} has no channel named {}.",
+                    color, channel
+                ),
+                args.span(),
+            )
+                .into())
+        }
+    };
+
+    let (number, unit) = match space {
+        ColorSpace::Rgb => {
+            let color = color.to_space(space);
+            let value = match index {
+                0 => color.red(),
+                1 => color.green(),
+                _ => color.blue(),
+            };
+            (value, Unit::None)
+        }
+        ColorSpace::Hsl => {
+            let (hue, saturation, lightness, _) = color.to_space(space).as_hsla();
+            match index {
+                0 => (hue, Unit::Deg),
+                1 => (saturation * Number::from(100), Unit::Percent),
+                _ => (lightness * Number::from(100), Unit::Percent),
+            }
+        }
+        ColorSpace::Hwb => {
+            let (hue, whiteness, blackness, _) = color.to_space(space).as_hwba();
+            match index {
+                0 => (hue, Unit::Deg),
+                1 => (whiteness * Number::from(100), Unit::Percent),
+                _ => (blackness * Number::from(100), Unit::Percent),
+            }
+        }
+        ColorSpace::Lab
+        | ColorSpace::Lch
+        | ColorSpace::Oklab
+        | ColorSpace::Oklch
+        | ColorSpace::DisplayP3 => {
+            let value = color.channels(space)[index];
+            match channel.as_str() {
+                "lightness" if matches!(space, ColorSpace::Oklab | ColorSpace::Oklch) => {
+                    (finite_number(value * 100.0), Unit::Percent)
+                }
+                "lightness" => (finite_number(value), Unit::Percent),
+                "hue" => (finite_number(value), Unit::Deg),
+                _ => (finite_number(value), Unit::None),
+            }
+        }
+    };
+
+    Ok(Value::Dimension(Some(number), unit, Division::Computed))
+}
+
+pub(crate) fn space(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
+    args.max_args(1)?;
+    let color = color_arg(&mut args)?;
+    Ok(Value::String(
+        color.space().name().to_owned(),
+        QuoteKind::None,
+    ))
+}
+
+pub(crate) fn is_in_gamut(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
+    args.max_args(2)?;
+    let color = color_arg(&mut args)?;
+    let space = optional_space_arg(&mut args, 1, &color)?;
+    Ok(Value::bool(color.is_in_gamut(space)))
+}
+
+pub(crate) fn to_gamut(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
+    args.max_args(3)?;
+    let color = color_arg(&mut args)?;
+    let space = optional_space_arg(&mut args, 1, &color)?;
+    let method = match args.default_arg(2, "method", Value::Null)? {
+        Value::Null => return Err((
+            "$method: color.to-gamut() requires a $method argument for forwards-compatibility with changes in the CSS spec. Suggestion:\n\n$method: local-minde",
+            args.span(),
+        )
+            .into()),
+        Value::String(s, ..) => match s.as_str() {
+            "clip" => GamutMethod::Clip,
+            "local-minde" => GamutMethod::LocalMinde,
+            _ => {
+                return Err((
+                    format!("$method: Unknown gamut map method \"{}\".", s),
+                    args.span(),
+                )
+                    .into())
+            }
+        },
+        v => {
+            return Err((
+                format!("$method: {} is not a string.", v.inspect(args.span())?),
+                args.span(),
+            )
+                .into())
+        }
+    };
+    Ok(Value::Color(Box::new(color.to_gamut(space, method))))
+}
+
+pub(crate) fn declare(f: &mut GlobalFunctionMap) {
+    f.insert("lab", Builtin::with_slash_channels(lab));
+    f.insert("lch", Builtin::with_slash_channels(lch));
+    f.insert("oklab", Builtin::with_slash_channels(oklab));
+    f.insert("oklch", Builtin::with_slash_channels(oklch));
+    f.insert("color", Builtin::with_slash_channels(color));
+}
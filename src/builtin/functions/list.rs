@@ -8,7 +8,7 @@ use crate::{
     error::SassResult,
     parse::Parser,
     unit::Unit,
-    value::{Number, Value},
+    value::{Division, Number, Value},
 };
 
 pub(crate) fn length(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
@@ -16,7 +16,7 @@ pub(crate) fn length(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<
     Ok(Value::Dimension(
         Some(Number::from(args.get_err(0, "list")?.as_list().len())),
         Unit::None,
-        true,
+        Division::Computed,
     ))
 }
 
@@ -142,9 +142,10 @@ pub(crate) fn append(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<
             "auto" => sep,
             "comma" => ListSeparator::Comma,
             "space" => ListSeparator::Space,
+            "slash" => ListSeparator::Slash,
             _ => {
                 return Err((
-                    "$separator: Must be \"space\", \"comma\", or \"auto\".",
+                    "$separator: Must be \"space\", \"comma\", \"slash\", or \"auto\".",
                     args.span(),
                 )
                     .into())
@@ -191,9 +192,10 @@ pub(crate) fn join(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Va
             }
             "comma" => ListSeparator::Comma,
             "space" => ListSeparator::Space,
+            "slash" => ListSeparator::Slash,
             _ => {
                 return Err((
-                    "$separator: Must be \"space\", \"comma\", or \"auto\".",
+                    "$separator: Must be \"space\", \"comma\", \"slash\", or \"auto\".",
                     args.span(),
                 )
                     .into())
@@ -250,7 +252,11 @@ pub(crate) fn index(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<V
         Some(v) => Number::from(v + 1),
         None => return Ok(Value::Null),
     };
-    Ok(Value::Dimension(Some(index), Unit::None, true))
+    Ok(Value::Dimension(
+        Some(index),
+        Unit::None,
+        Division::Computed,
+    ))
 }
 
 pub(crate) fn zip(args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
@@ -280,6 +286,21 @@ pub(crate) fn zip(args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value>
     Ok(Value::List(result, ListSeparator::Comma, Brackets::None))
 }
 
+pub(crate) fn slash(args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
+    let span = args.span();
+    let elements = args
+        .get_variadic()?
+        .into_iter()
+        .map(|x| x.node)
+        .collect::<Vec<Value>>();
+
+    if elements.len() < 2 {
+        return Err(("At least two elements are required.", span).into());
+    }
+
+    Ok(Value::List(elements, ListSeparator::Slash, Brackets::None))
+}
+
 pub(crate) fn declare(f: &mut GlobalFunctionMap) {
     f.insert("length", Builtin::new(length));
     f.insert("nth", Builtin::new(nth));
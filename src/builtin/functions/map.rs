@@ -9,39 +9,37 @@ use crate::{
 };
 
 pub(crate) fn map_get(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
-    args.max_args(2)?;
+    let span = args.span();
     let key = args.get_err(1, "key")?;
     let map = match args.get_err(0, "map")? {
         Value::Map(m) => m,
         Value::List(v, ..) if v.is_empty() => SassMap::new(),
         Value::ArgList(v) if v.is_empty() => SassMap::new(),
-        v => {
-            return Err((
-                format!("$map: {} is not a map.", v.inspect(args.span())?),
-                args.span(),
-            )
-                .into())
-        }
+        v => return Err((format!("$map: {} is not a map.", v.inspect(span)?), span).into()),
     };
-    Ok(map.get(&key)?.unwrap_or(Value::Null))
+    let keys = key_path(key, args)?;
+    Ok(map.get_nested(&keys).cloned().unwrap_or(Value::Null))
 }
 
 pub(crate) fn map_has_key(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
-    args.max_args(2)?;
+    let span = args.span();
     let key = args.get_err(1, "key")?;
     let map = match args.get_err(0, "map")? {
         Value::Map(m) => m,
         Value::List(v, ..) if v.is_empty() => SassMap::new(),
         Value::ArgList(v) if v.is_empty() => SassMap::new(),
-        v => {
-            return Err((
-                format!("$map: {} is not a map.", v.inspect(args.span())?),
-                args.span(),
-            )
-                .into())
-        }
+        v => return Err((format!("$map: {} is not a map.", v.inspect(span)?), span).into()),
     };
-    Ok(Value::bool(map.get(&key)?.is_some()))
+    let keys = key_path(key, args)?;
+    Ok(Value::bool(map.get_nested(&keys).is_some()))
+}
+
+/// The keys `$key, $keys...` passed after a map, which form a path
+/// through nested maps
+fn key_path(key: Value, args: CallArgs) -> SassResult<Vec<Value>> {
+    let mut keys = vec![key];
+    keys.extend(args.get_variadic()?.into_iter().map(|key| key.node));
+    Ok(keys)
 }
 
 pub(crate) fn map_keys(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
@@ -87,33 +85,123 @@ pub(crate) fn map_values(mut args: CallArgs, parser: &mut Parser<'_>) -> SassRes
 }
 
 pub(crate) fn map_merge(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
-    args.max_args(2)?;
-    let mut map1 = match args.get_err(0, "map1")? {
+    let span = args.span();
+
+    // `map-merge($map1, $map2)`, rather than `map-merge($map1, $keys..., $map2)`
+    let is_shallow = args.len() == 2;
+
+    let map1 = match args.get_err(0, "map1")? {
         Value::Map(m) => m,
         Value::List(v, ..) if v.is_empty() => SassMap::new(),
         Value::ArgList(v) if v.is_empty() => SassMap::new(),
-        v => {
-            return Err((
-                format!("$map1: {} is not a map.", v.inspect(args.span())?),
-                args.span(),
-            )
-                .into())
+        v => return Err((format!("$map1: {} is not a map.", v.inspect(span)?), span).into()),
+    };
+
+    let (keys, map2) = if is_shallow {
+        (Vec::new(), args.get_err(1, "map2")?)
+    } else {
+        let mut args = args
+            .get_variadic()?
+            .into_iter()
+            .map(|arg| arg.node)
+            .collect::<Vec<Value>>();
+        match args.len() {
+            0 => return Err(("Expected $args to contain a key.", span).into()),
+            1 => return Err(("Expected $args to contain a map.", span).into()),
+            _ => {
+                let map2 = args.pop().unwrap();
+                (args, map2)
+            }
         }
     };
-    let map2 = match args.get_err(1, "map2")? {
+
+    let map2 = match map2 {
         Value::Map(m) => m,
         Value::List(v, ..) if v.is_empty() => SassMap::new(),
         Value::ArgList(v) if v.is_empty() => SassMap::new(),
-        v => {
-            return Err((
-                format!("$map2: {} is not a map.", v.inspect(args.span())?),
-                args.span(),
-            )
-                .into())
+        v => return Err((format!("$map2: {} is not a map.", v.inspect(span)?), span).into()),
+    };
+
+    Ok(map1.modify_nested(&keys, true, |old| match old.try_map() {
+        Some(mut old) => {
+            old.merge(map2);
+            Value::Map(old)
+        }
+        None => Value::Map(map2),
+    }))
+}
+
+pub(crate) fn map_set(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
+    let span = args.span();
+
+    // `map.set($map, $key, $value)`, rather than `map.set($map, $keys..., $value)`
+    let is_shallow = args.len() == 3;
+
+    let map = match args.get_err(0, "map")? {
+        Value::Map(m) => m,
+        Value::List(v, ..) if v.is_empty() => SassMap::new(),
+        Value::ArgList(v) if v.is_empty() => SassMap::new(),
+        v => return Err((format!("$map: {} is not a map.", v.inspect(span)?), span).into()),
+    };
+
+    let (keys, value) = if is_shallow {
+        (vec![args.get_err(1, "key")?], args.get_err(2, "value")?)
+    } else {
+        let mut args = args
+            .get_variadic()?
+            .into_iter()
+            .map(|arg| arg.node)
+            .collect::<Vec<Value>>();
+        match args.len() {
+            0 => return Err(("Expected $args to contain a key.", span).into()),
+            1 => return Err(("Expected $args to contain a value.", span).into()),
+            _ => {
+                let value = args.pop().unwrap();
+                (args, value)
+            }
         }
     };
-    map1.merge(map2);
-    Ok(Value::Map(map1))
+
+    Ok(map.modify_nested(&keys, true, |_| value))
+}
+
+pub(crate) fn map_deep_merge(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
+    args.max_args(2)?;
+    let span = args.span();
+    let map1 = match args.get_err(0, "map1")? {
+        Value::Map(m) => m,
+        Value::List(v, ..) if v.is_empty() => SassMap::new(),
+        Value::ArgList(v) if v.is_empty() => SassMap::new(),
+        v => return Err((format!("$map1: {} is not a map.", v.inspect(span)?), span).into()),
+    };
+    let map2 = match args.get_err(1, "map2")? {
+        Value::Map(m) => m,
+        Value::List(v, ..) if v.is_empty() => SassMap::new(),
+        Value::ArgList(v) if v.is_empty() => SassMap::new(),
+        v => return Err((format!("$map2: {} is not a map.", v.inspect(span)?), span).into()),
+    };
+    Ok(Value::Map(map1.deep_merge(map2)))
+}
+
+pub(crate) fn map_deep_remove(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
+    let span = args.span();
+    let map = match args.get_err(0, "map")? {
+        Value::Map(m) => m,
+        Value::List(v, ..) if v.is_empty() => SassMap::new(),
+        Value::ArgList(v) if v.is_empty() => SassMap::new(),
+        v => return Err((format!("$map: {} is not a map.", v.inspect(span)?), span).into()),
+    };
+    let key = args.get_err(1, "key")?;
+    let mut keys = key_path(key, args)?;
+    let last = keys.pop().unwrap();
+
+    Ok(map.modify_nested(&keys, false, |value| match value {
+        Value::Map(mut nested) if nested.get(&last).is_some() => {
+            nested.remove(&last);
+            Value::Map(nested)
+        }
+        value => value,
+    }))
 }
 
 pub(crate) fn map_remove(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
@@ -10,7 +10,7 @@ use crate::{
     error::SassResult,
     parse::{HigherIntermediateValue, Parser, ValueVisitor},
     unit::Unit,
-    value::{Number, Value},
+    value::{Division, Number, Value},
 };
 
 pub(crate) fn percentage(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
@@ -36,13 +36,15 @@ pub(crate) fn percentage(mut args: CallArgs, parser: &mut Parser<'_>) -> SassRes
                 .into())
         }
     };
-    Ok(Value::Dimension(num, Unit::Percent, true))
+    Ok(Value::Dimension(num, Unit::Percent, Division::Computed))
 }
 
 pub(crate) fn round(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
     args.max_args(1)?;
     match args.get_err(0, "number")? {
-        Value::Dimension(Some(n), u, _) => Ok(Value::Dimension(Some(n.round()), u, true)),
+        Value::Dimension(Some(n), u, _) => {
+            Ok(Value::Dimension(Some(n.round()), u, Division::Computed))
+        }
         Value::Dimension(None, ..) => Err(("Infinity or NaN toInt", args.span()).into()),
         v => Err((
             format!("$number: {} is not a number.", v.inspect(args.span())?),
@@ -55,7 +57,9 @@ pub(crate) fn round(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<V
 pub(crate) fn ceil(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
     args.max_args(1)?;
     match args.get_err(0, "number")? {
-        Value::Dimension(Some(n), u, _) => Ok(Value::Dimension(Some(n.ceil()), u, true)),
+        Value::Dimension(Some(n), u, _) => {
+            Ok(Value::Dimension(Some(n.ceil()), u, Division::Computed))
+        }
         Value::Dimension(None, ..) => Err(("Infinity or NaN toInt", args.span()).into()),
         v => Err((
             format!("$number: {} is not a number.", v.inspect(args.span())?),
@@ -68,7 +72,9 @@ pub(crate) fn ceil(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Va
 pub(crate) fn floor(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
     args.max_args(1)?;
     match args.get_err(0, "number")? {
-        Value::Dimension(Some(n), u, _) => Ok(Value::Dimension(Some(n.floor()), u, true)),
+        Value::Dimension(Some(n), u, _) => {
+            Ok(Value::Dimension(Some(n.floor()), u, Division::Computed))
+        }
         Value::Dimension(None, ..) => Err(("Infinity or NaN toInt", args.span()).into()),
         v => Err((
             format!("$number: {} is not a number.", v.inspect(args.span())?),
@@ -81,8 +87,10 @@ pub(crate) fn floor(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<V
 pub(crate) fn abs(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
     args.max_args(1)?;
     match args.get_err(0, "number")? {
-        Value::Dimension(Some(n), u, _) => Ok(Value::Dimension(Some(n.abs()), u, true)),
-        Value::Dimension(None, u, ..) => Ok(Value::Dimension(None, u, true)),
+        Value::Dimension(Some(n), u, _) => {
+            Ok(Value::Dimension(Some(n.abs()), u, Division::Computed))
+        }
+        Value::Dimension(None, u, ..) => Ok(Value::Dimension(None, u, Division::Computed)),
         v => Err((
             format!("$number: {} is not a number.", v.inspect(args.span())?),
             args.span(),
@@ -131,7 +139,7 @@ pub(crate) fn random(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<
             return Ok(Value::Dimension(
                 Some(Number::from(rng.gen_range(0.0, 1.0))),
                 Unit::None,
-                true,
+                Division::Computed,
             ));
         }
         v => {
@@ -144,7 +152,11 @@ pub(crate) fn random(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<
     };
 
     if limit.is_one() {
-        return Ok(Value::Dimension(Some(Number::one()), Unit::None, true));
+        return Ok(Value::Dimension(
+            Some(Number::one()),
+            Unit::None,
+            Division::Computed,
+        ));
     }
 
     if limit.is_decimal() {
@@ -174,7 +186,7 @@ pub(crate) fn random(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<
     Ok(Value::Dimension(
         Some(Number::from(rng.gen_range(0, limit) + 1)),
         Unit::None,
-        true,
+        Division::Computed,
     ))
 }
 
@@ -193,7 +205,7 @@ pub(crate) fn min(args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value>
 
     let mut min = match nums.next() {
         Some((Some(n), u)) => (n, u),
-        Some((None, u)) => return Ok(Value::Dimension(None, u, true)),
+        Some((None, u)) => return Ok(Value::Dimension(None, u, Division::Computed)),
         None => unreachable!(),
     };
 
@@ -208,12 +220,12 @@ pub(crate) fn min(args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value>
                 HigherIntermediateValue::Literal(Value::Dimension(
                     Some(num.clone()),
                     unit.clone(),
-                    true,
+                    Division::Computed,
                 )),
                 HigherIntermediateValue::Literal(Value::Dimension(
                     Some(min.0.clone()),
                     min.1.clone(),
-                    true,
+                    Division::Computed,
                 )),
             )?
             .is_true()
@@ -221,7 +233,7 @@ pub(crate) fn min(args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value>
             min = (num, unit);
         }
     }
-    Ok(Value::Dimension(Some(min.0), min.1, true))
+    Ok(Value::Dimension(Some(min.0), min.1, Division::Computed))
 }
 
 pub(crate) fn max(args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
@@ -239,7 +251,7 @@ pub(crate) fn max(args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value>
 
     let mut max = match nums.next() {
         Some((Some(n), u)) => (n, u),
-        Some((None, u)) => return Ok(Value::Dimension(None, u, true)),
+        Some((None, u)) => return Ok(Value::Dimension(None, u, Division::Computed)),
         None => unreachable!(),
     };
 
@@ -254,12 +266,12 @@ pub(crate) fn max(args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value>
                 HigherIntermediateValue::Literal(Value::Dimension(
                     Some(num.clone()),
                     unit.clone(),
-                    true,
+                    Division::Computed,
                 )),
                 HigherIntermediateValue::Literal(Value::Dimension(
                     Some(max.0.clone()),
                     max.1.clone(),
-                    true,
+                    Division::Computed,
                 )),
             )?
             .is_true()
@@ -267,7 +279,7 @@ pub(crate) fn max(args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value>
             max = (num, unit);
         }
     }
-    Ok(Value::Dimension(Some(max.0), max.1, true))
+    Ok(Value::Dimension(Some(max.0), max.1, Division::Computed))
 }
 
 pub(crate) fn declare(f: &mut GlobalFunctionMap) {
@@ -174,9 +174,13 @@ pub(crate) fn mixin_exists(mut args: CallArgs, parser: &mut Parser<'_>) -> SassR
 pub(crate) fn function_exists(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
     args.max_args(2)?;
     match args.get_err(0, "name")? {
-        Value::String(s, _) => Ok(Value::bool(
-            parser.scopes.fn_exists(s.into(), parser.global_scope),
-        )),
+        Value::String(s, _) => {
+            let name = Identifier::from(s);
+            Ok(Value::bool(
+                parser.scopes.fn_exists(name, parser.global_scope)
+                    || parser.options.custom_function(name).is_some(),
+            ))
+        }
         v => Err((
             format!("$name: {} is not a string.", v.inspect(args.span())?),
             args.span(),
@@ -230,6 +234,7 @@ pub(crate) fn get_function(mut args: CallArgs, parser: &mut Parser<'_>) -> SassR
         parser.scopes.get_fn(name, parser.global_scope)
     } {
         Some(f) => f,
+        None if parser.options.custom_function(name).is_some() => SassFunction::Custom(name),
         None => match GLOBAL_FUNCTIONS.get(name.as_str()) {
             Some(f) => SassFunction::Builtin(f.clone(), name),
             None => return Err((format!("Function not found: {}", name), args.span()).into()),
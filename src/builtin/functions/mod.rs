@@ -30,12 +30,24 @@ static FUNCTION_COUNT: AtomicUsize = AtomicUsize::new(0);
 pub(crate) struct Builtin(
     pub fn(CallArgs, &mut Parser<'_>) -> SassResult<Value>,
     usize,
+    /// Whether a trailing `a / b` in the arguments is passed as a
+    /// slash-separated list rather than divided
+    pub bool,
 );
 
 impl Builtin {
     pub fn new(body: fn(CallArgs, &mut Parser<'_>) -> SassResult<Value>) -> Builtin {
         let count = FUNCTION_COUNT.fetch_add(1, Ordering::Relaxed);
-        Self(body, count)
+        Self(body, count, false)
+    }
+
+    /// A function such as `lab()` whose channels may be followed by `/`
+    /// and the alpha channel, e.g. `lab(50% 40 59.5 / 0.5)`
+    pub fn with_slash_channels(
+        body: fn(CallArgs, &mut Parser<'_>) -> SassResult<Value>,
+    ) -> Builtin {
+        let count = FUNCTION_COUNT.fetch_add(1, Ordering::Relaxed);
+        Self(body, count, true)
     }
 }
 
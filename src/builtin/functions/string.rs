@@ -12,7 +12,7 @@ use crate::{
     error::SassResult,
     parse::Parser,
     unit::Unit,
-    value::{Number, Value},
+    value::{Division, Number, Value},
 };
 
 pub(crate) fn to_upper_case(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
@@ -51,7 +51,7 @@ pub(crate) fn str_length(mut args: CallArgs, parser: &mut Parser<'_>) -> SassRes
         Value::String(i, _) => Ok(Value::Dimension(
             Some(Number::from(i.chars().count())),
             Unit::None,
-            true,
+            Division::Computed,
         )),
         v => Err((
             format!("$string: {} is not a string.", v.inspect(args.span())?),
@@ -209,7 +209,7 @@ pub(crate) fn str_index(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResu
     };
 
     Ok(match s1.find(&substr) {
-        Some(v) => Value::Dimension(Some(Number::from(v + 1)), Unit::None, true),
+        Some(v) => Value::Dimension(Some(Number::from(v + 1)), Unit::None, Division::Computed),
         None => Value::Null,
     })
 }
@@ -1,5 +1,5 @@
 use crate::builtin::{
-    list::{append, index, is_bracketed, join, length, list_separator, nth, set_nth, zip},
+    list::{append, index, is_bracketed, join, length, list_separator, nth, set_nth, slash, zip},
     modules::Module,
 };
 
@@ -12,5 +12,6 @@ pub(crate) fn declare(f: &mut Module) {
     f.insert_builtin("separator", list_separator);
     f.insert_builtin("nth", nth);
     f.insert_builtin("set-nth", set_nth);
+    f.insert_builtin("slash", slash);
     f.insert_builtin("zip", zip);
 }
@@ -1,5 +1,8 @@
 use crate::builtin::{
-    map::{map_get, map_has_key, map_keys, map_merge, map_remove, map_values},
+    map::{
+        map_deep_merge, map_deep_remove, map_get, map_has_key, map_keys, map_merge, map_remove,
+        map_set, map_values,
+    },
     modules::Module,
 };
 
@@ -10,4 +13,7 @@ pub(crate) fn declare(f: &mut Module) {
     f.insert_builtin("merge", map_merge);
     f.insert_builtin("remove", map_remove);
     f.insert_builtin("values", map_values);
+    f.insert_builtin("set", map_set);
+    f.insert_builtin("deep-merge", map_deep_merge);
+    f.insert_builtin("deep-remove", map_deep_remove);
 }
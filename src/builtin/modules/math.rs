@@ -11,9 +11,9 @@ use crate::{
     },
     common::Op,
     error::SassResult,
-    parse::Parser,
+    parse::{divide, HigherIntermediateValue, Parser, ValueVisitor},
     unit::Unit,
-    value::{Number, Value},
+    value::{Division, Number, Value},
 };
 
 #[cfg(feature = "random")]
@@ -101,6 +101,35 @@ fn clamp(mut args: CallArgs, _: &mut Parser<'_>) -> SassResult<Value> {
     Ok(number)
 }
 
+fn div(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
+    args.max_args(2)?;
+    let span = args.span();
+
+    let number1 = args.get_err(0, "number1")?;
+    let number2 = args.get_err(1, "number2")?;
+
+    match (number1, number2) {
+        (Value::Dimension(num, unit, _), Value::Dimension(num2, unit2, _)) => {
+            Ok(divide(num, unit, num2, unit2))
+        }
+        (number1, number2) => {
+            parser.deprecation_warning(
+                "math.div() will only support number arguments in a future release.\n\
+                Use list.slash() instead for a slash separator.",
+                span,
+            );
+            ValueVisitor::new(parser, span).eval(
+                HigherIntermediateValue::BinaryOp(
+                    Box::new(HigherIntermediateValue::Literal(number1)),
+                    Op::Div,
+                    Box::new(HigherIntermediateValue::Literal(number2)),
+                ),
+                true,
+            )
+        }
+    }
+}
+
 fn hypot(args: CallArgs, _: &mut Parser<'_>) -> SassResult<Value> {
     args.min_args(1)?;
 
@@ -115,7 +144,7 @@ fn hypot(args: CallArgs, _: &mut Parser<'_>) -> SassResult<Value> {
 
     let first: (Number, Unit) = match numbers.next().unwrap()? {
         (Some(n), u) => (n.clone() * n, u),
-        (None, u) => return Ok(Value::Dimension(None, u, true)),
+        (None, u) => return Ok(Value::Dimension(None, u, Division::Computed)),
     };
 
     let rest = numbers
@@ -164,12 +193,12 @@ fn hypot(args: CallArgs, _: &mut Parser<'_>) -> SassResult<Value> {
 
     let rest = match rest {
         Some(v) => v,
-        None => return Ok(Value::Dimension(None, first.1, true)),
+        None => return Ok(Value::Dimension(None, first.1, Division::Computed)),
     };
 
     let sum = first.0 + rest.into_iter().fold(Number::zero(), |a, b| a + b);
 
-    Ok(Value::Dimension(sum.sqrt(), first.1, true))
+    Ok(Value::Dimension(sum.sqrt(), first.1, Division::Computed))
 }
 
 fn log(mut args: CallArgs, _: &mut Parser<'_>) -> SassResult<Value> {
@@ -235,7 +264,7 @@ fn log(mut args: CallArgs, _: &mut Parser<'_>) -> SassResult<Value> {
             number.ln()
         },
         Unit::None,
-        true,
+        Division::Computed,
     ))
 }
 
@@ -254,7 +283,9 @@ fn pow(mut args: CallArgs, _: &mut Parser<'_>) -> SassResult<Value> {
             )
                 .into())
         }
-        Value::Dimension(None, ..) => return Ok(Value::Dimension(None, Unit::None, true)),
+        Value::Dimension(None, ..) => {
+            return Ok(Value::Dimension(None, Unit::None, Division::Computed))
+        }
         v => {
             return Err((
                 format!("$base: {} is not a number.", v.inspect(args.span())?),
@@ -276,7 +307,9 @@ fn pow(mut args: CallArgs, _: &mut Parser<'_>) -> SassResult<Value> {
             )
                 .into())
         }
-        Value::Dimension(None, ..) => return Ok(Value::Dimension(None, Unit::None, true)),
+        Value::Dimension(None, ..) => {
+            return Ok(Value::Dimension(None, Unit::None, Division::Computed))
+        }
         v => {
             return Err((
                 format!("$exponent: {} is not a number.", v.inspect(args.span())?),
@@ -286,7 +319,11 @@ fn pow(mut args: CallArgs, _: &mut Parser<'_>) -> SassResult<Value> {
         }
     };
 
-    Ok(Value::Dimension(base.pow(exponent), Unit::None, true))
+    Ok(Value::Dimension(
+        base.pow(exponent),
+        Unit::None,
+        Division::Computed,
+    ))
 }
 
 fn sqrt(mut args: CallArgs, _: &mut Parser<'_>) -> SassResult<Value> {
@@ -294,7 +331,9 @@ fn sqrt(mut args: CallArgs, _: &mut Parser<'_>) -> SassResult<Value> {
     let number = args.get_err(0, "number")?;
 
     Ok(match number {
-        Value::Dimension(Some(n), Unit::None, ..) => Value::Dimension(n.sqrt(), Unit::None, true),
+        Value::Dimension(Some(n), Unit::None, ..) => {
+            Value::Dimension(n.sqrt(), Unit::None, Division::Computed)
+        }
         v @ Value::Dimension(Some(..), ..) => {
             return Err((
                 format!(
@@ -305,7 +344,7 @@ fn sqrt(mut args: CallArgs, _: &mut Parser<'_>) -> SassResult<Value> {
             )
                 .into())
         }
-        Value::Dimension(None, ..) => Value::Dimension(None, Unit::None, true),
+        Value::Dimension(None, ..) => Value::Dimension(None, Unit::None, Division::Computed),
         v => {
             return Err((
                 format!("$number: {} is not a number.", v.inspect(args.span())?),
@@ -325,10 +364,10 @@ macro_rules! trig_fn {
             Ok(match number {
                 Value::Dimension(Some(n), Unit::None, ..)
                 | Value::Dimension(Some(n), Unit::Rad, ..) => {
-                    Value::Dimension(n.$name(), Unit::None, true)
+                    Value::Dimension(n.$name(), Unit::None, Division::Computed)
                 }
                 Value::Dimension(Some(n), Unit::Deg, ..) => {
-                    Value::Dimension(n.$name_deg(), Unit::None, true)
+                    Value::Dimension(n.$name_deg(), Unit::None, Division::Computed)
                 }
                 v @ Value::Dimension(Some(..), ..) => {
                     return Err((
@@ -340,7 +379,9 @@ macro_rules! trig_fn {
                     )
                         .into())
                 }
-                Value::Dimension(None, ..) => Value::Dimension(None, Unit::None, true),
+                Value::Dimension(None, ..) => {
+                    Value::Dimension(None, Unit::None, Division::Computed)
+                }
                 v => {
                     return Err((
                         format!("$number: {} is not a number.", v.inspect(args.span())?),
@@ -371,7 +412,7 @@ fn acos(mut args: CallArgs, _: &mut Parser<'_>) -> SassResult<Value> {
                 n.acos()
             },
             Unit::Deg,
-            true,
+            Division::Computed,
         ),
         v @ Value::Dimension(Some(..), ..) => {
             return Err((
@@ -383,7 +424,7 @@ fn acos(mut args: CallArgs, _: &mut Parser<'_>) -> SassResult<Value> {
             )
                 .into())
         }
-        Value::Dimension(None, ..) => Value::Dimension(None, Unit::Deg, true),
+        Value::Dimension(None, ..) => Value::Dimension(None, Unit::Deg, Division::Computed),
         v => {
             return Err((
                 format!("$number: {} is not a number.", v.inspect(args.span())?),
@@ -401,12 +442,16 @@ fn asin(mut args: CallArgs, _: &mut Parser<'_>) -> SassResult<Value> {
     Ok(match number {
         Value::Dimension(Some(n), Unit::None, ..) => {
             if n > Number::from(1) || n < Number::from(-1) {
-                return Ok(Value::Dimension(None, Unit::Deg, true));
+                return Ok(Value::Dimension(None, Unit::Deg, Division::Computed));
             } else if n.is_zero() {
-                return Ok(Value::Dimension(Some(Number::zero()), Unit::Deg, true));
+                return Ok(Value::Dimension(
+                    Some(Number::zero()),
+                    Unit::Deg,
+                    Division::Computed,
+                ));
             }
 
-            Value::Dimension(n.asin(), Unit::Deg, true)
+            Value::Dimension(n.asin(), Unit::Deg, Division::Computed)
         }
         v @ Value::Dimension(Some(..), ..) => {
             return Err((
@@ -418,7 +463,7 @@ fn asin(mut args: CallArgs, _: &mut Parser<'_>) -> SassResult<Value> {
             )
                 .into())
         }
-        Value::Dimension(None, ..) => Value::Dimension(None, Unit::Deg, true),
+        Value::Dimension(None, ..) => Value::Dimension(None, Unit::Deg, Division::Computed),
         v => {
             return Err((
                 format!("$number: {} is not a number.", v.inspect(args.span())?),
@@ -436,10 +481,14 @@ fn atan(mut args: CallArgs, _: &mut Parser<'_>) -> SassResult<Value> {
     Ok(match number {
         Value::Dimension(Some(n), Unit::None, ..) => {
             if n.is_zero() {
-                return Ok(Value::Dimension(Some(Number::zero()), Unit::Deg, true));
+                return Ok(Value::Dimension(
+                    Some(Number::zero()),
+                    Unit::Deg,
+                    Division::Computed,
+                ));
             }
 
-            Value::Dimension(n.atan(), Unit::Deg, true)
+            Value::Dimension(n.atan(), Unit::Deg, Division::Computed)
         }
         v @ Value::Dimension(Some(..), ..) => {
             return Err((
@@ -451,7 +500,7 @@ fn atan(mut args: CallArgs, _: &mut Parser<'_>) -> SassResult<Value> {
             )
                 .into())
         }
-        Value::Dimension(None, ..) => Value::Dimension(None, Unit::Deg, true),
+        Value::Dimension(None, ..) => Value::Dimension(None, Unit::Deg, Division::Computed),
         v => {
             return Err((
                 format!("$number: {} is not a number.", v.inspect(args.span())?),
@@ -489,12 +538,12 @@ fn atan2(mut args: CallArgs, _: &mut Parser<'_>) -> SassResult<Value> {
     let (x_num, y_num) = if x_unit == Unit::None && y_unit == Unit::None {
         let x = match x_num {
             Some(n) => n,
-            None => return Ok(Value::Dimension(None, Unit::Deg, true)),
+            None => return Ok(Value::Dimension(None, Unit::Deg, Division::Computed)),
         };
 
         let y = match y_num {
             Some(n) => n,
-            None => return Ok(Value::Dimension(None, Unit::Deg, true)),
+            None => return Ok(Value::Dimension(None, Unit::Deg, Division::Computed)),
         };
 
         (x, y)
@@ -521,12 +570,12 @@ fn atan2(mut args: CallArgs, _: &mut Parser<'_>) -> SassResult<Value> {
     } else if x_unit.comparable(&y_unit) {
         let x = match x_num {
             Some(n) => n,
-            None => return Ok(Value::Dimension(None, Unit::Deg, true)),
+            None => return Ok(Value::Dimension(None, Unit::Deg, Division::Computed)),
         };
 
         let y = match y_num {
             Some(n) => n,
-            None => return Ok(Value::Dimension(None, Unit::Deg, true)),
+            None => return Ok(Value::Dimension(None, Unit::Deg, Division::Computed)),
         };
 
         (x, y.convert(&y_unit, &x_unit))
@@ -544,13 +593,13 @@ fn atan2(mut args: CallArgs, _: &mut Parser<'_>) -> SassResult<Value> {
             NumberState::from_number(&y_num),
         ) {
             (NumberState::Zero, NumberState::FiniteNegative) => {
-                Value::Dimension(Some(Number::from(-90)), Unit::Deg, true)
+                Value::Dimension(Some(Number::from(-90)), Unit::Deg, Division::Computed)
             }
             (NumberState::Zero, NumberState::Zero) | (NumberState::Finite, NumberState::Zero) => {
-                Value::Dimension(Some(Number::zero()), Unit::Deg, true)
+                Value::Dimension(Some(Number::zero()), Unit::Deg, Division::Computed)
             }
             (NumberState::Zero, NumberState::Finite) => {
-                Value::Dimension(Some(Number::from(90)), Unit::Deg, true)
+                Value::Dimension(Some(Number::from(90)), Unit::Deg, Division::Computed)
             }
             (NumberState::Finite, NumberState::Finite)
             | (NumberState::FiniteNegative, NumberState::Finite)
@@ -560,10 +609,10 @@ fn atan2(mut args: CallArgs, _: &mut Parser<'_>) -> SassResult<Value> {
                     .atan2(x_num)
                     .map(|n| (n * Number::from(180)) / Number::pi()),
                 Unit::Deg,
-                true,
+                Division::Computed,
             ),
             (NumberState::FiniteNegative, NumberState::Zero) => {
-                Value::Dimension(Some(Number::from(180)), Unit::Deg, true)
+                Value::Dimension(Some(Number::from(180)), Unit::Deg, Division::Computed)
             }
         },
     )
@@ -597,6 +646,7 @@ pub(crate) fn declare(f: &mut Module) {
     f.insert_builtin("unit", unit);
     f.insert_builtin("percentage", percentage);
     f.insert_builtin("clamp", clamp);
+    f.insert_builtin("div", div);
     f.insert_builtin("sqrt", sqrt);
     f.insert_builtin("cos", cos);
     f.insert_builtin("sin", sin);
@@ -613,10 +663,18 @@ pub(crate) fn declare(f: &mut Module) {
 
     f.insert_builtin_var(
         "e",
-        Value::Dimension(Some(Number::from(std::f64::consts::E)), Unit::None, true),
+        Value::Dimension(
+            Some(Number::from(std::f64::consts::E)),
+            Unit::None,
+            Division::Computed,
+        ),
     );
     f.insert_builtin_var(
         "pi",
-        Value::Dimension(Some(Number::from(std::f64::consts::PI)), Unit::None, true),
+        Value::Dimension(
+            Some(Number::from(std::f64::consts::PI)),
+            Unit::None,
+            Division::Computed,
+        ),
     );
 }
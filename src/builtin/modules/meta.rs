@@ -62,7 +62,7 @@ fn load_css(mut args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Vec<Stmt>
             )?;
         }
 
-        let (_, stmts) = parser.load_module(&url, &mut config)?;
+        let (_, stmts) = parser.load_module(&url, "load-css()", &mut config)?;
 
         Ok(stmts)
     } else {
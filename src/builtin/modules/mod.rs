@@ -1,4 +1,4 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 
 use codemap::{Span, Spanned};
 
@@ -27,7 +27,7 @@ pub(crate) struct Module {
 
     /// Whether or not this module is builtin
     /// e.g. `"sass:math"`
-    is_builtin: bool,
+    pub is_builtin: bool,
 }
 
 #[derive(Debug, Default)]
@@ -36,6 +36,41 @@ pub(crate) struct Modules(BTreeMap<Identifier, Module>);
 #[derive(Debug, Default)]
 pub(crate) struct ModuleConfig(BTreeMap<Identifier, Value>);
 
+/// Which members of a module are passed on by `@forward`
+///
+/// Names are compared after the `as` prefix has been applied
+#[derive(Debug)]
+pub(crate) enum ForwardVisibility {
+    All,
+    Show {
+        vars: BTreeSet<Identifier>,
+        members: BTreeSet<Identifier>,
+    },
+    Hide {
+        vars: BTreeSet<Identifier>,
+        members: BTreeSet<Identifier>,
+    },
+}
+
+impl ForwardVisibility {
+    pub fn is_var_visible(&self, name: Identifier) -> bool {
+        match self {
+            Self::All => true,
+            Self::Show { vars, .. } => vars.contains(&name),
+            Self::Hide { vars, .. } => !vars.contains(&name),
+        }
+    }
+
+    /// Mixins and functions share a namespace for the purposes of `show` and `hide`
+    pub fn is_member_visible(&self, name: Identifier) -> bool {
+        match self {
+            Self::All => true,
+            Self::Show { members, .. } => members.contains(&name),
+            Self::Hide { members, .. } => !members.contains(&name),
+        }
+    }
+}
+
 impl ModuleConfig {
     /// Removes and returns element with name
     pub fn get(&mut self, name: Identifier) -> Option<Value> {
@@ -48,6 +83,28 @@ impl ModuleConfig {
         self.0.is_empty()
     }
 
+    /// Removes and returns every element whose name matches `predicate`
+    pub fn remove_matching<F: Fn(Identifier) -> bool>(
+        &mut self,
+        predicate: F,
+    ) -> Vec<(Identifier, Value)> {
+        let names: Vec<Identifier> = self
+            .0
+            .keys()
+            .copied()
+            .filter(|name| predicate(*name))
+            .collect();
+
+        names
+            .into_iter()
+            .filter_map(|name| self.0.remove(&name).map(|value| (name, value)))
+            .collect()
+    }
+
+    pub fn contains(&self, name: Identifier) -> bool {
+        self.0.contains_key(&name)
+    }
+
     pub fn insert(&mut self, name: Spanned<Identifier>, value: Spanned<Value>) -> SassResult<()> {
         if self.0.insert(name.node, value.node).is_some() {
             Err((
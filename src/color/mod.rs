@@ -1,8 +1,10 @@
 //! A color is internally represented as either RGBA or HSLA.
+//! Colors in the spaces added by CSS Color Level 4, such as `oklch`,
+//! additionally keep their channels in that space.
 //!
 //! Colors can be constructed in Sass through names (e.g. red, blue, aqua)
-//! or the builtin functions `rgb()`, `rgba()`, `hsl()`, and `hsla()`,
-//! all of which can accept 1-4 arguments.
+//! or the builtin functions `rgb()`, `rgba()`, `hsl()`, `hsla()`, and
+//! `color.hwb()`, all of which can accept 1-4 arguments.
 //!
 //! It is necessary to retain the original values with which the
 //! color was constructed.
@@ -22,21 +24,30 @@ use std::{
 
 use crate::value::Number;
 pub(crate) use name::NAMED_COLORS;
+pub(crate) use space::{ColorSpace, GamutMethod};
 
 use num_traits::{One, Signed, ToPrimitive, Zero};
 
 mod name;
+mod space;
 
 #[derive(Debug, Clone)]
 pub(crate) struct Color {
     rgba: Rgba,
     hsla: Option<Hsla>,
     repr: String,
+    space: Option<Box<Channels>>,
 }
 
 impl PartialEq for Color {
     fn eq(&self, other: &Self) -> bool {
-        self.rgba == other.rgba
+        match (&self.space, &other.space) {
+            (None, None) => self.rgba == other.rgba,
+            (Some(channels1), Some(channels2)) => {
+                channels1 == channels2 && self.alpha() == other.alpha()
+            }
+            (Some(..), None) | (None, Some(..)) => false,
+        }
     }
 }
 
@@ -54,6 +65,7 @@ impl Color {
             rgba: Rgba::new(red, green, blue, alpha),
             hsla: None,
             repr,
+            space: None,
         }
     }
 
@@ -69,6 +81,7 @@ impl Color {
             rgba: Rgba::new(red, green, blue, alpha),
             hsla: Some(hsla),
             repr,
+            space: None,
         }
     }
 }
@@ -159,6 +172,31 @@ impl Hsla {
     }
 }
 
+/// The channels of a color in one of the spaces that are not
+/// stored as RGBA, scaled as described in `space`. Missing channels,
+/// written `none`, are stored as NaN.
+#[derive(Debug, Clone)]
+struct Channels {
+    space: ColorSpace,
+    channels: [f64; 3],
+}
+
+impl PartialEq for Channels {
+    fn eq(&self, other: &Self) -> bool {
+        self.space == other.space
+            && self
+                .channels
+                .iter()
+                .zip(other.channels.iter())
+                .all(|(a, b)| a == b || (a.is_nan() && b.is_nan()))
+    }
+}
+
+/// Missing channels are treated as zero by conversions
+fn present_channels(channels: [f64; 3]) -> [f64; 3] {
+    channels.map(|channel| if channel.is_nan() { 0.0 } else { channel })
+}
+
 // RGBA color functions
 impl Color {
     pub fn new(red: u8, green: u8, blue: u8, alpha: u8, repr: String) -> Self {
@@ -166,6 +204,7 @@ impl Color {
             rgba: Rgba::new(red.into(), green.into(), blue.into(), alpha.into()),
             hsla: None,
             repr,
+            space: None,
         }
     }
 
@@ -474,6 +513,154 @@ impl Color {
     }
 }
 
+/// HWB color functions
+/// Algorithms adapted from <https://www.w3.org/TR/css-color-4/#hwb-to-rgb>
+impl Color {
+    /// Calculate whiteness from RGBA values
+    pub fn whiteness(&self) -> Number {
+        let min = min(&self.red(), min(&self.green(), &self.blue())).clone();
+        min / Number::from(255) * Number::from(100)
+    }
+
+    /// Calculate blackness from RGBA values
+    pub fn blackness(&self) -> Number {
+        let max = max(&self.red(), max(&self.green(), &self.blue())).clone();
+        Number::from(100) - max / Number::from(255) * Number::from(100)
+    }
+
+    /// Hue in degrees, and whiteness, blackness and alpha between 0 and 1
+    pub fn as_hwba(&self) -> (Number, Number, Number, Number) {
+        let (hue, ..) = self.as_hsla();
+        (
+            hue,
+            self.whiteness() / Number::from(100),
+            self.blackness() / Number::from(100),
+            self.alpha(),
+        )
+    }
+
+    /// Create RGBA representation from HWBA values
+    ///
+    /// If whiteness and blackness add up to more than 1, they are
+    /// normalized so that they add up to exactly 1, producing a gray
+    pub fn from_hwba(
+        hue: Number,
+        mut whiteness: Number,
+        mut blackness: Number,
+        alpha: Number,
+    ) -> Self {
+        whiteness = whiteness.clamp(0, 1);
+        blackness = blackness.clamp(0, 1);
+
+        let sum = whiteness.clone() + blackness.clone();
+        if sum > Number::one() {
+            whiteness /= sum.clone();
+            blackness /= sum;
+        }
+
+        let factor = Number::one() - whiteness.clone() - blackness;
+        let white = whiteness * Number::from(255);
+        let pure = Color::from_hsla(hue, Number::one(), Number::small_ratio(1, 2), Number::one());
+
+        let channel = |channel: Number| channel * factor.clone() + white.clone();
+
+        Color::from_rgba(
+            channel(pure.rgba.red),
+            channel(pure.rgba.green),
+            channel(pure.rgba.blue),
+            alpha,
+        )
+    }
+}
+
+/// CSS Color Level 4 functions
+impl Color {
+    /// Create a color from channels in `space`, scaled as described in
+    /// `space`. Colors in the legacy spaces are stored as RGBA, and all
+    /// others are mapped into the sRGB gamut for use by the functions
+    /// that only understand RGBA. Channels that are NaN are missing.
+    pub fn from_space(space: ColorSpace, channels: [f64; 3], alpha: Number) -> Self {
+        let [red, green, blue] = space::to_gamut(
+            space,
+            ColorSpace::Rgb,
+            present_channels(channels),
+            GamutMethod::LocalMinde,
+        )
+        .map(|channel| finite_number(channel * 255.0));
+
+        if space.is_legacy() {
+            let color = Color::from_rgba(red, green, blue, alpha);
+            if space == ColorSpace::Hsl {
+                let (hue, saturation, luminance, alpha) = color.as_hsla();
+                return Color::from_hsla(hue, saturation, luminance, alpha);
+            }
+            return color;
+        }
+
+        let alpha = alpha.clamp(0, 1);
+        let repr = space_repr(space, channels, &alpha);
+
+        Color {
+            rgba: Rgba::new(red, green, blue, alpha),
+            hsla: None,
+            repr,
+            space: Some(Box::new(Channels { space, channels })),
+        }
+    }
+
+    /// The space this color was created in
+    pub fn space(&self) -> ColorSpace {
+        match &self.space {
+            Some(channels) => channels.space,
+            None if self.hsla.is_some() => ColorSpace::Hsl,
+            None => ColorSpace::Rgb,
+        }
+    }
+
+    /// This color's channels in `space`, scaled as described in `space`
+    pub fn channels(&self, space: ColorSpace) -> [f64; 3] {
+        match &self.space {
+            Some(channels) => {
+                space::convert(channels.space, space, present_channels(channels.channels))
+            }
+            None => space::convert(
+                ColorSpace::Rgb,
+                space,
+                [&self.rgba.red, &self.rgba.green, &self.rgba.blue]
+                    .map(|channel| channel.clone().as_float().unwrap_or(0.0) / 255.0),
+            ),
+        }
+    }
+
+    pub fn to_space(&self, space: ColorSpace) -> Self {
+        if space == self.space() {
+            return self.clone();
+        }
+
+        Color::from_space(space, self.channels(space), self.alpha())
+    }
+
+    pub fn is_in_gamut(&self, space: ColorSpace) -> bool {
+        space::is_in_gamut(space, self.channels(space))
+    }
+
+    /// Map this color into the gamut of `space`, keeping it in its own space
+    pub fn to_gamut(&self, space: ColorSpace, method: GamutMethod) -> Self {
+        if self.is_in_gamut(space) {
+            return self.clone();
+        }
+
+        let own_space = self.space();
+        let mapped = space::to_gamut(own_space, space, self.channels(own_space), method);
+
+        Color::from_space(
+            own_space,
+            space::convert(space, own_space, mapped),
+            self.alpha(),
+        )
+    }
+}
+
 /// Opacity color functions
 impl Color {
     pub fn alpha(&self) -> Number {
@@ -518,18 +705,58 @@ impl Color {
     }
 }
 
-/// Get the proper representation from RGBA values
-fn repr(red: &Number, green: &Number, blue: &Number, alpha: &Number) -> String {
-    fn into_u8(channel: &Number) -> u8 {
-        if channel > &Number::from(255) {
-            255_u8
-        } else if channel.is_negative() {
-            0_u8
+/// Compressed output
+impl Color {
+    /// The shortest representation of this color, as emitted
+    /// in compressed output
+    pub fn to_compressed_string(&self) -> String {
+        if self.space.is_some() {
+            return self.repr.clone();
+        }
+
+        let red = into_u8(&self.red());
+        let green = into_u8(&self.green());
+        let blue = into_u8(&self.blue());
+
+        if self.alpha() < Number::one() {
+            if !self.repr.starts_with("rgba(") {
+                return self.repr.clone();
+            }
+
+            let mut alpha = self.alpha().to_string();
+            if alpha.starts_with("0.") {
+                alpha.remove(0);
+            }
+            return format!("rgba({},{},{},{})", red, green, blue, alpha);
+        }
+
+        let can_use_short_hex = [red, green, blue].iter().all(|c| c >> 4 == c & 0xF);
+
+        let hex = if can_use_short_hex {
+            format!("#{:x}{:x}{:x}", red & 0xF, green & 0xF, blue & 0xF)
         } else {
-            channel.round().to_integer().to_u8().unwrap_or(255)
+            format!("#{:0>2x}{:0>2x}{:0>2x}", red, green, blue)
+        };
+
+        match NAMED_COLORS.get_by_rgba([red, green, blue]) {
+            Some(name) if name.len() <= hex.len() => (*name).to_owned(),
+            Some(..) | None => hex,
         }
     }
+}
 
+fn into_u8(channel: &Number) -> u8 {
+    if channel > &Number::from(255) {
+        255_u8
+    } else if channel.is_negative() {
+        0_u8
+    } else {
+        channel.round().to_integer().to_u8().unwrap_or(255)
+    }
+}
+
+/// Get the proper representation from RGBA values
+fn repr(red: &Number, green: &Number, blue: &Number, alpha: &Number) -> String {
     let red_u8 = into_u8(red);
     let green_u8 = into_u8(green);
     let blue_u8 = into_u8(blue);
@@ -543,6 +770,73 @@ fn repr(red: &Number, green: &Number, blue: &Number, alpha: &Number) -> String {
     }
 }
 
+/// Convert a channel computed as a float back to a `Number`
+pub(crate) fn finite_number(value: f64) -> Number {
+    if value.is_finite() {
+        Number::from(value)
+    } else {
+        Number::zero()
+    }
+}
+
+/// A single channel of `space_repr`, or `none` if it is missing
+fn channel_repr(channel: f64, scale: f64, unit: &str) -> String {
+    if channel.is_nan() {
+        "none".to_owned()
+    } else {
+        format!("{}{}", finite_number(channel * scale), unit)
+    }
+}
+
+/// Get the representation of a color in one of the spaces that
+/// are not stored as RGBA, e.g. `oklch(50% 0.1 120deg)`
+fn space_repr(space: ColorSpace, channels: [f64; 3], alpha: &Number) -> String {
+    let [first, second, third] = channels;
+
+    let mut repr = match space {
+        ColorSpace::Lab => format!(
+            "lab({} {} {}",
+            channel_repr(first, 1.0, "%"),
+            channel_repr(second, 1.0, ""),
+            channel_repr(third, 1.0, "")
+        ),
+        ColorSpace::Lch => format!(
+            "lch({} {} {}",
+            channel_repr(first, 1.0, "%"),
+            channel_repr(second, 1.0, ""),
+            channel_repr(third, 1.0, "deg")
+        ),
+        ColorSpace::Oklab => format!(
+            "oklab({} {} {}",
+            channel_repr(first, 100.0, "%"),
+            channel_repr(second, 1.0, ""),
+            channel_repr(third, 1.0, "")
+        ),
+        ColorSpace::Oklch => format!(
+            "oklch({} {} {}",
+            channel_repr(first, 100.0, "%"),
+            channel_repr(second, 1.0, ""),
+            channel_repr(third, 1.0, "deg")
+        ),
+        ColorSpace::DisplayP3 => format!(
+            "color(display-p3 {} {} {}",
+            channel_repr(first, 1.0, ""),
+            channel_repr(second, 1.0, ""),
+            channel_repr(third, 1.0, "")
+        ),
+        ColorSpace::Rgb | ColorSpace::Hsl | ColorSpace::Hwb => {
+            unreachable!("legacy colors are stored as RGBA")
+        }
+    };
+
+    if alpha < &Number::one() {
+        repr.push_str(&format!(" / {}", alpha));
+    }
+
+    repr.push(')');
+    repr
+}
+
 impl Display for Color {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.repr)
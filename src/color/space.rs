@@ -0,0 +1,493 @@
+//! Conversions between the color spaces of CSS Color Level 4
+//!
+//! Colors in the legacy spaces (`rgb`, `hsl` and `hwb`) are stored as RGBA
+//! by `Color` itself. Here they are all treated as sRGB, with channels
+//! between 0 and 1.
+//!
+//! Algorithms and matrices adapted from
+//! <https://www.w3.org/TR/css-color-4/#color-conversion-code>
+//! and <https://www.w3.org/TR/css-color-4/#css-gamut-mapping>
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ColorSpace {
+    Rgb,
+    Hsl,
+    Hwb,
+    Lab,
+    Lch,
+    Oklab,
+    Oklch,
+    DisplayP3,
+}
+
+impl ColorSpace {
+    pub fn from_name(name: &str) -> Option<Self> {
+        Some(match name.to_ascii_lowercase().as_str() {
+            "rgb" => ColorSpace::Rgb,
+            "hsl" => ColorSpace::Hsl,
+            "hwb" => ColorSpace::Hwb,
+            "lab" => ColorSpace::Lab,
+            "lch" => ColorSpace::Lch,
+            "oklab" => ColorSpace::Oklab,
+            "oklch" => ColorSpace::Oklch,
+            "display-p3" => ColorSpace::DisplayP3,
+            _ => return None,
+        })
+    }
+
+    pub const fn name(self) -> &'static str {
+        match self {
+            ColorSpace::Rgb => "rgb",
+            ColorSpace::Hsl => "hsl",
+            ColorSpace::Hwb => "hwb",
+            ColorSpace::Lab => "lab",
+            ColorSpace::Lch => "lch",
+            ColorSpace::Oklab => "oklab",
+            ColorSpace::Oklch => "oklch",
+            ColorSpace::DisplayP3 => "display-p3",
+        }
+    }
+
+    pub const fn channel_names(self) -> [&'static str; 3] {
+        match self {
+            ColorSpace::Rgb | ColorSpace::DisplayP3 => ["red", "green", "blue"],
+            ColorSpace::Hsl => ["hue", "saturation", "lightness"],
+            ColorSpace::Hwb => ["hue", "whiteness", "blackness"],
+            ColorSpace::Lab | ColorSpace::Oklab => ["lightness", "a", "b"],
+            ColorSpace::Lch | ColorSpace::Oklch => ["lightness", "chroma", "hue"],
+        }
+    }
+
+    /// Whether colors in this space are stored as RGBA
+    pub const fn is_legacy(self) -> bool {
+        matches!(self, ColorSpace::Rgb | ColorSpace::Hsl | ColorSpace::Hwb)
+    }
+
+    /// Whether this space has a gamut that colors can fall outside of
+    pub const fn is_bounded(self) -> bool {
+        matches!(
+            self,
+            ColorSpace::Rgb | ColorSpace::Hsl | ColorSpace::Hwb | ColorSpace::DisplayP3
+        )
+    }
+}
+
+/// How colors outside of a gamut are brought within it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum GamutMethod {
+    /// Clamp each channel to the gamut
+    Clip,
+    /// Reduce the chroma of the color in Oklch until it is within
+    /// a just noticeable difference of its clipped version
+    LocalMinde,
+}
+
+type Matrix = [[f64; 3]; 3];
+
+const LINEAR_SRGB_TO_XYZ_D65: Matrix = [
+    [
+        0.412_390_799_265_959_34,
+        0.357_584_339_383_878,
+        0.180_480_788_401_834_3,
+    ],
+    [
+        0.212_639_005_871_510_27,
+        0.715_168_678_767_756,
+        0.072_192_315_360_733_71,
+    ],
+    [
+        0.019_330_818_715_591_82,
+        0.119_194_779_794_625_98,
+        0.950_532_152_249_660_7,
+    ],
+];
+
+const XYZ_D65_TO_LINEAR_SRGB: Matrix = [
+    [
+        3.240_969_941_904_522_6,
+        -1.537_383_177_570_094,
+        -0.498_610_760_293_003_4,
+    ],
+    [
+        -0.969_243_636_280_879_6,
+        1.875_967_501_507_720_2,
+        0.041_555_057_407_175_59,
+    ],
+    [
+        0.055_630_079_696_993_66,
+        -0.203_976_958_888_976_52,
+        1.056_971_514_242_878_6,
+    ],
+];
+
+const LINEAR_DISPLAY_P3_TO_XYZ_D65: Matrix = [
+    [
+        0.486_570_948_648_216_2,
+        0.265_667_693_169_093_06,
+        0.198_217_285_234_362_5,
+    ],
+    [
+        0.228_974_564_069_748_8,
+        0.691_738_521_836_506_4,
+        0.079_286_914_093_745,
+    ],
+    [0.0, 0.045_113_381_858_902_64, 1.043_944_368_900_976],
+];
+
+const XYZ_D65_TO_LINEAR_DISPLAY_P3: Matrix = [
+    [
+        2.493_496_911_941_425,
+        -0.931_383_617_919_123_9,
+        -0.402_710_784_450_716_84,
+    ],
+    [
+        -0.829_488_969_561_574_7,
+        1.762_664_060_318_346_3,
+        0.023_624_685_841_943_577,
+    ],
+    [
+        0.035_845_830_243_784_47,
+        -0.076_172_389_268_041_82,
+        0.956_884_524_007_687_2,
+    ],
+];
+
+const XYZ_D65_TO_XYZ_D50: Matrix = [
+    [
+        1.047_929_820_840_548_8,
+        0.022_946_793_341_019_088,
+        -0.050_192_229_543_135_57,
+    ],
+    [
+        0.029_627_815_688_159_344,
+        0.990_434_484_573_249,
+        -0.017_073_825_029_385_14,
+    ],
+    [
+        -0.009_243_058_152_591_178,
+        0.015_055_144_896_577_895,
+        0.751_874_289_958_000_8,
+    ],
+];
+
+const XYZ_D50_TO_XYZ_D65: Matrix = [
+    [
+        0.955_473_452_704_218_2,
+        -0.023_098_536_874_261_423,
+        0.063_259_308_661_021_7,
+    ],
+    [
+        -0.028_369_706_963_208_136,
+        1.009_995_458_005_822_6,
+        0.021_041_398_966_943_008,
+    ],
+    [
+        0.012_314_001_688_319_899,
+        -0.020_507_696_433_477_912,
+        1.330_365_936_608_075_3,
+    ],
+];
+
+const XYZ_D65_TO_LMS: Matrix = [
+    [
+        0.819_022_437_996_703,
+        0.361_906_260_052_890_4,
+        -0.128_873_781_520_987_9,
+    ],
+    [
+        0.032_983_653_932_388_5,
+        0.929_286_861_586_343_4,
+        0.036_144_666_350_642_4,
+    ],
+    [
+        0.048_177_189_359_624_2,
+        0.264_239_531_752_730_8,
+        0.633_547_828_469_430_9,
+    ],
+];
+
+const LMS_TO_XYZ_D65: Matrix = [
+    [
+        1.226_879_875_845_924_3,
+        -0.557_814_994_460_217_1,
+        0.281_391_045_665_964_7,
+    ],
+    [
+        -0.040_575_745_214_800_8,
+        1.112_286_803_280_317,
+        -0.071_711_058_065_516_4,
+    ],
+    [
+        -0.076_372_936_674_660_1,
+        -0.421_493_332_402_243_2,
+        1.586_924_019_836_781_6,
+    ],
+];
+
+const LMS_TO_OKLAB: Matrix = [
+    [
+        0.210_454_268_309_314,
+        0.793_617_774_702_305_4,
+        -0.004_072_043_011_619_3,
+    ],
+    [
+        1.977_998_532_431_168_4,
+        -2.428_592_242_048_58,
+        0.450_593_709_617_411,
+    ],
+    [
+        0.025_904_042_465_547_8,
+        0.782_771_712_457_529_6,
+        -0.808_675_754_923_077_4,
+    ],
+];
+
+const OKLAB_TO_LMS: Matrix = [
+    [1.0, 0.396_337_777_376_174_9, 0.215_803_757_309_913_6],
+    [1.0, -0.105_561_345_815_658_6, -0.063_854_172_825_813_3],
+    [1.0, -0.089_484_177_529_811_9, -1.291_485_548_019_409_2],
+];
+
+/// The D50 white point, used by Lab and LCH
+const D50: [f64; 3] = [0.345_7 / 0.358_5, 1.0, (1.0 - 0.345_7 - 0.358_5) / 0.358_5];
+
+/// Colors with less chroma than this are considered to have no hue
+const ACHROMATIC_EPSILON: f64 = 1e-6;
+
+/// Colors whose channels are within this distance of the edge of a gamut
+/// are considered to be within it
+const GAMUT_EPSILON: f64 = 1e-6;
+
+fn multiply(matrix: &Matrix, [a, b, c]: [f64; 3]) -> [f64; 3] {
+    let row = |[x, y, z]: [f64; 3]| x * a + y * b + z * c;
+    [row(matrix[0]), row(matrix[1]), row(matrix[2])]
+}
+
+/// Gamma-encoded sRGB (or Display P3) to linear light
+fn linearize(channel: f64) -> f64 {
+    let abs = channel.abs();
+    if abs <= 0.040_45 {
+        channel / 12.92
+    } else {
+        channel.signum() * ((abs + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+/// Linear light to gamma-encoded sRGB (or Display P3)
+fn gamma(channel: f64) -> f64 {
+    let abs = channel.abs();
+    if abs > 0.003_130_8 {
+        channel.signum() * (1.055 * abs.powf(1.0 / 2.4) - 0.055)
+    } else {
+        12.92 * channel
+    }
+}
+
+fn lab_to_xyz_d50([lightness, a, b]: [f64; 3]) -> [f64; 3] {
+    const EPSILON: f64 = 216.0 / 24_389.0;
+    const KAPPA: f64 = 24_389.0 / 27.0;
+
+    let f1 = (lightness + 16.0) / 116.0;
+    let f0 = a / 500.0 + f1;
+    let f2 = f1 - b / 200.0;
+
+    let x = if f0.powi(3) > EPSILON {
+        f0.powi(3)
+    } else {
+        (116.0 * f0 - 16.0) / KAPPA
+    };
+    let y = if lightness > KAPPA * EPSILON {
+        f1.powi(3)
+    } else {
+        lightness / KAPPA
+    };
+    let z = if f2.powi(3) > EPSILON {
+        f2.powi(3)
+    } else {
+        (116.0 * f2 - 16.0) / KAPPA
+    };
+
+    [x * D50[0], y * D50[1], z * D50[2]]
+}
+
+fn xyz_d50_to_lab(xyz: [f64; 3]) -> [f64; 3] {
+    const EPSILON: f64 = 216.0 / 24_389.0;
+    const KAPPA: f64 = 24_389.0 / 27.0;
+
+    let f = |value: f64, white: f64| {
+        let value = value / white;
+        if value > EPSILON {
+            value.cbrt()
+        } else {
+            (KAPPA * value + 16.0) / 116.0
+        }
+    };
+
+    let [f0, f1, f2] = [f(xyz[0], D50[0]), f(xyz[1], D50[1]), f(xyz[2], D50[2])];
+
+    [116.0 * f1 - 16.0, 500.0 * (f0 - f1), 200.0 * (f1 - f2)]
+}
+
+fn polar_to_rectangular([lightness, chroma, hue]: [f64; 3]) -> [f64; 3] {
+    let hue = hue.to_radians();
+    [lightness, chroma * hue.cos(), chroma * hue.sin()]
+}
+
+fn rectangular_to_polar([lightness, a, b]: [f64; 3]) -> [f64; 3] {
+    let chroma = a.hypot(b);
+
+    // the hue of an achromatic color is meaningless, and would
+    // otherwise be determined by rounding errors
+    let hue = if chroma < ACHROMATIC_EPSILON {
+        0.0
+    } else {
+        b.atan2(a).to_degrees().rem_euclid(360.0)
+    };
+
+    [lightness, chroma, hue]
+}
+
+/// Convert `channels` in `space` to XYZ with a D65 white point
+fn to_xyz(space: ColorSpace, channels: [f64; 3]) -> [f64; 3] {
+    match space {
+        ColorSpace::Rgb | ColorSpace::Hsl | ColorSpace::Hwb => {
+            multiply(&LINEAR_SRGB_TO_XYZ_D65, channels.map(linearize))
+        }
+        ColorSpace::DisplayP3 => multiply(&LINEAR_DISPLAY_P3_TO_XYZ_D65, channels.map(linearize)),
+        ColorSpace::Lab => multiply(&XYZ_D50_TO_XYZ_D65, lab_to_xyz_d50(channels)),
+        ColorSpace::Lch => to_xyz(ColorSpace::Lab, polar_to_rectangular(channels)),
+        ColorSpace::Oklab => multiply(
+            &LMS_TO_XYZ_D65,
+            multiply(&OKLAB_TO_LMS, channels).map(|c| c.powi(3)),
+        ),
+        ColorSpace::Oklch => to_xyz(ColorSpace::Oklab, polar_to_rectangular(channels)),
+    }
+}
+
+/// Convert XYZ with a D65 white point to channels in `space`
+fn from_xyz(space: ColorSpace, xyz: [f64; 3]) -> [f64; 3] {
+    match space {
+        ColorSpace::Rgb | ColorSpace::Hsl | ColorSpace::Hwb => {
+            multiply(&XYZ_D65_TO_LINEAR_SRGB, xyz).map(gamma)
+        }
+        ColorSpace::DisplayP3 => multiply(&XYZ_D65_TO_LINEAR_DISPLAY_P3, xyz).map(gamma),
+        ColorSpace::Lab => xyz_d50_to_lab(multiply(&XYZ_D65_TO_XYZ_D50, xyz)),
+        ColorSpace::Lch => rectangular_to_polar(from_xyz(ColorSpace::Lab, xyz)),
+        ColorSpace::Oklab => multiply(&LMS_TO_OKLAB, multiply(&XYZ_D65_TO_LMS, xyz).map(f64::cbrt)),
+        ColorSpace::Oklch => rectangular_to_polar(from_xyz(ColorSpace::Oklab, xyz)),
+    }
+}
+
+/// Convert `channels` in `from` to channels in `to`
+pub(crate) fn convert(from: ColorSpace, to: ColorSpace, channels: [f64; 3]) -> [f64; 3] {
+    let as_rgb = |space: ColorSpace| {
+        if space.is_legacy() {
+            ColorSpace::Rgb
+        } else {
+            space
+        }
+    };
+
+    if as_rgb(from) == as_rgb(to) {
+        return channels;
+    }
+
+    from_xyz(to, to_xyz(from, channels))
+}
+
+/// Whether `channels` in `space` fall within its gamut
+pub(crate) fn is_in_gamut(space: ColorSpace, channels: [f64; 3]) -> bool {
+    !space.is_bounded()
+        || channels
+            .iter()
+            .all(|&c| (-GAMUT_EPSILON..=1.0 + GAMUT_EPSILON).contains(&c))
+}
+
+fn clip(channels: [f64; 3]) -> [f64; 3] {
+    channels.map(|c| c.clamp(0.0, 1.0))
+}
+
+/// The distance between two colors in Oklab
+fn delta_eok(oklab1: [f64; 3], oklab2: [f64; 3]) -> f64 {
+    let [l, a, b] = [
+        oklab1[0] - oklab2[0],
+        oklab1[1] - oklab2[1],
+        oklab1[2] - oklab2[2],
+    ];
+    (l * l + a * a + b * b).sqrt()
+}
+
+/// Convert `channels` in `from` to channels within the gamut of `to`
+pub(crate) fn to_gamut(
+    from: ColorSpace,
+    to: ColorSpace,
+    channels: [f64; 3],
+    method: GamutMethod,
+) -> [f64; 3] {
+    /// A just noticeable difference in Oklab
+    const JND: f64 = 0.02;
+    const EPSILON: f64 = 0.0001;
+
+    let converted = convert(from, to, channels);
+
+    if is_in_gamut(to, converted) {
+        return converted;
+    }
+
+    if method == GamutMethod::Clip {
+        return clip(converted);
+    }
+
+    let mut current = convert(from, ColorSpace::Oklch, channels);
+
+    if current[0] >= 1.0 {
+        return clip(convert(ColorSpace::Oklab, to, [1.0, 0.0, 0.0]));
+    } else if current[0] <= 0.0 {
+        return clip(convert(ColorSpace::Oklab, to, [0.0, 0.0, 0.0]));
+    }
+
+    let delta = |clipped: [f64; 3], current: [f64; 3]| {
+        delta_eok(
+            convert(to, ColorSpace::Oklab, clipped),
+            convert(ColorSpace::Oklch, ColorSpace::Oklab, current),
+        )
+    };
+
+    let mut clipped = clip(convert(ColorSpace::Oklch, to, current));
+
+    if delta(clipped, current) < JND {
+        return clipped;
+    }
+
+    let mut min = 0.0;
+    let mut max = current[1];
+    let mut min_in_gamut = true;
+
+    while max - min > EPSILON {
+        let chroma = (min + max) / 2.0;
+        current[1] = chroma;
+
+        let candidate = convert(ColorSpace::Oklch, to, current);
+
+        if min_in_gamut && is_in_gamut(to, candidate) {
+            min = chroma;
+            continue;
+        }
+
+        clipped = clip(candidate);
+        let e = delta(clipped, current);
+
+        if e < JND {
+            if JND - e < EPSILON {
+                return clipped;
+            }
+
+            min_in_gamut = false;
+            min = chroma;
+        } else {
+            max = chroma;
+        }
+    }
+
+    clipped
+}
@@ -89,6 +89,7 @@ pub(crate) enum Brackets {
 pub(crate) enum ListSeparator {
     Space,
     Comma,
+    Slash,
 }
 
 impl ListSeparator {
@@ -96,6 +97,15 @@ impl ListSeparator {
         match self {
             Self::Space => " ",
             Self::Comma => ", ",
+            Self::Slash => " / ",
+        }
+    }
+
+    pub fn as_compressed_str(self) -> &'static str {
+        match self {
+            Self::Space => " ",
+            Self::Comma => ",",
+            Self::Slash => "/",
         }
     }
 
@@ -103,6 +113,7 @@ impl ListSeparator {
         match self {
             Self::Space => "space",
             Self::Comma => "comma",
+            Self::Slash => "slash",
         }
     }
 }
@@ -0,0 +1,102 @@
+use std::{collections::HashMap, fmt, path::Path};
+
+use codemap::CodeMap;
+use peekmore::PeekMore;
+
+use crate::{
+    args::FuncArgs,
+    builtin::modules::{ModuleConfig, Modules},
+    common::Identifier,
+    error::SassResult,
+    lexer::tokenize,
+    parse::{
+        common::{ContextFlags, NeverEmptyVec},
+        Parser,
+    },
+    sass_value::SassValue,
+    scope::{Scope, Scopes},
+    selector::{Extender, Selector},
+    Options, Token,
+};
+
+/// The signature of a function registered through
+/// [`Options::add_function`](crate::Options::add_function)
+pub(crate) type Callback = dyn Fn(&[SassValue]) -> Result<SassValue, String>;
+
+/// A function implemented by the host application
+pub(crate) struct CustomFunction {
+    pub name: Identifier,
+    /// The declared arguments, everything following the opening
+    /// parenthesis of the signature, e.g. `$path, $hash: false)`
+    pub args: String,
+    pub callback: Box<Callback>,
+}
+
+impl CustomFunction {
+    /// Split a signature such as `asset-url($path, $hash: false)` into
+    /// its name and arguments. A signature without parentheses declares
+    /// a function that takes no arguments.
+    pub fn new(signature: &str, callback: Box<Callback>) -> Self {
+        let (name, args) = signature.find('(').map_or((signature, ")"), |idx| {
+            (&signature[..idx], &signature[idx + 1..])
+        });
+
+        CustomFunction {
+            name: Identifier::from(name.trim()),
+            args: args.to_owned(),
+            callback,
+        }
+    }
+
+    /// Parse the declared arguments, adding them to `map` so that the
+    /// spans of their default values can be looked up
+    pub fn parse_args(&self, map: &mut CodeMap, options: &Options) -> SassResult<FuncArgs> {
+        let file = map.add_file(format!("{}()", self.name), self.args.clone());
+        let empty_span = file.span.subspan(0, 0);
+        let end = file.span.subspan(file.span.len(), file.span.len());
+
+        // `parse_func_args` expects the arguments to be followed by a body
+        let mut toks = tokenize(&file, false)?;
+        toks.push(Token::new(end, '{'));
+        let mut toks = toks.into_iter().peekmore();
+
+        let args = Parser {
+            toks: &mut toks,
+            map,
+            path: Path::new(""),
+            scopes: &mut Scopes::new(),
+            global_scope: &mut Scope::new(),
+            super_selectors: &mut NeverEmptyVec::new(Selector::new(empty_span)),
+            span_before: empty_span,
+            content: &mut Vec::new(),
+            flags: ContextFlags::empty(),
+            at_root: true,
+            at_root_has_selector: false,
+            extender: &mut Extender::new(empty_span),
+            media_queries: &mut None,
+            content_scopes: &mut Scopes::new(),
+            options,
+            modules: &mut Modules::default(),
+            module_config: &mut ModuleConfig::default(),
+            forwarded: &mut Scope::new(),
+            call_stack: &mut Vec::new(),
+            custom_function_args: &mut HashMap::new(),
+        }
+        .parse_func_args()?;
+
+        if toks.next().is_some() {
+            return Err(("Invalid signature.", end).into());
+        }
+
+        Ok(args)
+    }
+}
+
+impl fmt::Debug for CustomFunction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CustomFunction")
+            .field("name", &self.name)
+            .field("args", &self.args)
+            .finish()
+    }
+}
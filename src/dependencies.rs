@@ -0,0 +1,81 @@
+//! # Recording the stylesheets loaded during compilation
+use std::{
+    cell::RefCell,
+    path::{Path, PathBuf},
+};
+
+/// The graph of stylesheets loaded by `@use`, `@forward`, and `@import`
+/// rules while compiling
+///
+/// Pass a graph to [`Options::track_dependencies`](crate::Options::track_dependencies)
+/// to have it filled in as stylesheets are loaded. Stylesheets are recorded
+/// even if compilation later fails, which allows tools such as a file
+/// watcher to know which files may contain a fix.
+///
+/// ```no_run
+/// fn main() -> Result<(), Box<grass::Error>> {
+///     let graph = grass::DependencyGraph::new();
+///     grass::from_path(
+///         "input.scss",
+///         &grass::Options::default().track_dependencies(&graph),
+///     )?;
+///     for path in graph.files() {
+///         println!("{}", path.display());
+///     }
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Default)]
+pub struct DependencyGraph {
+    /// The canonical path of a stylesheet, and a stylesheet it loaded
+    edges: RefCell<Vec<(PathBuf, PathBuf)>>,
+}
+
+impl DependencyGraph {
+    #[must_use]
+    #[inline]
+    pub fn new() -> Self {
+        DependencyGraph::default()
+    }
+
+    /// Every stylesheet loaded, directly or transitively, in the order they
+    /// were first loaded
+    ///
+    /// The stylesheet being compiled is not included.
+    #[must_use]
+    #[inline]
+    pub fn files(&self) -> Vec<PathBuf> {
+        let mut files: Vec<PathBuf> = Vec::new();
+        for (_, to) in self.edges.borrow().iter() {
+            if !files.contains(to) {
+                files.push(to.clone());
+            }
+        }
+        files
+    }
+
+    /// The stylesheets loaded directly by the stylesheet at `path`
+    #[must_use]
+    #[inline]
+    pub fn dependencies_of(&self, path: &Path) -> Vec<PathBuf> {
+        self.edges
+            .borrow()
+            .iter()
+            .filter(|(from, _)| from == path)
+            .map(|(_, to)| to.clone())
+            .collect()
+    }
+
+    /// Forget every recorded stylesheet, e.g. before compiling again
+    #[inline]
+    pub fn clear(&self) {
+        self.edges.borrow_mut().clear();
+    }
+
+    pub(crate) fn record(&self, from: &Path, to: &Path) {
+        let mut edges = self.edges.borrow_mut();
+        if !edges.iter().any(|(f, t)| f == from && t == to) {
+            edges.push((from.to_path_buf(), to.to_path_buf()));
+        }
+    }
+}
@@ -2,11 +2,12 @@ use std::{
     error::Error,
     fmt::{self, Display},
     io,
+    path::Path,
     rc::Rc,
     string::FromUtf8Error,
 };
 
-use codemap::{Span, SpanLoc};
+use codemap::{CodeMap, LineCol, Span, SpanLoc};
 
 pub type SassResult<T> = Result<T, Box<SassError>>;
 
@@ -23,47 +24,224 @@ pub type SassResult<T> = Result<T, Box<SassError>>;
 /// 308 |     color: unit(foo);
 ///     |                 ^^^
 ///     |
-/// ./input.scss:308:17
+///   _mixins.scss 308:17  size()
+///   input.scss 12:3      @import
+///   input.scss 20:1      root stylesheet
 ///```
 ///
-/// The file name, line number, and column are structured in
-/// such a way as to allow Visual Studio Code users to go
-/// directly to the error by simply clicking the file name.
-///
-/// Note that this is a deviation from the Sass specification.
+/// The trace lists the location of the error followed by the
+/// mixins, functions, and stylesheets it occurred within,
+/// innermost first. Each entry is also available through
+/// [`trace`](SassError::trace).
 #[derive(Debug, Clone)]
 pub struct SassError {
     kind: SassErrorKind,
 }
 
 impl SassError {
-    pub(crate) fn raw(self) -> (String, Span) {
+    /// The message describing this error, without any location information
+    #[must_use]
+    #[inline]
+    pub fn message(&self) -> String {
+        match &self.kind {
+            SassErrorKind::Raw { message, .. } | SassErrorKind::ParseError { message, .. } => {
+                message.clone()
+            }
+            SassErrorKind::IoError(e) => e.to_string(),
+            SassErrorKind::FromUtf8Error(s) => s.clone(),
+        }
+    }
+
+    /// The severity of this diagnostic, which for errors is always
+    /// [`Severity::Error`](Severity::Error)
+    #[must_use]
+    #[inline]
+    pub const fn severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    /// The path of the stylesheet containing this error
+    ///
+    /// This is `stdin` for stylesheets compiled from a string, and
+    /// `None` for errors, such as those reading a file, that do not
+    /// have a location.
+    #[must_use]
+    #[inline]
+    pub fn path(&self) -> Option<&Path> {
+        self.loc().map(|loc| Path::new(loc.file.name()))
+    }
+
+    /// The position of the first character of the source of this error
+    #[must_use]
+    #[inline]
+    pub fn start(&self) -> Option<Position> {
+        self.loc().map(|loc| Position::from(loc.begin))
+    }
+
+    /// The position just after the last character of the source of this error
+    #[must_use]
+    #[inline]
+    pub fn end(&self) -> Option<Position> {
+        self.loc().map(|loc| Position::from(loc.end))
+    }
+
+    /// The full lines of source code containing this error, without
+    /// the final line terminator
+    #[must_use]
+    #[inline]
+    pub fn snippet(&self) -> Option<&str> {
+        self.loc().map(|loc| {
+            let lines = loc
+                .file
+                .line_span(loc.begin.line)
+                .merge(loc.file.line_span(loc.end.line));
+            loc.file
+                .source_slice(lines)
+                .trim_end_matches(&['\n', '\r'][..])
+        })
+    }
+
+    /// The mixins, functions, and stylesheets this error occurred within,
+    /// innermost first
+    ///
+    /// The first frame is the location of the error itself, and the last
+    /// frame is always within the root stylesheet. Errors without a location
+    /// have no frames.
+    #[must_use]
+    #[inline]
+    pub fn trace(&self) -> &[StackFrame] {
+        match &self.kind {
+            SassErrorKind::ParseError { trace, .. } => trace,
+            _ => &[],
+        }
+    }
+
+    fn loc(&self) -> Option<&SpanLoc> {
+        match &self.kind {
+            SassErrorKind::ParseError { loc, .. } => Some(loc),
+            _ => None,
+        }
+    }
+
+    /// A stylesheet which displays this error at the top of the page,
+    /// for use in place of the stylesheet that failed to compile
+    ///
+    /// This mirrors the CSS emitted by `dart-sass`: the error is shown
+    /// both in a comment and in the `content` of `body::before`.
+    #[must_use]
+    #[inline]
+    pub fn to_css(&self) -> String {
+        let message = self.to_string();
+        let message = message.trim_end();
+
+        let mut content = String::with_capacity(message.len());
+        for c in message.chars() {
+            match c {
+                '"' | '\\' => {
+                    content.push('\\');
+                    content.push(c);
+                }
+                // escapes are terminated by a space so that
+                // following hex digits are not consumed
+                '\n' => content.push_str("\\a "),
+                c if c.is_ascii() && !c.is_ascii_control() => content.push(c),
+                c => content.push_str(&format!("\\{:x} ", c as u32)),
+            }
+        }
+
+        format!(
+            "/* {} */\n\n\
+             body::before {{\n  \
+               font-family: \"Source Code Pro\", \"SF Mono\", Monaco, Inconsolata, \"Fira Mono\",\n      \
+                 \"Droid Sans Mono\", monospace, monospace;\n  \
+               white-space: pre;\n  \
+               display: block;\n  \
+               padding: 1em;\n  \
+               margin-bottom: 1em;\n  \
+               border-bottom: 2px solid black;\n  \
+               content: \"{}\";\n\
+             }}\n",
+            message.replace("*/", "*\u{2215}").replace('\n', "\n * "),
+            content
+        )
+    }
+
+    pub(crate) fn raw(self) -> (String, Span, Vec<Call>) {
         match self.kind {
-            SassErrorKind::Raw(string, span) => (string, span),
+            SassErrorKind::Raw {
+                message,
+                span,
+                call_stack,
+            } => (message, span, call_stack.unwrap_or_default()),
             e => todo!("unable to get raw of {:?}", e),
         }
     }
 
-    pub(crate) const fn from_loc(message: String, loc: SpanLoc, unicode: bool) -> Self {
+    /// Record the calls that were being made when this error occurred,
+    /// unless they have already been recorded by an inner call
+    pub(crate) fn with_call_stack(mut self: Box<Self>, calls: &[Call]) -> Box<Self> {
+        if let SassErrorKind::Raw {
+            call_stack: call_stack @ None,
+            ..
+        } = &mut self.kind
+        {
+            *call_stack = Some(calls.to_vec());
+        }
+        self
+    }
+
+    /// Attach a location to errors that do not have one,
+    /// such as those produced while reading a file
+    pub(crate) fn with_span(self, span: Span) -> Box<Self> {
+        Box::new(match self.kind {
+            SassErrorKind::IoError(e) => SassError::raw_from(e.to_string(), span),
+            SassErrorKind::FromUtf8Error(s) => SassError::raw_from(s, span),
+            SassErrorKind::Raw { .. } | SassErrorKind::ParseError { .. } => self,
+        })
+    }
+
+    pub(crate) const fn from_loc(
+        message: String,
+        loc: SpanLoc,
+        trace: Vec<StackFrame>,
+        unicode: bool,
+    ) -> Self {
         SassError {
             kind: SassErrorKind::ParseError {
                 message,
                 loc,
+                trace,
                 unicode,
             },
         }
     }
+
+    const fn raw_from(message: String, span: Span) -> Self {
+        SassError {
+            kind: SassErrorKind::Raw {
+                message,
+                span,
+                call_stack: None,
+            },
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
 enum SassErrorKind {
     /// A raw error with no additional metadata
-    /// It contains only a `String` message and
-    /// a span
-    Raw(String, Span),
+    /// It contains only a `String` message, a span,
+    /// and the calls it occurred within
+    Raw {
+        message: String,
+        span: Span,
+        /// `None` until the error is returned from a call
+        call_stack: Option<Vec<Call>>,
+    },
     ParseError {
         message: String,
         loc: SpanLoc,
+        trace: Vec<StackFrame>,
         unicode: bool,
     },
     // we put IoErrors in an `Rc` to allow it to be
@@ -78,15 +256,16 @@ impl Display for SassError {
     // TODO: integrate with codemap-diagnostics
     #[inline]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let (message, loc, unicode) = match &self.kind {
+        let (message, loc, trace, unicode) = match &self.kind {
             SassErrorKind::ParseError {
                 message,
                 loc,
+                trace,
                 unicode,
-            } => (message, loc, *unicode),
+            } => (message, loc, trace, *unicode),
             SassErrorKind::FromUtf8Error(s) => return writeln!(f, "Error: {}", s),
             SassErrorKind::IoError(s) => return writeln!(f, "Error: {}", s),
-            SassErrorKind::Raw(..) => todo!(),
+            SassErrorKind::Raw { .. } => todo!(),
         };
 
         let first_bar = if unicode { '╷' } else { '|' };
@@ -95,7 +274,6 @@ impl Display for SassError {
         let fourth_bar = if unicode { '╵' } else { '|' };
 
         let line = loc.begin.line + 1;
-        let col = loc.begin.column + 1;
         writeln!(f, "Error: {}", message)?;
         let padding = vec![' '; format!("{}", line).len() + 1]
             .iter()
@@ -119,8 +297,7 @@ impl Display for SassError {
                 .collect::<String>()
         )?;
         writeln!(f, "{}{}", padding, fourth_bar)?;
-        writeln!(f, "./{}:{}:{}", loc.file.name(), line, col)?;
-        Ok(())
+        f.write_str(&format_trace(trace, 2))
     }
 }
 
@@ -148,18 +325,14 @@ impl From<FromUtf8Error> for Box<SassError> {
 impl From<(&str, Span)> for Box<SassError> {
     #[inline]
     fn from(error: (&str, Span)) -> Box<SassError> {
-        Box::new(SassError {
-            kind: SassErrorKind::Raw(error.0.to_owned(), error.1),
-        })
+        Box::new(SassError::raw_from(error.0.to_owned(), error.1))
     }
 }
 
 impl From<(String, Span)> for Box<SassError> {
     #[inline]
     fn from(error: (String, Span)) -> Box<SassError> {
-        Box::new(SassError {
-            kind: SassErrorKind::Raw(error.0, error.1),
-        })
+        Box::new(SassError::raw_from(error.0, error.1))
     }
 }
 
@@ -169,3 +342,157 @@ impl Error for SassError {
         "Sass parsing error"
     }
 }
+
+/// The severity of a diagnostic
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+impl Display for Severity {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Warning => f.write_str("warning"),
+            Severity::Error => f.write_str("error"),
+        }
+    }
+}
+
+/// A position within a stylesheet, where both the line and
+/// the column start at 1, as they do in dart-sass's messages
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Position {
+    /// The line, starting at 1
+    pub line: usize,
+    /// The column, starting at 1 and counted in Unicode scalar values
+    /// (`char`s) rather than bytes or UTF-16 code units
+    pub column: usize,
+}
+
+impl From<LineCol> for Position {
+    #[inline]
+    fn from(loc: LineCol) -> Self {
+        Position {
+            line: loc.line + 1,
+            column: loc.column + 1,
+        }
+    }
+}
+
+/// A call to a mixin, function, or stylesheet, recorded while parsing
+/// so that diagnostics can report where they came from
+#[derive(Debug, Clone)]
+pub(crate) struct Call {
+    /// The name given to frames within the call, e.g. `foo()` or `@import`
+    pub name: String,
+    /// Where the call was made from
+    pub span: Span,
+}
+
+/// A single location in the stack trace of a diagnostic
+#[derive(Debug, Clone)]
+pub struct StackFrame {
+    name: String,
+    loc: SpanLoc,
+}
+
+impl StackFrame {
+    /// The member containing this location: `foo()` for the mixin or
+    /// function `foo`, `@import` or `@use` for a loaded stylesheet, or
+    /// `root stylesheet`
+    #[must_use]
+    #[inline]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The path of the stylesheet containing this location
+    #[must_use]
+    #[inline]
+    pub fn path(&self) -> &Path {
+        Path::new(self.loc.file.name())
+    }
+
+    /// The position of the first character of this location, with a
+    /// 1-based line and column
+    #[must_use]
+    #[inline]
+    pub fn start(&self) -> Position {
+        Position::from(self.loc.begin)
+    }
+
+    /// The position just after the last character of this location, with
+    /// a 1-based line and column
+    #[must_use]
+    #[inline]
+    pub fn end(&self) -> Position {
+        Position::from(self.loc.end)
+    }
+}
+
+/// Write `trace` as dart-sass does, one frame per line with each frame's
+/// location padded so that the names line up, e.g.
+///
+/// ```text
+///   _mixins.scss 2:11  foo()
+///   input.scss 7:1     root stylesheet
+/// ```
+pub(crate) fn format_trace(trace: &[StackFrame], indent: usize) -> String {
+    let locations: Vec<String> = trace
+        .iter()
+        .map(|frame| {
+            format!(
+                "{} {}:{}",
+                frame.path().display(),
+                frame.start().line,
+                frame.start().column
+            )
+        })
+        .collect();
+
+    let width = locations
+        .iter()
+        .map(|l| l.chars().count())
+        .max()
+        .unwrap_or(0);
+
+    trace
+        .iter()
+        .zip(locations)
+        .map(|(frame, location)| {
+            format!(
+                "{:indent$}{:width$}  {}\n",
+                "",
+                location,
+                frame.name(),
+                indent = indent,
+                width = width
+            )
+        })
+        .collect()
+}
+
+/// The stack trace of a diagnostic at `span` that occurred within
+/// `calls`, innermost frame first
+pub(crate) fn stack_trace(map: &CodeMap, span: Span, calls: &[Call]) -> Vec<StackFrame> {
+    // each frame is named after the call it is within, which is the
+    // call made by the next frame out
+    let names = calls
+        .iter()
+        .rev()
+        .map(|call| call.name.clone())
+        .chain(std::iter::once("root stylesheet".to_owned()));
+
+    let spans = std::iter::once(span).chain(calls.iter().rev().map(|call| call.span));
+
+    spans
+        .zip(names)
+        .map(|(span, name)| StackFrame {
+            name,
+            loc: map.look_up_span(span),
+        })
+        .collect()
+}
@@ -0,0 +1,160 @@
+//! # Resolving and loading stylesheets
+use std::{
+    ffi::OsStr,
+    fmt::Debug,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::error::SassResult;
+
+/// Resolves and loads the stylesheets referenced by `@use`, `@forward`,
+/// and `@import` rules
+///
+/// Importers registered through [`Options::importer`](crate::Options::importer)
+/// are consulted in the order they were added. If none of them are able to
+/// resolve a URL, grass falls back to searching the filesystem relative to the
+/// current file and then within each of the load paths.
+///
+/// ```
+/// use std::path::{Path, PathBuf};
+///
+/// #[derive(Debug)]
+/// struct Colors;
+///
+/// impl grass::Importer for Colors {
+///     fn canonicalize(&self, url: &str, _from: &Path) -> Option<PathBuf> {
+///         if url == "pkg:colors" {
+///             Some(PathBuf::from("pkg:colors"))
+///         } else {
+///             None
+///         }
+///     }
+///
+///     fn load(&self, _canonical: &Path) -> grass::Result<String> {
+///         Ok("$primary: red;".to_string())
+///     }
+/// }
+///
+/// fn main() -> Result<(), Box<grass::Error>> {
+///     let css = grass::from_string(
+///         "@use \"pkg:colors\";\na { color: colors.$primary; }".to_string(),
+///         &grass::Options::default().importer(&Colors),
+///     )?;
+///     assert_eq!(css, "a {\n  color: red;\n}\n");
+///     Ok(())
+/// }
+/// ```
+pub trait Importer: Debug {
+    /// Resolve `url`, exactly as it was written in the stylesheet, to a path
+    /// that uniquely identifies the stylesheet it refers to. Return `None`
+    /// if this importer does not recognize `url`.
+    ///
+    /// `from` is the canonical path of the stylesheet containing the rule,
+    /// which may be used to resolve relative URLs. It is empty when the
+    /// stylesheet was passed in as a string.
+    ///
+    /// The canonical path does not need to exist on disk. It is the name
+    /// used for the stylesheet in error messages and source maps, and
+    /// stylesheets whose canonical path ends in `.sass` are parsed using
+    /// the indented syntax.
+    fn canonicalize(&self, url: &str, from: &Path) -> Option<PathBuf>;
+
+    /// Load the contents of a stylesheet previously returned by
+    /// [`canonicalize`](Importer::canonicalize)
+    fn load(&self, canonical: &Path) -> SassResult<String>;
+}
+
+/// The default importer, which searches the current directory of the file
+/// then searches in `load_paths` directories if the import has not yet been found.
+///
+/// <https://sass-lang.com/documentation/at-rules/import#finding-the-file>
+/// <https://sass-lang.com/documentation/at-rules/import#load-paths>
+#[derive(Debug)]
+pub(crate) struct FilesystemImporter<'a> {
+    load_paths: &'a [&'a Path],
+}
+
+impl<'a> FilesystemImporter<'a> {
+    pub const fn new(load_paths: &'a [&'a Path]) -> Self {
+        FilesystemImporter { load_paths }
+    }
+}
+
+impl Importer for FilesystemImporter<'_> {
+    fn canonicalize(&self, url: &str, from: &Path) -> Option<PathBuf> {
+        let path: &Path = url.as_ref();
+
+        let path_buf = if path.is_absolute() {
+            // todo: test for absolute path imports
+            path.into()
+        } else {
+            from.parent().unwrap_or_else(|| Path::new("")).join(path)
+        };
+
+        let name = path_buf.file_name().unwrap_or_else(|| OsStr::new(".."));
+
+        let paths = [
+            path_buf.with_file_name(name).with_extension("scss"),
+            path_buf
+                .with_file_name(format!("_{}", name.to_str().unwrap()))
+                .with_extension("scss"),
+            path_buf.with_file_name(name).with_extension("sass"),
+            path_buf
+                .with_file_name(format!("_{}", name.to_str().unwrap()))
+                .with_extension("sass"),
+            path_buf.clone(),
+            path_buf.join("index.scss"),
+            path_buf.join("_index.scss"),
+            path_buf.join("index.sass"),
+            path_buf.join("_index.sass"),
+        ];
+
+        for name in &paths {
+            if name.is_file() {
+                return Some(name.to_path_buf());
+            }
+        }
+
+        for path in self.load_paths {
+            let paths: Vec<PathBuf> = if path.is_dir() {
+                vec![
+                    path.join(format!("{}.scss", name.to_str().unwrap())),
+                    path.join(format!("_{}.scss", name.to_str().unwrap())),
+                    path.join(format!("{}.sass", name.to_str().unwrap())),
+                    path.join(format!("_{}.sass", name.to_str().unwrap())),
+                    path.join("index.scss"),
+                    path.join("_index.scss"),
+                    path.join("index.sass"),
+                    path.join("_index.sass"),
+                ]
+            } else {
+                vec![
+                    path.to_path_buf(),
+                    path.with_file_name(name).with_extension("scss"),
+                    path.with_file_name(format!("_{}", name.to_str().unwrap()))
+                        .with_extension("scss"),
+                    path.with_file_name(name).with_extension("sass"),
+                    path.with_file_name(format!("_{}", name.to_str().unwrap()))
+                        .with_extension("sass"),
+                    path.join("index.scss"),
+                    path.join("_index.scss"),
+                    path.join("index.sass"),
+                    path.join("_index.sass"),
+                ]
+            };
+
+            for name in paths {
+                if name.is_file() {
+                    return Some(name);
+                }
+            }
+        }
+
+        None
+    }
+
+    fn load(&self, canonical: &Path) -> SassResult<String> {
+        Ok(String::from_utf8(fs::read(canonical)?)?)
+    }
+}
@@ -0,0 +1,433 @@
+//! # The indented syntax
+//!
+//! Rather than parsing `.sass` stylesheets separately, the indented syntax is
+//! translated into the equivalent stream of SCSS tokens. Blocks are inferred
+//! from indentation, statements are terminated by newlines, and the `=` and `+`
+//! shorthands for `@mixin` and `@include` are expanded.
+//!
+//! Every token keeps the span of the character it came from, so errors and
+//! source maps point into the original file. Inserted tokens, such as braces
+//! and semicolons, use the span of an adjacent character.
+//!
+//! <https://sass-lang.com/documentation/syntax#the-indented-syntax>
+use std::{iter::Peekable, slice::SplitInclusive, sync::Arc};
+
+use codemap::{File, Span};
+
+use crate::{error::SassResult, utils::is_name_start, Token};
+
+use super::Lexer;
+
+type Lines<'a> = Peekable<SplitInclusive<'a, Token, fn(&Token) -> bool>>;
+
+/// A statement whose terminator, either `;` or `{`, depends on
+/// the indentation of the line after it
+#[derive(Debug)]
+struct Statement {
+    toks: Vec<Token>,
+    /// A trailing silent comment and the final newline
+    trailing: Vec<Token>,
+    /// The span of the last significant character of the statement
+    end: Span,
+}
+
+#[derive(Debug)]
+pub(crate) struct IndentedLexer<'a> {
+    file: &'a Arc<File>,
+    toks: Vec<Token>,
+    /// The indentation of each currently open block
+    indentation: Vec<usize>,
+    pending: Option<Statement>,
+    /// Whether this file is indented using tabs, determined by the first indented line
+    uses_tabs: Option<bool>,
+}
+
+impl<'a> IndentedLexer<'a> {
+    pub fn new(file: &'a Arc<File>) -> IndentedLexer<'a> {
+        IndentedLexer {
+            file,
+            toks: Vec::new(),
+            indentation: Vec::new(),
+            pending: None,
+            uses_tabs: None,
+        }
+    }
+
+    pub fn lex(mut self) -> SassResult<Vec<Token>> {
+        let toks: Vec<Token> = Lexer::new(self.file).collect();
+        let is_newline: fn(&Token) -> bool = |tok| tok.kind == '\n';
+        let mut lines: Lines = toks.split_inclusive(is_newline).peekable();
+
+        while let Some(line) = lines.next() {
+            let (indentation, rest) = split_indentation(line);
+
+            if is_blank(rest) {
+                continue;
+            }
+
+            let width = self.indentation_width(indentation, rest[0].pos)?;
+
+            if starts_with(rest, "//") {
+                skip_indented_lines(&mut lines, width);
+                continue;
+            }
+
+            self.end_statement(width, rest[0].pos)?;
+
+            if starts_with(rest, "/*") {
+                self.loud_comment(rest, &mut lines, width);
+                continue;
+            }
+
+            self.pending = Self::statement(rest, &mut lines);
+        }
+
+        let end = toks
+            .last()
+            .map_or_else(|| self.file.span.subspan(0, 0), |tok| tok.pos);
+        self.end_statement(0, end)?;
+
+        Ok(self.toks)
+    }
+
+    fn indentation_width(&mut self, indentation: &[Token], span: Span) -> SassResult<usize> {
+        let has_tabs = indentation.iter().any(|tok| tok.kind == '\t');
+        let has_spaces = indentation.iter().any(|tok| tok.kind == ' ');
+
+        if has_tabs && has_spaces {
+            return Err(("Indentation can't use both tabs and spaces.", span).into());
+        }
+
+        if !indentation.is_empty() {
+            match self.uses_tabs {
+                Some(true) if has_spaces => return Err(("Expected tabs, was spaces.", span).into()),
+                Some(false) if has_tabs => return Err(("Expected spaces, was tabs.", span).into()),
+                Some(..) => {}
+                None => self.uses_tabs = Some(has_tabs),
+            }
+        }
+
+        Ok(indentation.len())
+    }
+
+    fn current_indentation(&self) -> usize {
+        self.indentation.last().copied().unwrap_or(0)
+    }
+
+    /// Terminate the pending statement now that we know the indentation of the line
+    /// following it, opening or closing blocks as necessary
+    fn end_statement(&mut self, width: usize, span: Span) -> SassResult<()> {
+        let current = self.current_indentation();
+
+        if width > current {
+            let stmt = match self.pending.take() {
+                Some(stmt) => stmt,
+                None if self.toks.is_empty() => {
+                    return Err((
+                        "Indenting at the beginning of the document is illegal.",
+                        span,
+                    )
+                        .into())
+                }
+                None => return Err(("Nothing may be indented beneath a comment.", span).into()),
+            };
+
+            self.toks.extend(stmt.toks);
+            self.toks.push(Token::new(stmt.end, '{'));
+            self.toks.extend(stmt.trailing);
+            self.indentation.push(width);
+            return Ok(());
+        }
+
+        if let Some(stmt) = self.pending.take() {
+            self.toks.extend(stmt.toks);
+            self.toks.push(Token::new(stmt.end, ';'));
+            self.toks.extend(stmt.trailing);
+        }
+
+        while width < self.current_indentation() {
+            self.indentation.pop();
+            self.toks.push(Token::new(span, '}'));
+        }
+
+        if width != self.current_indentation() {
+            return Err((
+                format!(
+                    "Inconsistent indentation, expected {} {}.",
+                    self.current_indentation(),
+                    if self.uses_tabs == Some(true) {
+                        "tabs"
+                    } else {
+                        "spaces"
+                    }
+                ),
+                span,
+            )
+                .into());
+        }
+
+        Ok(())
+    }
+
+    /// Loud comments continue for as long as lines are indented beneath them,
+    /// and are closed automatically
+    fn loud_comment(&mut self, rest: &[Token], lines: &mut Lines, width: usize) {
+        let mut comment = rest.to_vec();
+
+        if !trim_end(rest).ends_with_str("*/") {
+            while let Some(line) = lines.peek() {
+                let (indentation, next) = split_indentation(line);
+                if !is_blank(next) && indentation.len() <= width {
+                    break;
+                }
+                comment.extend_from_slice(line);
+                lines.next();
+            }
+
+            let end = trim_end(&comment).len();
+            if !comment[..end].ends_with_str("*/") {
+                let span = comment[end - 1].pos;
+                comment.splice(
+                    end..end,
+                    [
+                        Token::new(span, ' '),
+                        Token::new(span, '*'),
+                        Token::new(span, '/'),
+                    ],
+                );
+            }
+        }
+
+        self.toks.append(&mut comment);
+    }
+
+    /// Gather a statement and any lines it continues onto, which happens when a
+    /// line ends with a comma or inside parentheses
+    fn statement(rest: &[Token], lines: &mut Lines) -> Option<Statement> {
+        let mut toks = Vec::new();
+        let mut scanner = Scanner::default();
+        let mut line = rest;
+
+        let trailing = loop {
+            let content_end = scanner.scan(line);
+            let content = trim_end(&line[..content_end]);
+
+            if scanner.depth > 0 || content.last().map_or(false, |tok| tok.kind == ',') {
+                if let Some(next) = lines.next() {
+                    toks.extend_from_slice(line);
+                    line = next;
+                    continue;
+                }
+            }
+
+            toks.extend_from_slice(content);
+            break line[content.len()..].to_vec();
+        };
+
+        // semicolons are implied by newlines
+        while toks.last().map_or(false, |tok| tok.kind == ';') {
+            toks.pop();
+            let len = trim_end(&toks).len();
+            toks.truncate(len);
+        }
+
+        let end = toks.last()?.pos;
+
+        Some(Statement {
+            toks: expand_shorthand(toks),
+            trailing,
+            end,
+        })
+    }
+}
+
+/// Tracks strings and nesting across the lines of a single statement
+#[derive(Debug, Default)]
+struct Scanner {
+    depth: usize,
+}
+
+impl Scanner {
+    /// Returns the index at which a trailing silent comment begins, or the
+    /// length of the line if there is none
+    fn scan(&mut self, line: &[Token]) -> usize {
+        let mut quote = None;
+        let mut i = 0;
+
+        while let Some(tok) = line.get(i) {
+            match (quote, tok.kind) {
+                (_, '\\') => i += 1,
+                (Some(q), c) if c == q => quote = None,
+                (None, '"') | (None, '\'') => quote = Some(tok.kind),
+                (None, '/') => match line.get(i + 1).map(|tok| tok.kind) {
+                    Some('/') => return i,
+                    Some('*') => {
+                        // skip to the end of the comment, if it ends on this line
+                        i += 3;
+                        while i < line.len() && !line[..=i].ends_with_str("*/") {
+                            i += 1;
+                        }
+                    }
+                    _ => {}
+                },
+                (None, '(') if line[..i].ends_with_str_ignore_case("url") => {
+                    // urls may contain `//`, which does not start a comment
+                    while i < line.len() && line[i].kind != ')' {
+                        i += 1;
+                    }
+                }
+                (None, '(') | (None, '[') | (None, '{') => self.depth += 1,
+                (None, ')') | (None, ']') | (None, '}') => {
+                    self.depth = self.depth.saturating_sub(1);
+                }
+                _ => {}
+            }
+            i += 1;
+        }
+
+        line.len()
+    }
+}
+
+/// Expand `=name` into `@mixin name` and `+name` into `@include name`, and
+/// quote the urls of unquoted imports
+fn expand_shorthand(mut toks: Vec<Token>) -> Vec<Token> {
+    let first = toks[0];
+    let next_is_name = toks
+        .get(1)
+        .map_or(false, |tok| is_name_start(tok.kind) || tok.kind == '-');
+
+    let at_rule = match first.kind {
+        '=' => "@mixin ",
+        '+' if next_is_name => "@include ",
+        '@' if toks.starts_with_str("@import")
+            && toks.get(7).map_or(false, |tok| tok.kind.is_whitespace()) =>
+        {
+            return quote_imports(&toks)
+        }
+        _ => return toks,
+    };
+
+    let mut start = 1;
+    while toks
+        .get(start)
+        .map_or(false, |tok| tok.kind.is_whitespace())
+    {
+        start += 1;
+    }
+
+    toks.splice(..start, at_rule.chars().map(|c| Token::new(first.pos, c)));
+
+    toks
+}
+
+fn quote_imports(toks: &[Token]) -> Vec<Token> {
+    let (at_rule, args) = toks.split_at("@import".len());
+    let mut quoted = at_rule.to_vec();
+
+    for arg in args.split_inclusive(|tok| tok.kind == ',') {
+        let len = if arg.ends_with_str(",") {
+            arg.len() - 1
+        } else {
+            arg.len()
+        };
+        let start = arg
+            .iter()
+            .take_while(|tok| tok.kind.is_whitespace())
+            .count();
+        let end = trim_end(&arg[..len]).len().max(start);
+        let url = &arg[start..end];
+
+        quoted.extend_from_slice(&arg[..start]);
+        if url.is_empty()
+            || url[0].kind == '"'
+            || url[0].kind == '\''
+            || url.starts_with_str_ignore_case("url(")
+        {
+            quoted.extend_from_slice(url);
+        } else {
+            quoted.push(Token::new(url[0].pos, '"'));
+            quoted.extend_from_slice(url);
+            quoted.push(Token::new(url[url.len() - 1].pos, '"'));
+        }
+        quoted.extend_from_slice(&arg[end..]);
+    }
+
+    quoted
+}
+
+fn split_indentation(line: &[Token]) -> (&[Token], &[Token]) {
+    let width = line
+        .iter()
+        .take_while(|tok| tok.kind == ' ' || tok.kind == '\t')
+        .count();
+    line.split_at(width)
+}
+
+fn is_blank(line: &[Token]) -> bool {
+    line.iter().all(|tok| tok.kind.is_whitespace())
+}
+
+fn trim_end(toks: &[Token]) -> &[Token] {
+    let len = toks.len()
+        - toks
+            .iter()
+            .rev()
+            .take_while(|tok| tok.kind.is_whitespace())
+            .count();
+    &toks[..len]
+}
+
+/// Skip lines indented further than a silent comment, which are part of it
+fn skip_indented_lines(lines: &mut Lines, width: usize) {
+    while let Some(line) = lines.peek() {
+        let (indentation, rest) = split_indentation(line);
+        if !is_blank(rest) && indentation.len() <= width {
+            break;
+        }
+        lines.next();
+    }
+}
+
+fn starts_with(toks: &[Token], s: &str) -> bool {
+    toks.starts_with_str(s)
+}
+
+trait TokenSlice {
+    fn starts_with_str(&self, s: &str) -> bool;
+    fn ends_with_str(&self, s: &str) -> bool;
+    fn starts_with_str_ignore_case(&self, s: &str) -> bool;
+    fn ends_with_str_ignore_case(&self, s: &str) -> bool;
+}
+
+impl TokenSlice for [Token] {
+    fn starts_with_str(&self, s: &str) -> bool {
+        self.len() >= s.chars().count() && self.iter().zip(s.chars()).all(|(t, c)| t.kind == c)
+    }
+
+    fn ends_with_str(&self, s: &str) -> bool {
+        self.len() >= s.chars().count()
+            && self
+                .iter()
+                .rev()
+                .zip(s.chars().rev())
+                .all(|(t, c)| t.kind == c)
+    }
+
+    fn starts_with_str_ignore_case(&self, s: &str) -> bool {
+        self.len() >= s.chars().count()
+            && self
+                .iter()
+                .zip(s.chars())
+                .all(|(t, c)| t.kind.eq_ignore_ascii_case(&c))
+    }
+
+    fn ends_with_str_ignore_case(&self, s: &str) -> bool {
+        self.len() >= s.chars().count()
+            && self
+                .iter()
+                .rev()
+                .zip(s.chars().rev())
+                .all(|(t, c)| t.kind.eq_ignore_ascii_case(&c))
+    }
+}
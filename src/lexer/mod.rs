@@ -1,8 +1,12 @@
-use std::{iter::Peekable, str::Chars, sync::Arc};
+use std::{ffi::OsStr, iter::Peekable, path::Path, str::Chars, sync::Arc};
 
 use codemap::File;
 
-use crate::Token;
+use crate::{error::SassResult, Token};
+
+use indented::IndentedLexer;
+
+mod indented;
 
 const FORM_FEED: char = '\x0C';
 
@@ -46,3 +50,18 @@ impl<'a> Lexer<'a> {
         }
     }
 }
+
+/// Tokenize the contents of `file`, translating from the
+/// indented syntax into SCSS if necessary
+pub(crate) fn tokenize(file: &Arc<File>, is_indented: bool) -> SassResult<Vec<Token>> {
+    if is_indented {
+        IndentedLexer::new(file).lex()
+    } else {
+        Ok(Lexer::new(file).collect())
+    }
+}
+
+/// Whether the stylesheet at `path` uses the indented syntax, based on its extension
+pub(crate) fn is_indented_path(path: &Path) -> bool {
+    path.extension() == Some(OsStr::new("sass"))
+}
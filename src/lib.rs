@@ -81,7 +81,9 @@ grass input.scss
 )]
 #![cfg_attr(feature = "nightly", feature(track_caller))]
 #![cfg_attr(feature = "profiling", inline(never))]
-use std::{fs, path::Path};
+use std::{collections::HashMap, path::Path};
+#[cfg(not(feature = "wasm"))]
+use std::{fs, sync::Arc};
 
 #[cfg(feature = "wasm")]
 use wasm_bindgen::prelude::*;
@@ -89,14 +91,31 @@ use wasm_bindgen::prelude::*;
 pub(crate) use beef::lean::Cow;
 
 use codemap::CodeMap;
+#[cfg(not(feature = "wasm"))]
+use codemap::File;
 
 use peekmore::PeekMore;
 
-pub use crate::error::{SassError as Error, SassResult as Result};
+pub use crate::dependencies::DependencyGraph;
+pub use crate::error::{Position, SassError as Error, SassResult as Result, Severity, StackFrame};
+pub use crate::importer::Importer;
+#[cfg(not(feature = "wasm"))]
+use crate::lexer::is_indented_path;
+pub use crate::logger::{Logger, StderrLogger};
+pub use crate::repl::Repl;
+pub use crate::sass_value::{
+    ListSeparator, SassCalculation, SassColor, SassFunctionRef, SassList, SassMap, SassNumber,
+    SassString, SassValue,
+};
+pub use crate::source_map::{SourceMap, SourceMapUrls};
 pub(crate) use crate::token::Token;
 use crate::{
+    args::FuncArgs,
     builtin::modules::{ModuleConfig, Modules},
-    lexer::Lexer,
+    common::Identifier,
+    custom_function::CustomFunction,
+    error::{stack_trace, SassResult},
+    lexer::tokenize,
     output::Css,
     parse::{
         common::{ContextFlags, NeverEmptyVec},
@@ -104,6 +123,7 @@ use crate::{
     },
     scope::{Scope, Scopes},
     selector::{Extender, Selector},
+    value::Value,
 };
 
 mod args;
@@ -111,13 +131,20 @@ mod atrule;
 mod builtin;
 mod color;
 mod common;
+mod custom_function;
+mod dependencies;
 mod error;
+mod importer;
 mod interner;
 mod lexer;
+mod logger;
 mod output;
 mod parse;
+mod repl;
+mod sass_value;
 mod scope;
 mod selector;
+mod source_map;
 mod style;
 mod token;
 mod unit;
@@ -148,6 +175,15 @@ pub struct Options<'a> {
     allows_charset: bool,
     unicode_error_messages: bool,
     quiet: bool,
+    logger: Option<&'a dyn Logger>,
+    warn_slash_division: bool,
+    indented_syntax: bool,
+    importers: Vec<&'a dyn Importer>,
+    dependencies: Option<&'a DependencyGraph>,
+    functions: Vec<CustomFunction>,
+    global_variables: Vec<(Identifier, SassValue)>,
+    /// The url of the module, and a variable to configure it with
+    module_config: Vec<(String, Identifier, SassValue)>,
 }
 
 impl Default for Options<'_> {
@@ -159,6 +195,14 @@ impl Default for Options<'_> {
             allows_charset: true,
             unicode_error_messages: true,
             quiet: false,
+            logger: None,
+            warn_slash_division: false,
+            indented_syntax: false,
+            importers: Vec::new(),
+            dependencies: None,
+            functions: Vec::new(),
+            global_variables: Vec::new(),
+            module_config: Vec::new(),
         }
     }
 }
@@ -193,6 +237,32 @@ impl<'a> Options<'a> {
         self
     }
 
+    /// Send warnings and `@debug` messages to `logger` rather
+    /// than writing them to stderr
+    ///
+    /// See [`Logger`](Logger) for more information.
+    #[must_use]
+    #[inline]
+    pub fn logger(mut self, logger: &'a dyn Logger) -> Self {
+        self.logger = Some(logger);
+        self
+    }
+
+    /// Emit a deprecation warning whenever `/` is used as division
+    /// rather than as a separator, e.g. in `$half: $width / 2`
+    ///
+    /// Sass will stop treating `/` as division in a future release, and
+    /// such uses should be replaced with `math.div()` or `calc()`. These
+    /// warnings help find them ahead of time.
+    ///
+    /// By default, this value is `false` and no warnings are emitted.
+    #[must_use]
+    #[inline]
+    pub fn warn_slash_division(mut self, warn_slash_division: bool) -> Self {
+        self.warn_slash_division = warn_slash_division;
+        self
+    }
+
     /// All Sass implementations allow users to provide
     /// load paths: paths on the filesystem that Sass
     /// will look in when locating modules. For example,
@@ -225,6 +295,152 @@ impl<'a> Options<'a> {
         self
     }
 
+    /// Register a custom [`Importer`](Importer), used to resolve
+    /// the URLs of `@use`, `@forward`, and `@import` rules.
+    ///
+    /// Importers are consulted in the order they are added. If no
+    /// importer is able to resolve a URL, Sass falls back to searching
+    /// the filesystem relative to the current file and then
+    /// within the load paths.
+    #[must_use]
+    #[inline]
+    pub fn importer(mut self, importer: &'a dyn Importer) -> Self {
+        self.importers.push(importer);
+        self
+    }
+
+    /// Record every stylesheet loaded through `@use`, `@forward`,
+    /// and `@import` rules in `graph`
+    ///
+    /// Stylesheets are recorded as they are loaded, so the graph
+    /// is filled in even if compilation fails.
+    #[must_use]
+    #[inline]
+    pub fn track_dependencies(mut self, graph: &'a DependencyGraph) -> Self {
+        self.dependencies = Some(graph);
+        self
+    }
+
+    /// Register a Rust closure as a Sass function available to
+    /// all stylesheets, with a signature such as `asset-url($path, $hash: false)`.
+    ///
+    /// Arguments are bound just as they are for functions declared with
+    /// `@function`: they may be passed by position or by name, and default
+    /// values are evaluated in the caller's scope. The closure receives the
+    /// value of each declared argument in order, with a variadic argument
+    /// received as a list. Returning `Err` fails compilation with the
+    /// given message. Any state the closure captures must be owned, e.g.
+    /// by using a `move` closure.
+    ///
+    /// Functions declared within a stylesheet take precedence over these,
+    /// which in turn take precedence over the builtin functions.
+    ///
+    /// Every signature is parsed when compilation begins, so if the
+    /// arguments of `signature` are not valid Sass, e.g. `double($n`,
+    /// compiling any stylesheet with these options returns an error.
+    ///
+    /// ```
+    /// # use grass::{Options, SassValue};
+    /// fn main() -> Result<(), Box<grass::Error>> {
+    ///     let options = Options::default().add_function("double($n)", |args: &[SassValue]| {
+    ///         match args[0].as_number() {
+    ///             Some((n, unit)) => Ok(SassValue::number(n * 2.0, &unit)),
+    ///             None => Err("$n: Expected a number.".to_string()),
+    ///         }
+    ///     });
+    ///     let css = grass::from_string("a { width: double(2px); }".to_string(), &options)?;
+    ///     assert_eq!(css, "a {\n  width: 4px;\n}\n");
+    ///     Ok(())
+    /// }
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn add_function<F>(mut self, signature: &str, function: F) -> Self
+    where
+        F: Fn(&[SassValue]) -> std::result::Result<SassValue, String> + 'static,
+    {
+        self.functions
+            .push(CustomFunction::new(signature, Box::new(function)));
+        self
+    }
+
+    /// Define a global variable before compilation begins, as though it
+    /// were declared at the top of the stylesheet. The leading `$` of
+    /// `name` is optional.
+    ///
+    /// The variable is visible to the stylesheet being compiled and any
+    /// files it `@import`s, but not to modules loaded with `@use`, which
+    /// should be configured through [`Options::module_config`](Options::module_config)
+    /// instead. Declarations using `!default` do not override it.
+    ///
+    /// ```
+    /// # use grass::{Options, SassValue};
+    /// fn main() -> Result<(), Box<grass::Error>> {
+    ///     let options = Options::default().global_variable("$brand", SassValue::string("#639", false));
+    ///     let css = grass::from_string(
+    ///         "$brand: red !default;\na { color: $brand; }".to_string(),
+    ///         &options,
+    ///     )?;
+    ///     assert_eq!(css, "a {\n  color: #639;\n}\n");
+    ///     Ok(())
+    /// }
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn global_variable(mut self, name: &str, value: SassValue) -> Self {
+        self.global_variables.push((variable_name(name), value));
+        self
+    }
+
+    /// Define multiple global variables
+    ///
+    /// See [`Options::global_variable`](Options::global_variable) for more information
+    #[must_use]
+    #[inline]
+    pub fn global_variables<I, S>(mut self, variables: I) -> Self
+    where
+        I: IntoIterator<Item = (S, SassValue)>,
+        S: AsRef<str>,
+    {
+        self.global_variables.extend(
+            variables
+                .into_iter()
+                .map(|(name, value)| (variable_name(name.as_ref()), value)),
+        );
+        self
+    }
+
+    /// Configure a variable of the module loaded by `@use` or `@forward`
+    /// with the given URL, as though the rule were written with
+    /// `with ($name: value)`. The leading `$` of `name` is optional.
+    ///
+    /// The URL must match the one written in the stylesheet. As with
+    /// `with`, the module must declare the variable using `!default`,
+    /// or compilation fails. Variables configured by the stylesheet's
+    /// own `with` clause take precedence.
+    ///
+    /// ```no_run
+    /// # use grass::{Options, SassValue};
+    /// fn main() -> Result<(), Box<grass::Error>> {
+    ///     // _theme.scss contains `$brand: red !default;`
+    ///     let options = Options::default()
+    ///         .module_config("theme", "$brand", SassValue::string("#639", false));
+    ///     let css = grass::from_string(
+    ///         "@use \"theme\";\na { color: theme.$brand; }".to_string(),
+    ///         &options,
+    ///     )?;
+    ///     assert_eq!(css, "a {\n  color: #639;\n}\n");
+    ///     Ok(())
+    /// }
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn module_config(mut self, url: &str, name: &str, value: SassValue) -> Self {
+        self.module_config
+            .push((url.to_owned(), variable_name(name), value));
+        self
+    }
+
     /// This flag tells Sass whether to emit a `@charset`
     /// declaration or a UTF-8 byte-order mark.
     ///
@@ -252,39 +468,115 @@ impl<'a> Options<'a> {
         self.unicode_error_messages = unicode_error_messages;
         self
     }
+
+    /// This flag tells Sass to parse input passed to
+    /// [`from_string`](from_string) using the indented syntax
+    /// rather than SCSS.
+    ///
+    /// Files are always parsed according to their extension:
+    /// `.sass` files use the indented syntax and all others use SCSS.
+    ///
+    /// By default, this value is `false` and strings are parsed as SCSS.
+    #[must_use]
+    #[inline]
+    pub fn indented_syntax(mut self, indented_syntax: bool) -> Self {
+        self.indented_syntax = indented_syntax;
+        self
+    }
+}
+
+impl Options<'_> {
+    /// The logger messages should be sent to, or `None` if they
+    /// have been silenced
+    pub(crate) fn active_logger(&self) -> Option<&dyn Logger> {
+        if self.quiet {
+            None
+        } else {
+            Some(self.logger.unwrap_or(&StderrLogger))
+        }
+    }
+
+    pub(crate) fn custom_function(&self, name: Identifier) -> Option<&CustomFunction> {
+        self.functions.iter().rev().find(|f| f.name == name)
+    }
+
+    /// The arguments of every function added through `add_function`,
+    /// which are parsed up front so that an invalid signature is
+    /// reported even if the function is never called
+    pub(crate) fn custom_function_args(
+        &self,
+        map: &mut CodeMap,
+    ) -> SassResult<HashMap<Identifier, FuncArgs>> {
+        let mut args = HashMap::new();
+        // a later function replaces an earlier one of the same name
+        for function in &self.functions {
+            args.insert(function.name, function.parse_args(map, self)?);
+        }
+        Ok(args)
+    }
+
+    /// The scope containing the global variables given by the host
+    pub(crate) fn global_scope(&self) -> Scope {
+        let mut scope = Scope::new();
+        for (name, value) in &self.global_variables {
+            scope.insert_var(*name, value.clone().to_value());
+        }
+        scope
+    }
+
+    /// The variables given by the host to configure the module with this url,
+    /// most recently added first
+    pub(crate) fn module_config_for<'b>(
+        &'b self,
+        url: &'b str,
+    ) -> impl Iterator<Item = (Identifier, Value)> + 'b {
+        self.module_config
+            .iter()
+            .rev()
+            .filter(move |(module, ..)| module == url)
+            .map(|(_, name, value)| (*name, value.clone().to_value()))
+    }
+}
+
+fn variable_name(name: &str) -> Identifier {
+    Identifier::from(name.strip_prefix('$').unwrap_or(name))
 }
 
 fn raw_to_parse_error(map: &CodeMap, err: Error, unicode: bool) -> Box<Error> {
-    let (message, span) = err.raw();
-    Box::new(Error::from_loc(message, map.look_up_span(span), unicode))
+    let (message, span, call_stack) = err.raw();
+    let trace = stack_trace(map, span, &call_stack);
+    Box::new(Error::from_loc(
+        message,
+        map.look_up_span(span),
+        trace,
+        unicode,
+    ))
 }
 
-/// Compile CSS from a path
-///
-/// ```
-/// fn main() -> Result<(), Box<grass::Error>> {
-///     let sass = grass::from_path("input.scss", &grass::Options::default())?;
-///     Ok(())
-/// }
-/// ```
-/// (grass does not currently allow files or paths that are not valid UTF-8)
-#[cfg_attr(feature = "profiling", inline(never))]
-#[cfg_attr(not(feature = "profiling"), inline)]
+/// Parse and evaluate a stylesheet that has already been added to `map`
 #[cfg(not(feature = "wasm"))]
-pub fn from_path(p: &str, options: &Options) -> Result<String> {
-    let mut map = CodeMap::new();
-    let file = map.add_file(p.into(), String::from_utf8(fs::read(p)?)?);
+fn compile(
+    map: &mut CodeMap,
+    file: &Arc<File>,
+    path: &Path,
+    is_indented: bool,
+    options: &Options,
+) -> Result<Css> {
     let empty_span = file.span.subspan(0, 0);
 
+    let toks = tokenize(file, is_indented)
+        .map_err(|e| raw_to_parse_error(map, *e, options.unicode_error_messages))?;
+
+    let mut custom_function_args = options
+        .custom_function_args(map)
+        .map_err(|e| raw_to_parse_error(map, *e, options.unicode_error_messages))?;
+
     let stmts = Parser {
-        toks: &mut Lexer::new(&file)
-            .collect::<Vec<Token>>()
-            .into_iter()
-            .peekmore(),
-        map: &mut map,
-        path: p.as_ref(),
+        toks: &mut toks.into_iter().peekmore(),
+        map,
+        path,
         scopes: &mut Scopes::new(),
-        global_scope: &mut Scope::new(),
+        global_scope: &mut options.global_scope(),
         super_selectors: &mut NeverEmptyVec::new(Selector::new(empty_span)),
         span_before: empty_span,
         content: &mut Vec::new(),
@@ -292,18 +584,79 @@ pub fn from_path(p: &str, options: &Options) -> Result<String> {
         at_root: true,
         at_root_has_selector: false,
         extender: &mut Extender::new(empty_span),
+        media_queries: &mut None,
         content_scopes: &mut Scopes::new(),
         options,
         modules: &mut Modules::default(),
         module_config: &mut ModuleConfig::default(),
+        forwarded: &mut Scope::new(),
+        call_stack: &mut Vec::new(),
+        custom_function_args: &mut custom_function_args,
     }
     .parse()
-    .map_err(|e| raw_to_parse_error(&map, *e, options.unicode_error_messages))?;
+    .map_err(|e| raw_to_parse_error(map, *e, options.unicode_error_messages))?;
 
     Css::from_stmts(stmts, false, options.allows_charset)
-        .map_err(|e| raw_to_parse_error(&map, *e, options.unicode_error_messages))?
-        .pretty_print(&map)
-        .map_err(|e| raw_to_parse_error(&map, *e, options.unicode_error_messages))
+        .map_err(|e| raw_to_parse_error(map, *e, options.unicode_error_messages))
+}
+
+/// Compile CSS from a path
+///
+/// ```
+/// fn main() -> Result<(), Box<grass::Error>> {
+///     let sass = grass::from_path("input.scss", &grass::Options::default())?;
+///     Ok(())
+/// }
+/// ```
+/// (grass does not currently allow files or paths that are not valid UTF-8)
+#[cfg_attr(feature = "profiling", inline(never))]
+#[cfg_attr(not(feature = "profiling"), inline)]
+#[cfg(not(feature = "wasm"))]
+pub fn from_path(p: &str, options: &Options) -> Result<String> {
+    let mut map = CodeMap::new();
+    let file = map.add_file(p.into(), String::from_utf8(fs::read(p)?)?);
+
+    compile(
+        &mut map,
+        &file,
+        p.as_ref(),
+        is_indented_path(p.as_ref()),
+        options,
+    )?
+    .pretty_print(&map, &options.style)
+    .map_err(|e| raw_to_parse_error(&map, *e, options.unicode_error_messages))
+}
+
+/// Compile CSS from a path, additionally generating a source map
+///
+/// The source map refers to source files by the paths they were loaded from.
+/// Use [`SourceMap::source_urls`](SourceMap::source_urls) to make these relative
+/// to the location the map will be written to.
+///
+/// ```no_run
+/// fn main() -> Result<(), Box<grass::Error>> {
+///     let (css, source_map) =
+///         grass::from_path_with_source_map("input.scss", &grass::Options::default())?;
+///     std::fs::write("input.css.map", source_map.file("input.css".to_string()).to_json())?;
+///     Ok(())
+/// }
+/// ```
+#[cfg_attr(feature = "profiling", inline(never))]
+#[cfg_attr(not(feature = "profiling"), inline)]
+#[cfg(not(feature = "wasm"))]
+pub fn from_path_with_source_map(p: &str, options: &Options) -> Result<(String, SourceMap)> {
+    let mut map = CodeMap::new();
+    let file = map.add_file(p.into(), String::from_utf8(fs::read(p)?)?);
+
+    compile(
+        &mut map,
+        &file,
+        p.as_ref(),
+        is_indented_path(p.as_ref()),
+        options,
+    )?
+    .pretty_print_with_source_map(&map, &options.style)
+    .map_err(|e| raw_to_parse_error(&map, *e, options.unicode_error_messages))
 }
 
 /// Compile CSS from a string
@@ -321,35 +674,49 @@ pub fn from_path(p: &str, options: &Options) -> Result<String> {
 pub fn from_string(p: String, options: &Options) -> Result<String> {
     let mut map = CodeMap::new();
     let file = map.add_file("stdin".into(), p);
-    let empty_span = file.span.subspan(0, 0);
-    let stmts = Parser {
-        toks: &mut Lexer::new(&file)
-            .collect::<Vec<Token>>()
-            .into_iter()
-            .peekmore(),
-        map: &mut map,
-        path: Path::new(""),
-        scopes: &mut Scopes::new(),
-        global_scope: &mut Scope::new(),
-        super_selectors: &mut NeverEmptyVec::new(Selector::new(empty_span)),
-        span_before: empty_span,
-        content: &mut Vec::new(),
-        flags: ContextFlags::empty(),
-        at_root: true,
-        at_root_has_selector: false,
-        extender: &mut Extender::new(empty_span),
-        content_scopes: &mut Scopes::new(),
+
+    compile(
+        &mut map,
+        &file,
+        Path::new(""),
+        options.indented_syntax,
         options,
-        modules: &mut Modules::default(),
-        module_config: &mut ModuleConfig::default(),
-    }
-    .parse()
-    .map_err(|e| raw_to_parse_error(&map, *e, options.unicode_error_messages))?;
+    )?
+    .pretty_print(&map, &options.style)
+    .map_err(|e| raw_to_parse_error(&map, *e, options.unicode_error_messages))
+}
 
-    Css::from_stmts(stmts, false, options.allows_charset)
-        .map_err(|e| raw_to_parse_error(&map, *e, options.unicode_error_messages))?
-        .pretty_print(&map)
-        .map_err(|e| raw_to_parse_error(&map, *e, options.unicode_error_messages))
+/// Compile CSS from a string, additionally generating a source map
+///
+/// The input is referred to as `stdin` in the source map.
+///
+/// ```
+/// fn main() -> Result<(), Box<grass::Error>> {
+///     let (css, source_map) = grass::from_string_with_source_map(
+///         "a { color: red; }".to_string(),
+///         &grass::Options::default(),
+///     )?;
+///     assert_eq!(css, "a {\n  color: red;\n}\n");
+///     assert_eq!(source_map.mappings(), "AAAA;EAAI");
+///     Ok(())
+/// }
+/// ```
+#[cfg_attr(feature = "profiling", inline(never))]
+#[cfg_attr(not(feature = "profiling"), inline)]
+#[cfg(not(feature = "wasm"))]
+pub fn from_string_with_source_map(p: String, options: &Options) -> Result<(String, SourceMap)> {
+    let mut map = CodeMap::new();
+    let file = map.add_file("stdin".into(), p);
+
+    compile(
+        &mut map,
+        &file,
+        Path::new(""),
+        options.indented_syntax,
+        options,
+    )?
+    .pretty_print_with_source_map(&map, &options.style)
+    .map_err(|e| raw_to_parse_error(&map, *e, options.unicode_error_messages))
 }
 
 #[cfg(feature = "wasm")]
@@ -360,8 +727,8 @@ pub fn from_string(p: String) -> std::result::Result<String, JsValue> {
     let empty_span = file.span.subspan(0, 0);
 
     let stmts = Parser {
-        toks: &mut Lexer::new(&file)
-            .collect::<Vec<Token>>()
+        toks: &mut tokenize(&file, false)
+            .map_err(|e| raw_to_parse_error(&map, *e, true).to_string())?
             .into_iter()
             .peekmore(),
         map: &mut map,
@@ -375,16 +742,20 @@ pub fn from_string(p: String) -> std::result::Result<String, JsValue> {
         at_root: true,
         at_root_has_selector: false,
         extender: &mut Extender::new(empty_span),
+        media_queries: &mut None,
         content_scopes: &mut Scopes::new(),
         options: &Options::default(),
         modules: &mut Modules::default(),
         module_config: &mut ModuleConfig::default(),
+        forwarded: &mut Scope::new(),
+        call_stack: &mut Vec::new(),
+        custom_function_args: &mut HashMap::new(),
     }
     .parse()
     .map_err(|e| raw_to_parse_error(&map, *e, true).to_string())?;
 
     Ok(Css::from_stmts(stmts, false, true)
         .map_err(|e| raw_to_parse_error(&map, *e, true).to_string())?
-        .pretty_print(&map)
+        .pretty_print(&map, &OutputStyle::Expanded)
         .map_err(|e| raw_to_parse_error(&map, *e, true).to_string())?)
 }
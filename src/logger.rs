@@ -0,0 +1,78 @@
+//! # Reporting warnings and debug messages
+use std::fmt::Debug;
+
+use crate::error::{format_trace, StackFrame};
+
+/// Receives the messages emitted while compiling that are not errors:
+/// `@warn` and `@debug` rules, and warnings about deprecated features
+///
+/// A logger registered through [`Options::logger`](crate::Options::logger)
+/// replaces the default [`StderrLogger`]. No messages are logged at all
+/// when [`Options::quiet`](crate::Options::quiet) is set.
+///
+/// ```
+/// use std::cell::RefCell;
+///
+/// #[derive(Debug, Default)]
+/// struct Collect(RefCell<Vec<String>>);
+///
+/// impl grass::Logger for Collect {
+///     fn warn(&self, message: &str, _deprecation: bool, _trace: &[grass::StackFrame]) {
+///         self.0.borrow_mut().push(message.to_string());
+///     }
+///
+///     fn debug(&self, _message: &str, _location: &grass::StackFrame) {}
+/// }
+///
+/// fn main() -> Result<(), Box<grass::Error>> {
+///     let logger = Collect::default();
+///     grass::from_string(
+///         "@warn \"careful\";".to_string(),
+///         &grass::Options::default().logger(&logger),
+///     )?;
+///     assert_eq!(logger.0.into_inner(), vec!["careful".to_string()]);
+///     Ok(())
+/// }
+/// ```
+pub trait Logger: Debug {
+    /// Called for each `@warn` rule and each use of a deprecated feature,
+    /// in which case `deprecation` is `true`
+    ///
+    /// `trace` is never empty. Its first frame is the location of the
+    /// warning and the rest are the calls that led to it, innermost first.
+    fn warn(&self, message: &str, deprecation: bool, trace: &[StackFrame]);
+
+    /// Called for each `@debug` rule, with `message` formatted
+    /// as it would be by `inspect()`
+    fn debug(&self, message: &str, location: &StackFrame);
+}
+
+/// The default logger, which writes every message to stderr
+#[derive(Debug, Default, Clone, Copy)]
+pub struct StderrLogger;
+
+impl Logger for StderrLogger {
+    #[inline]
+    fn warn(&self, message: &str, deprecation: bool, trace: &[StackFrame]) {
+        eprint!(
+            "{}: {}\n{}\n",
+            if deprecation {
+                "DEPRECATION WARNING"
+            } else {
+                "Warning"
+            },
+            message,
+            format_trace(trace, 4)
+        );
+    }
+
+    #[inline]
+    fn debug(&self, message: &str, location: &StackFrame) {
+        eprintln!(
+            "{}:{} Debug: {}",
+            location.path().display(),
+            location.start().line,
+            message
+        );
+    }
+}
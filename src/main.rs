@@ -1,13 +1,25 @@
+#[cfg(not(feature = "wasm"))]
 use std::{
-    fs::File,
-    io::{stdin, stdout, BufWriter, Read, Write},
-    path::Path,
+    ffi::OsStr,
+    fs::{self, File},
+    io::{stdin, stdout, BufRead, BufWriter, Read, Write},
+    path::{Path, PathBuf},
+    sync::mpsc,
+    thread,
+    time::{Duration, SystemTime},
 };
 
-use clap::{arg_enum, App, AppSettings, Arg};
+use clap::arg_enum;
+#[cfg(not(feature = "wasm"))]
+use clap::{value_t, App, AppSettings, Arg, ArgMatches, Error, ErrorKind};
+#[cfg(not(feature = "wasm"))]
+use notify::{DebouncedEvent, RecursiveMode};
 
 #[cfg(not(feature = "wasm"))]
-use grass::{from_path, from_string, Options};
+use grass::{
+    from_path, from_path_with_source_map, from_string, from_string_with_source_map,
+    DependencyGraph, Options, OutputStyle, Repl, SourceMap,
+};
 
 arg_enum! {
     #[derive(PartialEq, Debug)]
@@ -17,6 +29,14 @@ arg_enum! {
     }
 }
 
+arg_enum! {
+    #[derive(PartialEq, Debug)]
+    pub enum ErrorFormat {
+        Human,
+        Json,
+    }
+}
+
 arg_enum! {
     #[derive(PartialEq, Debug)]
     pub enum SourceMapUrls {
@@ -44,7 +64,6 @@ fn main() -> std::io::Result<()> {
         .arg(
             Arg::with_name("INDENTED")
                 .long("indented")
-                .hidden(true)
                 .help("Use the indented syntax for input from stdin"),
         )
         .arg(
@@ -62,7 +81,6 @@ fn main() -> std::io::Result<()> {
                 // this is required for compatibility with ruby sass
                 .short("t")
                 .long("style")
-                .hidden(true)
                 .help("Minified or expanded output")
                 .default_value("expanded")
                 .case_insensitive(true)
@@ -77,26 +95,32 @@ fn main() -> std::io::Result<()> {
         .arg(
             Arg::with_name("UPDATE")
                 .long("update")
-                .hidden(true)
                 .help("Only compile out-of-date stylesheets."),
         )
         .arg(
             Arg::with_name("NO_ERROR_CSS")
                 .long("no-error-css")
-                .hidden(true)
                 .help("When an error occurs, don't emit a stylesheet describing it."),
         )
+        .arg(
+            Arg::with_name("ERROR_FORMAT")
+                .long("error-format")
+                .help("How to print errors. JSON errors are printed one per line.")
+                .default_value("human")
+                .case_insensitive(true)
+                .possible_values(&ErrorFormat::variants())
+                .takes_value(true),
+        )
         // Source maps
         .arg(
             Arg::with_name("NO_SOURCE_MAP")
                 .long("no-source-map")
-                .hidden(true)
-                .help("Whether to generate source maps."),
+                .help("Don't generate source maps.")
+                .conflicts_with_all(&["EMBED_SOURCES", "EMBED_SOURCE_MAP"]),
         )
         .arg(
             Arg::with_name("SOURCE_MAP_URLS")
                 .long("source-map-urls")
-                .hidden(true)
                 .help("How to link from source maps to source files.")
                 .default_value("relative")
                 .case_insensitive(true)
@@ -106,40 +130,36 @@ fn main() -> std::io::Result<()> {
         .arg(
             Arg::with_name("EMBED_SOURCES")
                 .long("embed-sources")
-                .hidden(true)
                 .help("Embed source file contents in source maps."),
         )
         .arg(
             Arg::with_name("EMBED_SOURCE_MAP")
                 .long("embed-source-map")
-                .hidden(true)
                 .help("Embed source map contents in CSS."),
         )
         // Other
         .arg(
             Arg::with_name("WATCH")
                 .long("watch")
-                .hidden(true)
-                .help("Watch stylesheets and recompile when they change."),
+                .help("Watch stylesheets and recompile when they change.")
+                .requires("INPUT")
+                .conflicts_with("STDIN"),
         )
         .arg(
             Arg::with_name("POLL")
                 .long("poll")
-                .hidden(true)
                 .help("Manually check for changes rather than using a native watcher. Only valid with --watch.")
                 .requires("WATCH"),
         )
         .arg(
             Arg::with_name("NO_STOP_ON_ERROR")
                 .long("no-stop-on-error")
-                .hidden(true)
                 .help("Continue to compile more files after error is encountered.")
         )
         .arg(
             Arg::with_name("INTERACTIVE")
                 .short("i")
                 .long("interactive")
-                .hidden(true)
                 .help("Run an interactive SassScript shell.")
         )
         .arg(
@@ -161,13 +181,15 @@ fn main() -> std::io::Result<()> {
                 .help("Don't print warnings."),
         )
         .arg(
-            Arg::with_name("INPUT")
-                .required_unless("STDIN")
-                .help("SCSS files"),
+            Arg::with_name("WARN_SLASH_DIVISION")
+                .long("warn-slash-division")
+                .help("Print a deprecation warning whenever / is used as division."),
         )
         .arg(
-            Arg::with_name("OUTPUT")
-                .help("Output SCSS file")
+            Arg::with_name("INPUT")
+                .required_unless_one(&["STDIN", "INTERACTIVE"])
+                .multiple(true)
+                .help("An input SCSS file and optional output CSS file, or any number of in:out pairs of files or directories"),
         )
 
         // Hidden, legacy arguments
@@ -183,41 +205,766 @@ fn main() -> std::io::Result<()> {
         .values_of("LOAD_PATH")
         .map_or_else(Vec::new, |vals| vals.map(Path::new).collect());
 
-    let options = &Options::default()
+    let style = value_t!(matches, "STYLE", Style).unwrap_or_else(|e| e.exit());
+
+    let graph = DependencyGraph::new();
+
+    let mut options = Options::default()
+        .style(match style {
+            Style::Expanded => OutputStyle::Expanded,
+            Style::Compressed => OutputStyle::Compressed,
+        })
         .load_paths(&load_paths)
         .quiet(matches.is_present("QUIET"))
+        .warn_slash_division(matches.is_present("WARN_SLASH_DIVISION"))
+        .indented_syntax(matches.is_present("INDENTED"))
         .unicode_error_messages(!matches.is_present("NO_UNICODE"))
         .allows_charset(!matches.is_present("NO_CHARSET"));
 
+    if matches.is_present("INTERACTIVE") {
+        return interactive(&options);
+    }
+
+    if matches.is_present("STDIN") && !matches.is_present("INPUT") {
+        let options = &options;
+        let mut buffer = String::new();
+        stdin().read_to_string(&mut buffer)?;
+        let result = if generate_source_map(None, &matches) {
+            from_string_with_source_map(buffer, options).map(|(css, map)| (css, Some(map)))
+        } else {
+            from_string(buffer, options).map(|css| (css, None))
+        };
+        let (css, source_map) = result.unwrap_or_else(|e| {
+            report_error(&e, None, &matches);
+            std::process::exit(1)
+        });
+        write_css(css, source_map, None, &matches)?;
+        return Ok(());
+    }
+
+    let pairs = pairs(&matches).unwrap_or_else(|e| e.exit());
+
+    let update = matches.is_present("UPDATE");
+
+    if matches.is_present("WATCH") || update {
+        options = options.track_dependencies(&graph);
+    }
+
+    if matches.is_present("WATCH") {
+        if pairs.iter().any(|pair| pair.output.is_none()) {
+            Error::with_description(
+                "--watch is not allowed when printing to stdout.",
+                ErrorKind::ArgumentConflict,
+            )
+            .exit();
+        }
+
+        let watcher = Watcher {
+            pairs: &pairs,
+            options: &options,
+            matches: &matches,
+            graph: &graph,
+        };
+
+        if !matches.is_present("POLL") {
+            if let Err(e) = watcher.watch() {
+                eprintln!(
+                    "Unable to watch for changes ({}), falling back to polling.",
+                    e
+                );
+            }
+        }
+
+        watcher.poll();
+    }
+
+    let stop_on_error = !matches.is_present("NO_STOP_ON_ERROR");
+    let mut failed = false;
+
+    for pair in &pairs {
+        let targets = pair.targets().unwrap_or_else(|e| {
+            eprintln!("Error: {}", e);
+            std::process::exit(1)
+        });
+
+        for target in targets {
+            graph.clear();
+            let result = compile(&target.input, target.output.as_deref(), &options, &matches);
+
+            if update && !target.is_stale(&graph)? {
+                continue;
+            }
+
+            let written = result.and_then(|(css, source_map)| {
+                Ok(write_css(
+                    css,
+                    source_map,
+                    target.output.as_deref(),
+                    &matches,
+                )?)
+            });
+
+            match written {
+                Ok(()) => {
+                    if update {
+                        target.report_compiled();
+                    }
+                }
+                Err(e) => {
+                    report_error(&e, target.output.as_deref(), &matches);
+                    if stop_on_error {
+                        std::process::exit(1);
+                    }
+                    failed = true;
+                }
+            }
+        }
+    }
+
+    if failed {
+        std::process::exit(1);
+    }
+
+    Ok(())
+}
+
+/// An input and output given on the command line, either as separate
+/// arguments or as an `in:out` pair
+///
+/// Both the input and output of a pair may be directories, in which case
+/// every stylesheet in the input directory is compiled to the same relative
+/// path in the output directory.
+#[cfg(not(feature = "wasm"))]
+#[derive(Debug)]
+struct Pair {
+    input: PathBuf,
+    /// `None` when the CSS should be written to stdout
+    output: Option<PathBuf>,
+}
+
+#[cfg(not(feature = "wasm"))]
+impl Pair {
+    /// The stylesheets to compile for this pair
+    fn targets(&self) -> std::io::Result<Vec<Target>> {
+        let output = match &self.output {
+            Some(output) if self.input.is_dir() => output,
+            _ => {
+                return Ok(vec![Target {
+                    input: self.input.clone(),
+                    output: self.output.clone(),
+                }])
+            }
+        };
+
+        Ok(sass_files(&self.input, true)?
+            .into_iter()
+            .filter(|path| !is_partial(path))
+            .map(|path| {
+                let relative = path.strip_prefix(&self.input).unwrap_or(&path);
+                Target {
+                    output: Some(output.join(relative).with_extension("css")),
+                    input: path,
+                }
+            })
+            .collect())
+    }
+}
+
+/// A single stylesheet to compile
+#[cfg(not(feature = "wasm"))]
+#[derive(Debug, PartialEq)]
+struct Target {
+    input: PathBuf,
+    /// `None` when the CSS should be written to stdout
+    output: Option<PathBuf>,
+}
+
+#[cfg(not(feature = "wasm"))]
+impl Target {
+    /// Whether the output is missing, or older than the input or any
+    /// stylesheet it loaded, directly or transitively
+    ///
+    /// `graph` must hold the stylesheets loaded while compiling the input.
+    fn is_stale(&self, graph: &DependencyGraph) -> std::io::Result<bool> {
+        let output = match &self.output {
+            Some(output) => output,
+            None => return Ok(true),
+        };
+
+        let compiled = match fs::metadata(output).and_then(|m| m.modified()) {
+            Ok(time) => time,
+            Err(..) => return Ok(true),
+        };
+
+        let mut sources = vec![self.input.clone()];
+        sources.extend(graph.files());
+
+        Ok(modified_times(&sources)
+            .into_iter()
+            .any(|modified| modified.map_or(true, |time| time > compiled)))
+    }
+
+    fn report_compiled(&self) {
+        if let Some(output) = &self.output {
+            println!("Compiled {} to {}.", self.input.display(), output.display());
+        }
+    }
+}
+
+/// Interpret the positional arguments, which are either a single input
+/// followed by an optional output, or any number of `in:out` pairs
+#[cfg(not(feature = "wasm"))]
+fn pairs(matches: &ArgMatches) -> Result<Vec<Pair>, Error> {
+    let args: Vec<&str> = matches
+        .values_of("INPUT")
+        .map_or_else(Vec::new, Iterator::collect);
+
+    let split: Vec<Option<(&str, &str)>> = args.iter().map(|arg| split_pair(arg)).collect();
+
+    if split.iter().all(Option::is_none) {
+        return match args.as_slice() {
+            [input] => Ok(vec![Pair {
+                input: PathBuf::from(input),
+                output: None,
+            }]),
+            [input, output] => Ok(vec![Pair {
+                input: PathBuf::from(input),
+                output: Some(PathBuf::from(output)),
+            }]),
+            _ => Err(Error::with_description(
+                "Only two positional args may be passed.",
+                ErrorKind::TooManyValues,
+            )),
+        };
+    }
+
+    split
+        .into_iter()
+        .map(|pair| match pair {
+            Some((input, output)) => Ok(Pair {
+                input: PathBuf::from(input),
+                output: Some(PathBuf::from(output)),
+            }),
+            None => Err(Error::with_description(
+                "Positional and \":\" arguments may not both be used.",
+                ErrorKind::ArgumentConflict,
+            )),
+        })
+        .collect()
+}
+
+/// Split an `in:out` argument at its colon, ignoring the colon of
+/// a leading Windows drive letter such as `C:\`
+#[cfg(not(feature = "wasm"))]
+fn split_pair(arg: &str) -> Option<(&str, &str)> {
+    let mut chars = arg.chars();
+    let start = match (chars.next(), chars.next(), chars.next()) {
+        (Some(letter), Some(':'), Some('\\')) | (Some(letter), Some(':'), Some('/'))
+            if letter.is_ascii_alphabetic() =>
+        {
+            2
+        }
+        _ => 0,
+    };
+
+    let idx = start + arg[start..].find(':')?;
+
+    Some((&arg[..idx], &arg[idx + 1..]))
+}
+
+/// Every `.scss` and `.sass` file within `dir`, and optionally its
+/// subdirectories, in a consistent order
+#[cfg(not(feature = "wasm"))]
+fn sass_files(dir: &Path, recursive: bool) -> std::io::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    let mut entries = fs::read_dir(dir)?
+        .map(|entry| entry.map(|e| e.path()))
+        .collect::<std::io::Result<Vec<PathBuf>>>()?;
+    entries.sort();
+
+    for path in entries {
+        if path.is_dir() {
+            if recursive {
+                files.extend(sass_files(&path, true)?);
+            }
+        } else if matches!(
+            path.extension().and_then(OsStr::to_str),
+            Some("scss") | Some("sass")
+        ) {
+            files.push(path);
+        }
+    }
+
+    Ok(files)
+}
+
+/// Partials, whose names begin with an underscore, are only
+/// meant to be loaded by other stylesheets
+#[cfg(not(feature = "wasm"))]
+fn is_partial(path: &Path) -> bool {
+    path.file_name()
+        .and_then(OsStr::to_str)
+        .map_or(false, |name| name.starts_with('_'))
+}
+
+/// Read SassScript from stdin line by line, printing the result of each line
+#[cfg(not(feature = "wasm"))]
+fn interactive(options: &Options) -> std::io::Result<()> {
+    let mut repl = Repl::new(options);
+    let stdin = stdin();
+    let mut line = String::new();
+
+    loop {
+        print!(">> ");
+        stdout().flush()?;
+
+        line.clear();
+        if stdin.lock().read_line(&mut line)? == 0 {
+            println!();
+            return Ok(());
+        }
+
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        match repl.evaluate(line.trim_end()) {
+            Ok(Some(value)) => println!("{}", value),
+            Ok(None) => {}
+            Err(e) => eprint!("{}", e),
+        }
+    }
+}
+
+/// Whether to generate a source map for CSS written to `output`
+#[cfg(not(feature = "wasm"))]
+fn generate_source_map(output: Option<&Path>, matches: &ArgMatches) -> bool {
+    // without an output file, there is nowhere to write a separate source map
+    !matches.is_present("NO_SOURCE_MAP")
+        && (output.is_some() || matches.is_present("EMBED_SOURCE_MAP"))
+}
+
+/// Compile the stylesheet at `input`, along with a source map if one
+/// should be written alongside `output`
+#[cfg(not(feature = "wasm"))]
+fn compile(
+    input: &Path,
+    output: Option<&Path>,
+    options: &Options,
+    matches: &ArgMatches,
+) -> grass::Result<(String, Option<SourceMap>)> {
+    let input = &input.to_string_lossy();
+
+    Ok(if generate_source_map(output, matches) {
+        from_path_with_source_map(input, options).map(|(css, map)| (css, Some(map)))?
+    } else {
+        (from_path(input, options)?, None)
+    })
+}
+
+/// Compile the stylesheet at `input`, writing the result to `output`
+/// or to stdout
+#[cfg(not(feature = "wasm"))]
+fn compile_path(
+    input: &Path,
+    output: Option<&Path>,
+    options: &Options,
+    matches: &ArgMatches,
+) -> grass::Result<()> {
+    let (css, source_map) = compile(input, output, options, matches)?;
+
+    write_css(css, source_map, output, matches)?;
+
+    Ok(())
+}
+
+/// Print an error from compiling the stylesheet for `output`, and unless
+/// `--no-error-css` was passed, write a stylesheet describing the error in
+/// its place so that it is visible in the browser
+#[cfg(not(feature = "wasm"))]
+fn report_error(error: &grass::Error, output: Option<&Path>, matches: &ArgMatches) {
+    match value_t!(matches, "ERROR_FORMAT", ErrorFormat).unwrap_or_else(|e| e.exit()) {
+        ErrorFormat::Human => eprintln!("{}", error),
+        ErrorFormat::Json => eprintln!("{}", error_to_json(error)),
+    }
+
+    // like dart-sass, error CSS is never written to stdout
+    let output = match output {
+        Some(output) if !matches.is_present("NO_ERROR_CSS") => output,
+        _ => return,
+    };
+
+    let written = output
+        .parent()
+        .map_or(Ok(()), fs::create_dir_all)
+        .and_then(|()| fs::write(output, error.to_css()));
+
+    if let Err(e) = written {
+        eprintln!("Error: {}", e);
+    }
+}
+
+/// Describe an error as a single line of JSON, for editors and other tools
+///
+/// Location fields are `null` for errors that don't point into a stylesheet,
+/// such as a missing input file.
+#[cfg(not(feature = "wasm"))]
+fn error_to_json(error: &grass::Error) -> String {
+    fn position(position: Option<grass::Position>) -> String {
+        position.map_or_else(
+            || "null".to_owned(),
+            |p| format!("{{\"line\":{},\"column\":{}}}", p.line, p.column),
+        )
+    }
+
+    fn path(path: Option<&Path>) -> String {
+        path.map_or_else(|| "null".to_owned(), |p| json_string(&p.to_string_lossy()))
+    }
+
+    let trace: Vec<String> = error
+        .trace()
+        .iter()
+        .map(|frame| {
+            format!(
+                "{{\"name\":{},\"file\":{},\"line\":{},\"column\":{}}}",
+                json_string(frame.name()),
+                path(Some(frame.path())),
+                frame.start().line,
+                frame.start().column
+            )
+        })
+        .collect();
+
+    format!(
+        "{{\"message\":{},\"severity\":{},\"file\":{},\"start\":{},\"end\":{},\"snippet\":{},\"trace\":[{}]}}",
+        json_string(&error.message()),
+        json_string(&error.severity().to_string()),
+        path(error.path()),
+        position(error.start()),
+        position(error.end()),
+        error.snippet().map_or_else(|| "null".to_owned(), json_string),
+        trace.join(",")
+    )
+}
+
+#[cfg(not(feature = "wasm"))]
+fn json_string(s: &str) -> String {
+    let mut buf = String::with_capacity(s.len() + 2);
+    buf.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => buf.push_str("\\\""),
+            '\\' => buf.push_str("\\\\"),
+            '\n' => buf.push_str("\\n"),
+            '\r' => buf.push_str("\\r"),
+            '\t' => buf.push_str("\\t"),
+            c if c.is_control() => buf.push_str(&format!("\\u{:04x}", c as u32)),
+            c => buf.push(c),
+        }
+    }
+    buf.push('"');
+    buf
+}
+
+/// Write compiled CSS, along with its source map, to `output` or to stdout
+#[cfg(not(feature = "wasm"))]
+fn write_css(
+    mut css: String,
+    source_map: Option<SourceMap>,
+    output: Option<&Path>,
+    matches: &ArgMatches,
+) -> std::io::Result<()> {
+    let is_compressed =
+        value_t!(matches, "STYLE", Style).unwrap_or_else(|e| e.exit()) == Style::Compressed;
+    let embed_source_map = matches.is_present("EMBED_SOURCE_MAP");
+
+    if let Some(dir) = output.and_then(Path::parent) {
+        fs::create_dir_all(dir)?;
+    }
+
+    if let Some(source_map) = source_map {
+        let urls = match value_t!(matches, "SOURCE_MAP_URLS", SourceMapUrls)
+            .unwrap_or_else(|e| e.exit())
+        {
+            SourceMapUrls::Relative => grass::SourceMapUrls::Relative,
+            SourceMapUrls::Absolute => grass::SourceMapUrls::Absolute,
+        };
+
+        // the source map is always written alongside the CSS
+        let map_dir = output
+            .and_then(Path::parent)
+            .unwrap_or_else(|| Path::new("."));
+
+        let mut source_map = source_map
+            .embed_sources(matches.is_present("EMBED_SOURCES"))
+            .source_urls(urls, map_dir);
+
+        if let Some(file_name) = output.and_then(Path::file_name) {
+            source_map = source_map.file(file_name.to_string_lossy().into_owned());
+        }
+
+        let url = if embed_source_map {
+            source_map.to_data_url()
+        } else {
+            // `generate_source_map` guarantees an output path here
+            let output = output.unwrap();
+            let mut map_path = output.as_os_str().to_owned();
+            map_path.push(".map");
+            let map_path = PathBuf::from(map_path);
+
+            fs::write(&map_path, source_map.to_json())?;
+
+            map_path.file_name().unwrap().to_string_lossy().into_owned()
+        };
+
+        if is_compressed {
+            css.push_str(&format!("/*# sourceMappingURL={} */", url));
+        } else {
+            css.push_str(&format!("\n/*# sourceMappingURL={} */\n", url));
+        }
+    }
+
     let (mut stdout_write, mut file_write);
-    let buf_out: &mut dyn Write = if let Some(path) = matches.value_of("OUTPUT") {
-        file_write = BufWriter::new(File::open(path).unwrap_or(File::create(path)?));
+    let buf_out: &mut dyn Write = if let Some(path) = output {
+        file_write = BufWriter::new(File::create(path)?);
         &mut file_write
     } else {
         stdout_write = BufWriter::new(stdout());
         &mut stdout_write
     };
 
-    buf_out.write_all(
-        if let Some(name) = matches.value_of("INPUT") {
-            from_path(name, &options)
-        } else if matches.is_present("STDIN") {
-            from_string(
-                {
-                    let mut buffer = String::new();
-                    stdin().read_to_string(&mut buffer)?;
-                    buffer
-                },
-                options,
-            )
-        } else {
-            unreachable!()
+    buf_out.write_all(css.as_bytes())?;
+    buf_out.flush()
+}
+
+/// How often `--poll` checks stylesheets for changes
+#[cfg(not(feature = "wasm"))]
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Recompiles stylesheets whenever they, or any stylesheet they load, change
+#[cfg(not(feature = "wasm"))]
+struct Watcher<'a> {
+    pairs: &'a [Pair],
+    options: &'a Options<'a>,
+    matches: &'a ArgMatches<'a>,
+    /// The graph tracked by `options`
+    graph: &'a DependencyGraph,
+}
+
+/// A target compiled by the watcher, along with the files it depended on
+#[cfg(not(feature = "wasm"))]
+struct Compiled {
+    target: Target,
+    /// The absolute path of the input and every stylesheet it loaded
+    files: Vec<PathBuf>,
+    /// The modification times of `files` when the target was compiled
+    modified: Vec<Option<SystemTime>>,
+}
+
+#[cfg(not(feature = "wasm"))]
+impl Watcher<'_> {
+    /// Compile a stylesheet, reporting rather than exiting on errors
+    fn compile(&self, target: Target) -> Compiled {
+        self.graph.clear();
+
+        match compile_path(
+            &target.input,
+            target.output.as_deref(),
+            self.options,
+            self.matches,
+        ) {
+            Ok(()) => target.report_compiled(),
+            Err(e) => report_error(&e, target.output.as_deref(), self.matches),
         }
-        .unwrap_or_else(|e| {
-            eprintln!("{}", e);
-            std::process::exit(1)
-        })
-        .as_bytes(),
-    )?;
-    Ok(())
+
+        let mut files = vec![absolute(&target.input)];
+        for path in self.graph.files() {
+            let path = absolute(&path);
+            if !files.contains(&path) {
+                files.push(path);
+            }
+        }
+
+        Compiled {
+            target,
+            modified: modified_times(&files),
+            files,
+        }
+    }
+
+    /// Compile stylesheets that have been added to the input directories,
+    /// and recompile any stylesheet for which `changed` returns `true`
+    fn update(
+        &self,
+        compiled: Vec<Compiled>,
+        changed: impl Fn(&Compiled) -> bool,
+    ) -> Vec<Compiled> {
+        let mut previous = compiled;
+        let mut next = Vec::new();
+
+        for pair in self.pairs {
+            let targets = match pair.targets() {
+                Ok(targets) => targets,
+                Err(e) => {
+                    eprintln!("Error: {}", e);
+                    continue;
+                }
+            };
+
+            for target in targets {
+                match previous.iter().position(|c| c.target == target) {
+                    Some(idx) => {
+                        let c = previous.swap_remove(idx);
+                        next.push(if changed(&c) { self.compile(target) } else { c });
+                    }
+                    None => next.push(self.compile(target)),
+                }
+            }
+        }
+
+        next
+    }
+
+    /// Check the modification time of every dependency at a fixed interval
+    fn poll(&self) -> ! {
+        let mut compiled = self.update(Vec::new(), |_| true);
+
+        println!("Sass is watching for changes. Press Ctrl-C to stop.\n");
+
+        loop {
+            thread::sleep(POLL_INTERVAL);
+
+            compiled = self.update(compiled, |c| modified_times(&c.files) != c.modified);
+        }
+    }
+
+    /// Use the platform's native file notifications to wait for changes,
+    /// returning only if the watcher could not be set up
+    ///
+    /// The directories containing the dependencies are watched rather than
+    /// the files themselves so that changes made by editors which replace
+    /// a file instead of writing to it are still noticed.
+    fn watch(&self) -> notify::Result<()> {
+        let (tx, rx) = mpsc::channel();
+        let mut watcher = notify::watcher(tx, Duration::from_millis(50))?;
+
+        let mut compiled = self.update(Vec::new(), |_| true);
+        let mut dirs = watch_dirs(&mut watcher, self.dirs(&compiled), Vec::new())?;
+
+        println!("Sass is watching for changes. Press Ctrl-C to stop.\n");
+
+        while let Ok(event) = rx.recv() {
+            let paths = match event {
+                DebouncedEvent::Create(path)
+                | DebouncedEvent::Write(path)
+                | DebouncedEvent::Chmod(path)
+                | DebouncedEvent::Remove(path) => vec![absolute(&path)],
+                DebouncedEvent::Rename(from, to) => vec![absolute(&from), absolute(&to)],
+                // events may have been missed, so everything is recompiled
+                DebouncedEvent::Rescan => {
+                    compiled = self.update(compiled, |_| true);
+                    dirs = watch_dirs(&mut watcher, self.dirs(&compiled), dirs)?;
+                    continue;
+                }
+                DebouncedEvent::Error(e, _) => {
+                    eprintln!("Error: {}", e);
+                    continue;
+                }
+                DebouncedEvent::NoticeWrite(..) | DebouncedEvent::NoticeRemove(..) => continue,
+            };
+
+            compiled = self.update(compiled, |c| paths.iter().any(|p| c.files.contains(p)));
+            dirs = watch_dirs(&mut watcher, self.dirs(&compiled), dirs)?;
+        }
+
+        Ok(())
+    }
+
+    /// The directories containing every dependency, along with every
+    /// input directory so that new stylesheets are noticed
+    fn dirs(&self, compiled: &[Compiled]) -> Vec<PathBuf> {
+        let mut dirs: Vec<PathBuf> = Vec::new();
+        let mut add = |dir: PathBuf| {
+            if !dirs.contains(&dir) {
+                dirs.push(dir);
+            }
+        };
+
+        for c in compiled {
+            for file in &c.files {
+                if let Some(parent) = file.parent() {
+                    add(parent.to_path_buf());
+                }
+            }
+        }
+
+        for pair in self.pairs.iter().filter(|pair| pair.input.is_dir()) {
+            add(absolute(&pair.input));
+            for dir in sub_dirs(&pair.input) {
+                add(absolute(&dir));
+            }
+        }
+
+        dirs
+    }
+}
+
+/// Watch `dirs`, replacing the watches on `watched`, and return
+/// the directories now being watched
+#[cfg(not(feature = "wasm"))]
+fn watch_dirs(
+    watcher: &mut impl notify::Watcher,
+    dirs: Vec<PathBuf>,
+    watched: Vec<PathBuf>,
+) -> notify::Result<Vec<PathBuf>> {
+    for dir in watched.iter().filter(|dir| !dirs.contains(dir)) {
+        // the directory may have been removed, which also removes its watch
+        let _ = watcher.unwatch(dir);
+    }
+
+    for dir in dirs.iter().filter(|dir| !watched.contains(dir)) {
+        watcher.watch(dir, RecursiveMode::NonRecursive)?;
+    }
+
+    Ok(dirs)
+}
+
+/// Every directory within `dir`, recursively
+#[cfg(not(feature = "wasm"))]
+fn sub_dirs(dir: &Path) -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    if let Ok(entries) = fs::read_dir(dir) {
+        for path in entries.filter_map(|entry| entry.ok().map(|e| e.path())) {
+            if path.is_dir() {
+                dirs.extend(sub_dirs(&path));
+                dirs.push(path);
+            }
+        }
+    }
+    dirs
+}
+
+/// An absolute version of `path` which can be compared with the paths
+/// reported by the watcher, even if the file no longer exists
+#[cfg(not(feature = "wasm"))]
+fn absolute(path: &Path) -> PathBuf {
+    let parent = match path.parent() {
+        Some(parent) if parent != Path::new("") => parent,
+        _ => Path::new("."),
+    };
+
+    match (parent.canonicalize(), path.file_name()) {
+        (Ok(parent), Some(name)) => parent.join(name),
+        _ => path.to_path_buf(),
+    }
+}
+
+/// The last modification time of each file, or `None` for files
+/// that cannot be read
+#[cfg(not(feature = "wasm"))]
+fn modified_times(files: &[PathBuf]) -> Vec<Option<SystemTime>> {
+    files
+        .iter()
+        .map(|file| fs::metadata(file).and_then(|m| m.modified()).ok())
+        .collect()
 }
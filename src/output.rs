@@ -1,18 +1,20 @@
 //! # Convert from SCSS AST to CSS
 use std::io::Write;
 
-use codemap::CodeMap;
+use codemap::{CodeMap, Span};
 
 use crate::{
     atrule::{
         keyframes::{Keyframes, KeyframesRuleSet, KeyframesSelector},
-        media::MediaRule,
-        SupportsRule, UnknownAtRule,
+        media::{MediaQuery, MediaRule},
+        SupportsCondition, SupportsRule, UnknownAtRule,
     },
     error::SassResult,
     parse::Stmt,
     selector::Selector,
+    source_map::{SourceMap, SourceMapBuilder},
     style::Style,
+    OutputStyle,
 };
 
 #[derive(Debug, Clone)]
@@ -20,6 +22,7 @@ struct ToplevelUnknownAtRule {
     name: String,
     params: String,
     body: Vec<Stmt>,
+    span: Span,
 }
 
 #[derive(Debug, Clone)]
@@ -29,8 +32,17 @@ enum Toplevel {
     UnknownAtRule(Box<ToplevelUnknownAtRule>),
     Keyframes(Box<Keyframes>),
     KeyframesRuleSet(Vec<KeyframesSelector>, Vec<BlockEntry>),
-    Media { query: String, body: Vec<Stmt> },
-    Supports { params: String, body: Vec<Stmt> },
+    Media {
+        query: Vec<MediaQuery>,
+        body: Vec<Toplevel>,
+        is_merged: bool,
+        span: Span,
+    },
+    Supports {
+        condition: SupportsCondition,
+        body: Vec<Toplevel>,
+        span: Span,
+    },
     Newline,
     // todo: do we actually need a toplevel style variant?
     Style(Style),
@@ -89,6 +101,18 @@ impl Toplevel {
             panic!()
         }
     }
+
+    /// Whether this block produces no output
+    fn is_invisible(&self) -> bool {
+        match self {
+            Toplevel::RuleSet(_, entries) => entries.is_empty(),
+            Toplevel::Media { body, .. } | Toplevel::Supports { body, .. } => {
+                body.iter().all(Toplevel::is_invisible)
+            }
+            Toplevel::Newline => true,
+            _ => false,
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -107,6 +131,15 @@ impl Css {
         }
     }
 
+    /// The contents of an at-rule whose children have already been converted
+    const fn new_with_blocks(blocks: Vec<Toplevel>, allows_charset: bool) -> Self {
+        Css {
+            blocks,
+            in_at_rule: true,
+            allows_charset,
+        }
+    }
+
     pub(crate) fn from_stmts(
         s: Vec<Stmt>,
         in_at_rule: bool,
@@ -131,22 +164,21 @@ impl Css {
                         Stmt::RuleSet { .. } => vals.extend(self.parse_stmt(rule)?),
                         Stmt::Style(s) => vals.first_mut().unwrap().push_style(s),
                         Stmt::Comment(s) => vals.first_mut().unwrap().push_comment(s),
-                        Stmt::Media(m) => {
-                            let MediaRule { query, body, .. } = *m;
-                            vals.push(Toplevel::Media { query, body })
-                        }
-                        Stmt::Supports(s) => {
-                            let SupportsRule { params, body } = *s;
-                            vals.push(Toplevel::Supports { params, body })
-                        }
+                        Stmt::Media(m) => vals.extend(self.parse_media(*m)?),
+                        Stmt::Supports(s) => vals.extend(self.parse_supports(*s)?),
                         Stmt::UnknownAtRule(u) => {
                             let UnknownAtRule {
-                                params, body, name, ..
+                                params,
+                                body,
+                                name,
+                                span,
+                                ..
                             } = *u;
                             vals.push(Toplevel::UnknownAtRule(Box::new(ToplevelUnknownAtRule {
                                 params,
                                 body,
                                 name,
+                                span,
                             })))
                         }
                         Stmt::Return(..) => unreachable!(),
@@ -156,14 +188,7 @@ impl Css {
                                 Ok(())
                             })?
                         }
-                        Stmt::Keyframes(k) => {
-                            let Keyframes { rule, name, body } = *k;
-                            vals.push(Toplevel::Keyframes(Box::new(Keyframes {
-                                rule,
-                                name,
-                                body,
-                            })))
-                        }
+                        Stmt::Keyframes(k) => vals.push(Toplevel::Keyframes(k)),
                         k @ Stmt::KeyframesRuleSet(..) => {
                             unreachable!("@keyframes ruleset {:?}", k)
                         }
@@ -175,22 +200,21 @@ impl Css {
             Stmt::Comment(s) => vec![Toplevel::MultilineComment(s)],
             Stmt::Import(s) => vec![Toplevel::Import(s)],
             Stmt::Style(s) => vec![Toplevel::Style(s)],
-            Stmt::Media(m) => {
-                let MediaRule { query, body, .. } = *m;
-                vec![Toplevel::Media { query, body }]
-            }
-            Stmt::Supports(s) => {
-                let SupportsRule { params, body } = *s;
-                vec![Toplevel::Supports { params, body }]
-            }
+            Stmt::Media(m) => self.parse_media(*m)?,
+            Stmt::Supports(s) => self.parse_supports(*s)?,
             Stmt::UnknownAtRule(u) => {
                 let UnknownAtRule {
-                    params, body, name, ..
+                    params,
+                    body,
+                    name,
+                    span,
+                    ..
                 } = *u;
                 vec![Toplevel::UnknownAtRule(Box::new(ToplevelUnknownAtRule {
                     params,
                     name,
                     body,
+                    span,
                 }))]
             }
             Stmt::Return(..) => unreachable!("@return: {:?}", stmt),
@@ -214,6 +238,66 @@ impl Css {
         })
     }
 
+    /// Convert an `@media` rule, along with any rules within it whose queries
+    /// were merged with its own, which are emitted after it
+    fn parse_media(&self, media: MediaRule) -> SassResult<Vec<Toplevel>> {
+        let MediaRule {
+            query,
+            body,
+            is_merged,
+            span,
+            ..
+        } = media;
+
+        let mut merged_rules = Vec::new();
+        let body: Vec<Toplevel> = Css::from_stmts(body, true, self.allows_charset)?
+            .blocks
+            .into_iter()
+            .filter_map(|block| match block {
+                Toplevel::Media {
+                    is_merged: true, ..
+                } => {
+                    merged_rules.push(block);
+                    None
+                }
+                _ => Some(block),
+            })
+            .collect();
+
+        if body.iter().all(Toplevel::is_invisible) {
+            return Ok(merged_rules);
+        }
+
+        let mut vals = vec![Toplevel::Media {
+            query,
+            body,
+            is_merged,
+            span,
+        }];
+        vals.append(&mut merged_rules);
+        Ok(vals)
+    }
+
+    fn parse_supports(&self, supports: SupportsRule) -> SassResult<Vec<Toplevel>> {
+        let SupportsRule {
+            condition,
+            body,
+            span,
+        } = supports;
+
+        let body = Css::from_stmts(body, true, self.allows_charset)?.blocks;
+
+        if body.iter().all(Toplevel::is_invisible) {
+            return Ok(Vec::new());
+        }
+
+        Ok(vec![Toplevel::Supports {
+            condition,
+            body,
+            span,
+        }])
+    }
+
     fn parse_stylesheet(mut self, stmts: Vec<Stmt>) -> SassResult<Css> {
         let mut is_first = true;
         for stmt in stmts {
@@ -233,42 +317,289 @@ impl Css {
         Ok(self)
     }
 
-    pub fn pretty_print(self, map: &CodeMap) -> SassResult<String> {
-        let mut string = Vec::new();
+    pub fn pretty_print(self, map: &CodeMap, style: &OutputStyle) -> SassResult<String> {
+        self.write(map, style, &mut SourceMapBuilder::new(false))
+    }
+
+    /// Like `pretty_print`, but additionally records where each emitted
+    /// selector and declaration came from
+    pub fn pretty_print_with_source_map(
+        self,
+        map: &CodeMap,
+        style: &OutputStyle,
+    ) -> SassResult<(String, SourceMap)> {
+        let mut source_map = SourceMapBuilder::new(true);
+        let css = self.write(map, style, &mut source_map)?;
+        Ok((css, source_map.build(map)))
+    }
+
+    fn write(
+        self,
+        map: &CodeMap,
+        style: &OutputStyle,
+        source_map: &mut SourceMapBuilder,
+    ) -> SassResult<String> {
+        let mut buf = Vec::new();
         let allows_charset = self.allows_charset;
-        self._inner_pretty_print(&mut string, map, 0)?;
-        if allows_charset && string.iter().any(|s| !s.is_ascii()) {
-            return Ok(format!("@charset \"UTF-8\";\n{}", unsafe {
-                String::from_utf8_unchecked(string)
-            }));
+        match style {
+            OutputStyle::Compressed => {
+                CompressedFormatter.write_css(&mut buf, self, map, source_map)?;
+            }
+            OutputStyle::Expanded => {
+                ExpandedFormatter::default().write_css(&mut buf, self, map, source_map)?;
+            }
         }
-        Ok(unsafe { String::from_utf8_unchecked(string) })
+        let string = unsafe { String::from_utf8_unchecked(buf) };
+        if allows_charset && !string.is_ascii() {
+            return Ok(match style {
+                // compressed output uses a byte-order mark rather than `@charset`
+                OutputStyle::Compressed => {
+                    source_map.shift(0, 1);
+                    format!("\u{FEFF}{}", string)
+                }
+                OutputStyle::Expanded => {
+                    source_map.shift(1, 0);
+                    format!("@charset \"UTF-8\";\n{}", string)
+                }
+            });
+        }
+        Ok(string)
     }
+}
 
-    fn _inner_pretty_print(
-        self,
+trait Formatter {
+    fn write_css(
+        &mut self,
         buf: &mut Vec<u8>,
+        css: Css,
         map: &CodeMap,
-        nesting: usize,
+        source_map: &mut SourceMapBuilder,
+    ) -> SassResult<()>;
+}
+
+#[derive(Debug, Default)]
+struct CompressedFormatter;
+
+impl Formatter for CompressedFormatter {
+    fn write_css(
+        &mut self,
+        buf: &mut Vec<u8>,
+        css: Css,
+        map: &CodeMap,
+        source_map: &mut SourceMapBuilder,
+    ) -> SassResult<()> {
+        let mut blocks = css
+            .blocks
+            .into_iter()
+            .filter(|block| match block {
+                Toplevel::MultilineComment(s) => s.starts_with('!'),
+                _ => !block.is_invisible(),
+            })
+            .peekable();
+
+        while let Some(block) = blocks.next() {
+            match block {
+                Toplevel::RuleSet(selector, styles) => {
+                    if styles.is_empty() {
+                        continue;
+                    }
+
+                    source_map.add_mapping(buf, selector.0.span);
+                    write!(buf, "{:#}{{", selector)?;
+                    Self::write_block_entries(buf, styles, source_map)?;
+                    write!(buf, "}}")?;
+                }
+                Toplevel::KeyframesRuleSet(selector, styles) => {
+                    if styles.is_empty() {
+                        continue;
+                    }
+
+                    write!(
+                        buf,
+                        "{}{{",
+                        selector
+                            .into_iter()
+                            .map(|s| s.to_string())
+                            .collect::<Vec<String>>()
+                            .join(",")
+                    )?;
+                    Self::write_block_entries(buf, styles, source_map)?;
+                    write!(buf, "}}")?;
+                }
+                Toplevel::MultilineComment(s) => {
+                    if s.starts_with('!') {
+                        write!(buf, "/*{}*/", s)?;
+                    }
+                }
+                Toplevel::Import(s) => {
+                    write!(buf, "@import {};", s)?;
+                }
+                Toplevel::UnknownAtRule(u) => {
+                    let ToplevelUnknownAtRule {
+                        params,
+                        name,
+                        body,
+                        span,
+                    } = *u;
+
+                    source_map.add_mapping(buf, span);
+                    if params.is_empty() {
+                        write!(buf, "@{}", name)?;
+                    } else {
+                        write!(buf, "@{} {}", name, params)?;
+                    }
+
+                    if body.is_empty() {
+                        write!(buf, ";")?;
+                        continue;
+                    }
+
+                    write!(buf, "{{")?;
+                    let css = Css::from_stmts(body, true, css.allows_charset)?;
+                    self.write_css(buf, css, map, source_map)?;
+                    write!(buf, "}}")?;
+                }
+                Toplevel::Keyframes(k) => {
+                    let Keyframes {
+                        rule,
+                        name,
+                        body,
+                        span,
+                    } = *k;
+
+                    source_map.add_mapping(buf, span);
+                    write!(buf, "@{}", rule)?;
+
+                    if !name.is_empty() {
+                        write!(buf, " {}", name)?;
+                    }
+
+                    if body.is_empty() {
+                        write!(buf, "{{}}")?;
+                        continue;
+                    }
+
+                    write!(buf, "{{")?;
+                    let css = Css::from_stmts(body, true, css.allows_charset)?;
+                    self.write_css(buf, css, map, source_map)?;
+                    write!(buf, "}}")?;
+                }
+                Toplevel::Supports {
+                    condition,
+                    body,
+                    span,
+                } => {
+                    source_map.add_mapping(buf, span);
+                    let condition = condition.to_string();
+                    // a space is only needed before an identifier
+                    if condition.starts_with('(') {
+                        write!(buf, "@supports{}{{", condition)?;
+                    } else {
+                        write!(buf, "@supports {}{{", condition)?;
+                    }
+                    let css = Css::new_with_blocks(body, css.allows_charset);
+                    self.write_css(buf, css, map, source_map)?;
+                    write!(buf, "}}")?;
+                }
+                Toplevel::Media {
+                    query, body, span, ..
+                } => {
+                    source_map.add_mapping(buf, span);
+                    write!(buf, "@media")?;
+                    // a space is only needed before an identifier
+                    if query.first().map_or(false, |query| !query.is_condition()) {
+                        write!(buf, " ")?;
+                    }
+                    write!(buf, "{}{{", MediaQuery::serialize_list(&query, true))?;
+                    let css = Css::new_with_blocks(body, css.allows_charset);
+                    self.write_css(buf, css, map, source_map)?;
+                    write!(buf, "}}")?;
+                }
+                Toplevel::Style(style) => {
+                    source_map.add_mapping(buf, style.property.span);
+                    write!(buf, "{}", style.to_compressed_string()?)?;
+                    // like within a style rule, the final declaration
+                    // has no semicolon
+                    if blocks.peek().is_some() {
+                        write!(buf, ";")?;
+                    }
+                }
+                Toplevel::Newline => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+impl CompressedFormatter {
+    /// Writes the contents of a style rule, omitting the semicolon
+    /// after the final declaration
+    fn write_block_entries(
+        buf: &mut Vec<u8>,
+        entries: Vec<BlockEntry>,
+        source_map: &mut SourceMapBuilder,
+    ) -> SassResult<()> {
+        let mut entries = entries
+            .into_iter()
+            .filter(|entry| match entry {
+                BlockEntry::MultilineComment(s) => s.starts_with('!'),
+                BlockEntry::Style(..) | BlockEntry::Import(..) => true,
+            })
+            .peekable();
+
+        while let Some(entry) = entries.next() {
+            match entry {
+                BlockEntry::Style(style) => {
+                    source_map.add_mapping(buf, style.property.span);
+                    write!(buf, "{}", style.to_compressed_string()?)?;
+                }
+                BlockEntry::MultilineComment(s) => {
+                    write!(buf, "/*{}*/", s)?;
+                    continue;
+                }
+                BlockEntry::Import(s) => write!(buf, "@import {}", s)?,
+            }
+
+            if entries.peek().is_some() {
+                write!(buf, ";")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Default)]
+struct ExpandedFormatter {
+    nesting: usize,
+}
+
+impl Formatter for ExpandedFormatter {
+    fn write_css(
+        &mut self,
+        buf: &mut Vec<u8>,
+        css: Css,
+        map: &CodeMap,
+        source_map: &mut SourceMapBuilder,
     ) -> SassResult<()> {
         let mut has_written = false;
-        let padding = vec![' '; nesting * 2].iter().collect::<String>();
+        let padding = vec![' '; self.nesting * 2].iter().collect::<String>();
         let mut should_emit_newline = false;
-        for block in self.blocks {
+        for block in css.blocks {
             match block {
                 Toplevel::RuleSet(selector, styles) => {
                     if styles.is_empty() {
                         continue;
                     }
                     has_written = true;
-                    if should_emit_newline && !self.in_at_rule {
+                    if should_emit_newline && !css.in_at_rule {
                         should_emit_newline = false;
                         writeln!(buf)?;
                     }
-                    writeln!(buf, "{}{} {{", padding, selector)?;
-                    for style in styles {
-                        writeln!(buf, "{}  {}", padding, style.to_string()?)?;
-                    }
+                    write!(buf, "{}", padding)?;
+                    source_map.add_mapping(buf, selector.0.span);
+                    writeln!(buf, "{} {{", selector)?;
+                    Self::write_block_entries(buf, &padding, styles, source_map)?;
                     writeln!(buf, "{}}}", padding)?;
                 }
                 Toplevel::KeyframesRuleSet(selector, body) => {
@@ -287,9 +618,7 @@ impl Css {
                             .collect::<Vec<String>>()
                             .join(", ")
                     )?;
-                    for style in body {
-                        writeln!(buf, "{}  {}", padding, style.to_string()?)?;
-                    }
+                    Self::write_block_entries(buf, &padding, body, source_map)?;
                     writeln!(buf, "{}}}", padding)?;
                 }
                 Toplevel::MultilineComment(s) => {
@@ -301,16 +630,23 @@ impl Css {
                     writeln!(buf, "{}@import {};", padding, s)?;
                 }
                 Toplevel::UnknownAtRule(u) => {
-                    let ToplevelUnknownAtRule { params, name, body } = *u;
+                    let ToplevelUnknownAtRule {
+                        params,
+                        name,
+                        body,
+                        span,
+                    } = *u;
                     if should_emit_newline {
                         should_emit_newline = false;
                         writeln!(buf)?;
                     }
 
+                    write!(buf, "{}", padding)?;
+                    source_map.add_mapping(buf, span);
                     if params.is_empty() {
-                        write!(buf, "{}@{}", padding, name)?;
+                        write!(buf, "@{}", name)?;
                     } else {
-                        write!(buf, "{}@{} {}", padding, name, params)?;
+                        write!(buf, "@{} {}", name, params)?;
                     }
 
                     if body.is_empty() {
@@ -320,21 +656,32 @@ impl Css {
                         writeln!(buf, " {{")?;
                     }
 
-                    Css::from_stmts(body, true, self.allows_charset)?._inner_pretty_print(
+                    ExpandedFormatter {
+                        nesting: self.nesting + 1,
+                    }
+                    .write_css(
                         buf,
+                        Css::from_stmts(body, true, css.allows_charset)?,
                         map,
-                        nesting + 1,
+                        source_map,
                     )?;
                     writeln!(buf, "{}}}", padding)?;
                 }
                 Toplevel::Keyframes(k) => {
-                    let Keyframes { rule, name, body } = *k;
+                    let Keyframes {
+                        rule,
+                        name,
+                        body,
+                        span,
+                    } = *k;
                     if should_emit_newline {
                         should_emit_newline = false;
                         writeln!(buf)?;
                     }
 
-                    write!(buf, "{}@{}", padding, rule)?;
+                    write!(buf, "{}", padding)?;
+                    source_map.add_mapping(buf, span);
+                    write!(buf, "@{}", rule)?;
 
                     if !name.is_empty() {
                         write!(buf, " {}", name)?;
@@ -347,54 +694,66 @@ impl Css {
                         writeln!(buf, " {{")?;
                     }
 
-                    Css::from_stmts(body, true, self.allows_charset)?._inner_pretty_print(
+                    ExpandedFormatter {
+                        nesting: self.nesting + 1,
+                    }
+                    .write_css(
                         buf,
+                        Css::from_stmts(body, true, css.allows_charset)?,
                         map,
-                        nesting + 1,
+                        source_map,
                     )?;
                     writeln!(buf, "{}}}", padding)?;
                 }
-                Toplevel::Supports { params, body } => {
+                Toplevel::Supports {
+                    condition,
+                    body,
+                    span,
+                } => {
                     if should_emit_newline {
                         should_emit_newline = false;
                         writeln!(buf)?;
                     }
 
-                    if params.is_empty() {
-                        write!(buf, "{}@supports", padding)?;
-                    } else {
-                        write!(buf, "{}@supports {}", padding, params)?;
-                    }
-
-                    if body.is_empty() {
-                        writeln!(buf, ";")?;
-                        continue;
-                    } else {
-                        writeln!(buf, " {{")?;
+                    write!(buf, "{}", padding)?;
+                    source_map.add_mapping(buf, span);
+                    writeln!(buf, "@supports {} {{", condition)?;
+                    ExpandedFormatter {
+                        nesting: self.nesting + 1,
                     }
-
-                    Css::from_stmts(body, true, self.allows_charset)?._inner_pretty_print(
+                    .write_css(
                         buf,
+                        Css::new_with_blocks(body, css.allows_charset),
                         map,
-                        nesting + 1,
+                        source_map,
                     )?;
                     writeln!(buf, "{}}}", padding)?;
                 }
-                Toplevel::Media { query, body } => {
-                    if body.is_empty() {
-                        continue;
+                Toplevel::Media {
+                    query, body, span, ..
+                } => {
+                    write!(buf, "{}", padding)?;
+                    source_map.add_mapping(buf, span);
+                    writeln!(
+                        buf,
+                        "@media {} {{",
+                        MediaQuery::serialize_list(&query, false)
+                    )?;
+                    ExpandedFormatter {
+                        nesting: self.nesting + 1,
                     }
-
-                    writeln!(buf, "{}@media {} {{", padding, query)?;
-                    Css::from_stmts(body, true, self.allows_charset)?._inner_pretty_print(
+                    .write_css(
                         buf,
+                        Css::new_with_blocks(body, css.allows_charset),
                         map,
-                        nesting + 1,
+                        source_map,
                     )?;
                     writeln!(buf, "{}}}", padding)?;
                 }
                 Toplevel::Style(s) => {
-                    writeln!(buf, "{}{}", padding, s.to_string()?)?;
+                    write!(buf, "{}", padding)?;
+                    source_map.add_mapping(buf, s.property.span);
+                    writeln!(buf, "{}", s.to_string()?)?;
                 }
                 Toplevel::Newline => {
                     if has_written {
@@ -407,3 +766,21 @@ impl Css {
         Ok(())
     }
 }
+
+impl ExpandedFormatter {
+    fn write_block_entries(
+        buf: &mut Vec<u8>,
+        padding: &str,
+        entries: Vec<BlockEntry>,
+        source_map: &mut SourceMapBuilder,
+    ) -> SassResult<()> {
+        for entry in entries {
+            write!(buf, "{}  ", padding)?;
+            if let BlockEntry::Style(style) = &entry {
+                source_map.add_mapping(buf, style.property.span);
+            }
+            writeln!(buf, "{}", entry.to_string()?)?;
+        }
+        Ok(())
+    }
+}
@@ -1,6 +1,6 @@
 use std::{collections::HashMap, mem};
 
-use codemap::Span;
+use codemap::{Span, Spanned};
 
 use crate::{
     args::{CallArg, CallArgs, FuncArg, FuncArgs},
@@ -18,7 +18,7 @@ use crate::{
 use super::Parser;
 
 impl<'a> Parser<'a> {
-    pub(super) fn parse_func_args(&mut self) -> SassResult<FuncArgs> {
+    pub(crate) fn parse_func_args(&mut self) -> SassResult<FuncArgs> {
         let mut args: Vec<FuncArg> = Vec::new();
         let mut close_paren_span: Span = match self.toks.peek() {
             Some(Token { pos, .. }) => *pos,
@@ -131,7 +131,7 @@ impl<'a> Parser<'a> {
                     default: None,
                     is_variadic,
                 }),
-                _ => {}
+                _ => return Err(("expected \")\".", span).into()),
             }
             self.whitespace_or_comment();
         }
@@ -146,6 +146,16 @@ impl<'a> Parser<'a> {
     }
 
     pub(super) fn parse_call_args(&mut self) -> SassResult<CallArgs> {
+        self.parse_call_args_with_slash(false)
+    }
+
+    /// Parse the arguments to a function such as `lab()`, where a trailing
+    /// `/ alpha` is passed as a slash-separated list
+    pub(super) fn parse_slash_call_args(&mut self) -> SassResult<CallArgs> {
+        self.parse_call_args_with_slash(true)
+    }
+
+    fn parse_call_args_with_slash(&mut self, keep_slash: bool) -> SassResult<CallArgs> {
         let mut args = HashMap::new();
         self.whitespace_or_comment();
         let mut name = String::new();
@@ -185,7 +195,7 @@ impl<'a> Parser<'a> {
 
             self.whitespace_or_comment();
 
-            let value = self.parse_value(true, &|c| match c.peek() {
+            let value = self.parse_value_with_slash(false, keep_slash, &|c| match c.peek() {
                 Some(Token { kind: ')', .. }) | Some(Token { kind: ',', .. }) => true,
                 Some(Token { kind: '.', .. }) => {
                     if matches!(c.peek_next(), Some(Token { kind: '.', .. })) {
@@ -374,7 +384,15 @@ impl<'a> Parser<'a> {
         self.scopes.enter_new_scope();
         for (idx, mut arg) in fn_args.0.into_iter().enumerate() {
             if arg.is_variadic {
-                let arg_list = Value::ArgList(args.get_variadic()?);
+                let arg_list = Value::ArgList(
+                    args.get_variadic()?
+                        .into_iter()
+                        .map(|arg| {
+                            let span = arg.span;
+                            Ok(self.without_slash(arg.node, span)?.span(span))
+                        })
+                        .collect::<SassResult<Vec<Spanned<Value>>>>()?,
+                );
                 scope.insert_var(arg.name, arg_list);
                 break;
             }
@@ -388,8 +406,8 @@ impl<'a> Parser<'a> {
                         )
                     }
                 },
-            }?
-            .node;
+            }?;
+            let val = self.without_slash(val.node, val.span)?;
             self.scopes.insert_var(arg.name, val.clone());
             scope.insert_var(arg.name, val);
         }
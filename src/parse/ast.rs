@@ -0,0 +1,742 @@
+//! The bodies of mixins, functions, and loops are split into statements once,
+//! rather than every time they're evaluated.
+//!
+//! Only the structure of the statements is cached: this is not an expression
+//! AST. Expressions, selectors, and the headers of control flow rules are kept
+//! as tokens and are still parsed each time they're evaluated, as this parser
+//! evaluates them as it reads them. Statements whose kind can't be known
+//! without evaluating anything, such as `a:b {`, which may be either a
+//! declaration or a style rule, are also kept as tokens, and are parsed as
+//! before each time they're evaluated.
+
+use codemap::{Span, Spanned};
+use peekmore::PeekMore;
+
+use crate::{
+    atrule::keyframes::KeyframesRuleSet, common::Identifier, error::SassResult,
+    interner::InternedString, style::Style, utils::is_name, Token,
+};
+
+use super::{variable::VariableValue, Parser, Stmt};
+
+#[derive(Debug, Clone)]
+pub(crate) enum AstStmt {
+    /// `$name: value`, along with any `!default` or `!global` flags
+    VariableDecl {
+        name: Identifier,
+        value: VariableValue,
+        /// The span of the colon
+        span: Span,
+    },
+
+    /// A declaration such as `color: $color`
+    Style {
+        property: Spanned<InternedString>,
+        value: Vec<Token>,
+        span: Span,
+    },
+
+    /// A style rule, or a keyframe block within `@keyframes`
+    RuleSet {
+        /// The beginning of the selector, which has already been read as an
+        /// identifier
+        init: String,
+        /// The rest of the selector, including the `{` that ends it
+        selector: Vec<Token>,
+        body: Vec<AstStmt>,
+        span: Span,
+    },
+
+    /// `@if`, along with any `@else if` and `@else` clauses
+    If {
+        clauses: Vec<(Vec<Token>, Vec<AstStmt>)>,
+        else_body: Option<Vec<AstStmt>>,
+        span: Span,
+    },
+
+    /// `@for`, whose header includes the `{` that begins its body
+    For {
+        header: Vec<Token>,
+        body: Vec<AstStmt>,
+        span: Span,
+    },
+
+    /// `@each`, whose header includes the `{` that begins its body
+    Each {
+        header: Vec<Token>,
+        body: Vec<AstStmt>,
+        span: Span,
+    },
+
+    /// `@while`, whose header includes the `{` that begins its body
+    While {
+        header: Vec<Token>,
+        body: Vec<AstStmt>,
+        span: Span,
+    },
+
+    Return {
+        value: Vec<Token>,
+        span: Span,
+    },
+
+    /// Any other statement, followed by a closing curly brace as though it
+    /// were the last statement in a body
+    Raw(Vec<Token>),
+}
+
+impl<'a> Parser<'a> {
+    /// Parse the statements of a body, not including its curly braces
+    pub(crate) fn parse_ast(&mut self, toks: &[Token]) -> Vec<AstStmt> {
+        let mut stmts = Vec::new();
+        let mut idx = 0;
+        while let Some(tok) = toks.get(idx) {
+            match tok.kind {
+                ' ' | '\t' | '\n' | ';' => idx += 1,
+                '/' if next_is(toks, idx + 1, '*') => {
+                    let end = comment_end(toks, idx + 2);
+                    stmts.push(raw(&toks[idx..end]));
+                    idx = end;
+                }
+                // `parse_stmt` stops at a closing curly brace, and reports
+                // errors for the rest
+                '}' | '{' | '!' | '\u{0}'..='\u{8}' | '\u{b}'..='\u{1f}' => {
+                    stmts.push(raw(&toks[idx..]));
+                    break;
+                }
+                _ => {
+                    let end = statement_end(toks, idx);
+                    let stmt = &toks[idx..end];
+                    stmts.push(self.parse_ast_stmt(stmt).unwrap_or_else(|| raw(stmt)));
+                    idx = end;
+                }
+            }
+        }
+        stmts
+    }
+
+    fn parse_ast_stmt(&mut self, toks: &[Token]) -> Option<AstStmt> {
+        match toks[0].kind {
+            '$' => self.parse_ast_variable_decl(toks),
+            '@' => self.parse_ast_at_rule(toks),
+            _ => self.parse_ast_style_or_rule_set(toks),
+        }
+    }
+
+    fn parse_ast_variable_decl(&mut self, toks: &[Token]) -> Option<AstStmt> {
+        self.with_toks(toks.to_vec(), |parser| {
+            parser.toks.next();
+            let name = parser.parse_identifier_no_interpolation(false).ok()?;
+            parser.whitespace_or_comment();
+            let span = parser.toks.peek()?.pos;
+            parser.expect_char(':').ok()?;
+            let value = parser.parse_variable_value().ok()?;
+            if parser.toks.peek().is_some() {
+                return None;
+            }
+            Some(AstStmt::VariableDecl {
+                name: name.node.into(),
+                value,
+                span,
+            })
+        })
+    }
+
+    fn parse_ast_at_rule(&mut self, toks: &[Token]) -> Option<AstStmt> {
+        let (name, span, mut idx) = at_rule_name(toks, 0)?;
+        match name.as_str() {
+            "return" => {
+                let value = match toks.last() {
+                    Some(Token { kind: ';', .. }) => &toks[idx..toks.len() - 1],
+                    _ => &toks[idx..],
+                };
+                if !is_simple_expression(value, true) {
+                    return None;
+                }
+                Some(AstStmt::Return {
+                    value: value.to_vec(),
+                    span,
+                })
+            }
+            "if" => {
+                let mut clauses = Vec::new();
+                let mut else_body = None;
+                loop {
+                    let (start, end) = clause_bounds(toks, idx, true)?;
+                    clauses.push((
+                        toks[idx..start].to_vec(),
+                        self.parse_ast(&toks[start + 1..end - 1]),
+                    ));
+                    idx = skip_whitespace_or_comment(toks, end);
+                    match at_rule_name(toks, idx) {
+                        Some((name, _, end)) if name == "else" => {
+                            idx = skip_whitespace_or_comment(toks, end);
+                        }
+                        _ => break,
+                    }
+                    match (toks.get(idx), toks.get(idx + 1)) {
+                        (Some(Token { kind: 'i', .. }), Some(Token { kind: 'f', .. }))
+                        | (Some(Token { kind: 'i', .. }), Some(Token { kind: 'F', .. })) => {
+                            idx += 2;
+                        }
+                        (Some(Token { kind: '{', .. }), _) => {
+                            let end = block_end(toks, idx)?;
+                            else_body = Some(self.parse_ast(&toks[idx + 1..end - 1]));
+                            idx = end;
+                            break;
+                        }
+                        _ => return None,
+                    }
+                }
+                if idx != toks.len() {
+                    return None;
+                }
+                Some(AstStmt::If {
+                    clauses,
+                    else_body,
+                    span,
+                })
+            }
+            "for" | "each" | "while" => {
+                // only `@for` parses its header as an expression, which
+                // allows interpolation
+                let (start, end) = clause_bounds(toks, idx, name == "for")?;
+                if end != toks.len() {
+                    return None;
+                }
+                let header = toks[idx..=start].to_vec();
+                let body = self.parse_ast(&toks[start + 1..end - 1]);
+                Some(match name.as_str() {
+                    "for" => AstStmt::For { header, body, span },
+                    "each" => AstStmt::Each { header, body, span },
+                    _ => AstStmt::While { header, body, span },
+                })
+            }
+            _ => None,
+        }
+    }
+
+    fn parse_ast_style_or_rule_set(&mut self, toks: &[Token]) -> Option<AstStmt> {
+        let span = toks[0].pos;
+        let (init, idx) = match toks[0].kind {
+            '#' if next_is(toks, 1, '{') => return None,
+            c if is_plain_name_start(c)
+                || (c == '-'
+                    && matches!(toks.get(1), Some(tok) if is_plain_name_start(tok.kind))) =>
+            {
+                let name_end = toks
+                    .iter()
+                    .position(|tok| !is_plain_name(tok.kind))
+                    .unwrap_or(toks.len());
+                // the identifier may continue with interpolation or escapes
+                if matches!(toks.get(name_end), Some(Token { kind, .. }) if is_name(*kind) || matches!(kind, '\\' | '#'))
+                {
+                    return None;
+                }
+                let name: String = toks[..name_end].iter().map(|tok| tok.kind).collect();
+                let idx = skip_whitespace(toks, name_end);
+                match toks.get(idx) {
+                    Some(Token { kind: ':', .. }) => {
+                        let property = Spanned {
+                            node: InternedString::get_or_intern(name),
+                            span: span.merge(toks[name_end - 1].pos),
+                        };
+                        return parse_ast_style(property, toks, idx + 1, span);
+                    }
+                    Some(Token { kind: '.', .. }) if next_is(toks, idx + 1, '$') => return None,
+                    Some(Token { kind: '/', .. }) | None => return None,
+                    Some(..) => {}
+                }
+                let mut init = name;
+                if idx > name_end {
+                    init.push(' ');
+                }
+                (init, idx)
+            }
+            c if is_name(c) => return None,
+            _ => (String::new(), 0),
+        };
+
+        let start = block_start(toks, idx)?;
+        let end = block_end(toks, start)?;
+        if end != toks.len() || !is_plain_selector(&toks[idx..start]) {
+            return None;
+        }
+
+        Some(AstStmt::RuleSet {
+            init,
+            selector: toks[idx..=start].to_vec(),
+            body: self.parse_ast(&toks[start + 1..end - 1]),
+            span,
+        })
+    }
+
+    /// Evaluate statements returned by `parse_ast`
+    pub(crate) fn eval_ast(&mut self, body: &[AstStmt]) -> SassResult<Vec<Stmt>> {
+        let mut stmts = Vec::new();
+        for stmt in body {
+            if self.flags.in_function() && !stmts.is_empty() {
+                return Ok(stmts);
+            }
+            match stmt {
+                AstStmt::VariableDecl { name, value, span } => {
+                    self.span_before = *span;
+                    self.declare_variable(*name, value.clone())?;
+                }
+                AstStmt::Style {
+                    property,
+                    value,
+                    span,
+                } => {
+                    self.span_before = *span;
+                    if self.flags.in_function() {
+                        return Err((
+                            "Functions can only contain variable declarations and control directives.",
+                            *span,
+                        )
+                            .into());
+                    }
+                    let value = self.parse_value_from_vec(value.clone(), false)?;
+                    stmts.push(Stmt::Style(Style {
+                        property: *property,
+                        value: Box::new(value),
+                    }));
+                }
+                AstStmt::RuleSet {
+                    init,
+                    selector,
+                    body,
+                    span,
+                } => stmts.push(self.eval_ast_rule_set(init, selector, body, *span)?),
+                AstStmt::If {
+                    clauses,
+                    else_body,
+                    span,
+                } => {
+                    self.span_before = *span;
+                    let mut body = else_body.as_ref();
+                    for (cond, clause_body) in clauses {
+                        if self
+                            .parse_value_from_vec(cond.clone(), true)?
+                            .node
+                            .is_true()
+                        {
+                            body = Some(clause_body);
+                            break;
+                        }
+                    }
+                    if let Some(body) = body {
+                        stmts.append(&mut self.eval_control_flow_body(body)?);
+                    }
+                }
+                AstStmt::For { header, body, span } => {
+                    self.span_before = *span;
+                    let header =
+                        self.with_toks(header.clone(), |parser| parser.parse_for_header())?;
+                    stmts.append(&mut self.eval_for(header, body)?);
+                }
+                AstStmt::Each { header, body, span } => {
+                    self.span_before = *span;
+                    let header =
+                        self.with_toks(header.clone(), |parser| parser.parse_each_header())?;
+                    stmts.append(&mut self.eval_each(header, body)?);
+                }
+                AstStmt::While { header, body, span } => {
+                    self.span_before = *span;
+                    let cond =
+                        self.with_toks(header.clone(), |parser| parser.parse_while_header())?;
+                    stmts.append(&mut self.eval_while(&cond, body)?);
+                }
+                AstStmt::Return { value, span } => {
+                    if !self.flags.in_function() {
+                        return Err(("This at-rule is not allowed here.", *span).into());
+                    }
+                    self.span_before = *span;
+                    let value = self.parse_value_from_vec(value.clone(), true)?;
+                    return Ok(vec![Stmt::Return(Box::new(value.node))]);
+                }
+                AstStmt::Raw(toks) => stmts.append(&mut self.eval_raw(toks)?),
+            }
+        }
+        Ok(stmts)
+    }
+
+    fn eval_ast_rule_set(
+        &mut self,
+        init: &str,
+        selector: &[Token],
+        body: &[AstStmt],
+        span: Span,
+    ) -> SassResult<Stmt> {
+        self.span_before = span;
+        if self.flags.in_function() {
+            return Err((
+                "Functions can only contain variable declarations and control directives.",
+                span,
+            )
+                .into());
+        }
+
+        if self.flags.in_keyframes() {
+            let selector = self.with_toks(selector.to_vec(), |parser| {
+                parser.parse_keyframes_selector(init.to_owned())
+            })?;
+            self.scopes.enter_new_scope();
+            let body = self.eval_ast(body)?;
+            self.scopes.exit_scope();
+            return Ok(Stmt::KeyframesRuleSet(Box::new(KeyframesRuleSet {
+                selector,
+                body,
+            })));
+        }
+
+        let at_root = self.at_root;
+        self.at_root = false;
+        let allows_parent = !self.super_selectors.is_empty();
+        let mut selector = self
+            .with_toks(selector.to_vec(), |parser| {
+                parser.parse_selector(allows_parent, false, init.to_owned())
+            })?
+            .0;
+        selector.0.span = span.merge(selector.0.span);
+        let selector = selector.resolve_parent_selectors(
+            self.super_selectors.last(),
+            !at_root || self.at_root_has_selector,
+        )?;
+        self.scopes.enter_new_scope();
+        self.super_selectors.push(selector.clone());
+
+        let extended_selector = self
+            .extender
+            .add_selector(selector.0, self.media_queries.clone())?;
+
+        let body = self.eval_ast(body)?;
+        self.scopes.exit_scope();
+        self.super_selectors.pop();
+        self.at_root = self.super_selectors.is_empty();
+        Ok(Stmt::RuleSet {
+            selector: extended_selector,
+            body,
+        })
+    }
+
+    fn eval_raw(&mut self, toks: &[Token]) -> SassResult<Vec<Stmt>> {
+        let mut parser = Parser {
+            toks: &mut toks.to_vec().into_iter().peekmore(),
+            map: self.map,
+            path: self.path,
+            scopes: self.scopes,
+            global_scope: self.global_scope,
+            super_selectors: self.super_selectors,
+            span_before: self.span_before,
+            content: self.content,
+            flags: self.flags,
+            at_root: self.at_root,
+            at_root_has_selector: self.at_root_has_selector,
+            extender: self.extender,
+            media_queries: self.media_queries,
+            content_scopes: self.content_scopes,
+            options: self.options,
+            modules: self.modules,
+            module_config: self.module_config,
+            forwarded: self.forwarded,
+            call_stack: self.call_stack,
+            custom_function_args: self.custom_function_args,
+        };
+        let stmts = parser.parse_stmt()?;
+        // a style rule at the root of a mixin leaves the rest of the mixin at
+        // the root
+        self.at_root = parser.at_root;
+        Ok(stmts)
+    }
+}
+
+/// Parse the rest of a declaration, whose property is followed by a colon
+/// before `idx`
+fn parse_ast_style(
+    property: Spanned<InternedString>,
+    toks: &[Token],
+    idx: usize,
+    span: Span,
+) -> Option<AstStmt> {
+    // anything else may be a selector, such as `a:hover`
+    match toks.get(idx) {
+        Some(Token { kind, .. }) if !is_name(*kind) && *kind != ':' => {}
+        Some(..) | None => return None,
+    }
+
+    let start = skip_whitespace(toks, idx);
+    let end = match toks.last() {
+        Some(Token { kind: ';', .. }) => toks.len() - 1,
+        _ => toks.len(),
+    };
+    if start >= end || !is_simple_expression(&toks[start..end], true) {
+        return None;
+    }
+
+    Some(AstStmt::Style {
+        property,
+        value: toks[start..end].to_vec(),
+        span,
+    })
+}
+
+fn raw(toks: &[Token]) -> AstStmt {
+    let mut toks = toks.to_vec();
+    if let Some(last) = toks.last() {
+        toks.push(Token::new(last.pos, '}'));
+    }
+    AstStmt::Raw(toks)
+}
+
+/// Characters that may appear in an identifier that can be read without
+/// resolving escapes or interpolation
+fn is_plain_name(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '-' || c == '_'
+}
+
+fn is_plain_name_start(c: char) -> bool {
+    c.is_ascii_alphabetic() || c == '_'
+}
+
+fn next_is(toks: &[Token], idx: usize, c: char) -> bool {
+    matches!(toks.get(idx), Some(Token { kind, .. }) if *kind == c)
+}
+
+/// The name of the at-rule beginning at `idx`, its span, and the index just
+/// past it
+fn at_rule_name(toks: &[Token], idx: usize) -> Option<(String, Span, usize)> {
+    if !next_is(toks, idx, '@') {
+        return None;
+    }
+    let start = idx + 1;
+    let end = toks[start..]
+        .iter()
+        .position(|tok| !is_plain_name(tok.kind))
+        .map_or(toks.len(), |len| start + len);
+    if start == end
+        || matches!(toks.get(end), Some(Token { kind, .. }) if is_name(*kind) || matches!(kind, '\\' | '#'))
+    {
+        return None;
+    }
+    Some((
+        toks[start..end].iter().map(|tok| tok.kind).collect(),
+        toks[idx].pos.merge(toks[end - 1].pos),
+        end,
+    ))
+}
+
+fn skip_whitespace(toks: &[Token], mut idx: usize) -> usize {
+    while matches!(
+        toks.get(idx),
+        Some(Token { kind: ' ', .. })
+            | Some(Token { kind: '\t', .. })
+            | Some(Token { kind: '\n', .. })
+    ) {
+        idx += 1;
+    }
+    idx
+}
+
+fn skip_whitespace_or_comment(toks: &[Token], mut idx: usize) -> usize {
+    loop {
+        idx = skip_whitespace(toks, idx);
+        if next_is(toks, idx, '/') && next_is(toks, idx + 1, '*') {
+            idx = comment_end(toks, idx + 2);
+        } else if next_is(toks, idx, '/') && next_is(toks, idx + 1, '/') {
+            idx = toks[idx..]
+                .iter()
+                .position(|tok| tok.kind == '\n')
+                .map_or(toks.len(), |len| idx + len + 1);
+        } else {
+            return idx;
+        }
+    }
+}
+
+/// The index just past the end of the loud comment whose contents begin at
+/// `idx`
+fn comment_end(toks: &[Token], mut idx: usize) -> usize {
+    while let Some(tok) = toks.get(idx) {
+        if tok.kind == '*' && next_is(toks, idx + 1, '/') {
+            return idx + 2;
+        }
+        idx += 1;
+    }
+    toks.len()
+}
+
+/// The index just past the end of the string whose contents begin at `idx`
+fn string_end(toks: &[Token], mut idx: usize, quote: char) -> usize {
+    while let Some(tok) = toks.get(idx) {
+        match tok.kind {
+            '\\' => idx += 2,
+            '#' if next_is(toks, idx + 1, '{') => {
+                idx = block_end(toks, idx + 1).unwrap_or(toks.len());
+            }
+            c if c == quote => return idx + 1,
+            _ => idx += 1,
+        }
+    }
+    toks.len()
+}
+
+/// The index just past the string, escape, interpolation, or loud comment
+/// beginning at `idx`, if there is one
+fn skip_unit(toks: &[Token], idx: usize) -> Option<usize> {
+    let end = match toks[idx].kind {
+        '\\' => idx + 2,
+        q @ '"' | q @ '\'' => string_end(toks, idx + 1, q),
+        '#' if next_is(toks, idx + 1, '{') => block_end(toks, idx + 1).unwrap_or(toks.len()),
+        '/' if next_is(toks, idx + 1, '*') => comment_end(toks, idx + 2),
+        _ => return None,
+    };
+    Some(end.min(toks.len()))
+}
+
+/// The index of the curly brace that begins the body following `idx`, and
+/// the index just past the end of that body, if everything from `idx` up to
+/// the body can be parsed as an expression on its own
+fn clause_bounds(toks: &[Token], idx: usize, allows_interpolation: bool) -> Option<(usize, usize)> {
+    let start = block_start(toks, idx)?;
+    let end = block_end(toks, start)?;
+    if !is_simple_expression(&toks[idx..start], allows_interpolation) {
+        return None;
+    }
+    Some((start, end))
+}
+
+/// The index just past the curly brace that closes the one at `idx`
+fn block_end(toks: &[Token], mut idx: usize) -> Option<usize> {
+    let mut depth = 0_usize;
+    while let Some(tok) = toks.get(idx) {
+        if let Some(end) = skip_unit(toks, idx) {
+            idx = end;
+            continue;
+        }
+        match tok.kind {
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(idx + 1);
+                }
+            }
+            _ => {}
+        }
+        idx += 1;
+    }
+    None
+}
+
+/// The index of the curly brace that begins the body of the statement
+/// containing `idx`
+fn block_start(toks: &[Token], mut idx: usize) -> Option<usize> {
+    let mut parens = 0_usize;
+    while let Some(tok) = toks.get(idx) {
+        if let Some(end) = skip_unit(toks, idx) {
+            idx = end;
+            continue;
+        }
+        match tok.kind {
+            '(' => parens += 1,
+            ')' => parens = parens.saturating_sub(1),
+            '{' if parens == 0 => return Some(idx),
+            ';' | '}' if parens == 0 => return None,
+            _ => {}
+        }
+        idx += 1;
+    }
+    None
+}
+
+/// The index just past the end of the statement beginning at `start`
+///
+/// If this can't be known without parsing the statement, the statement is
+/// assumed to continue to the end of `toks`, as it will then be parsed as a
+/// whole.
+fn statement_end(toks: &[Token], start: usize) -> usize {
+    let is_if = matches!(at_rule_name(toks, start), Some((name, ..)) if name == "if");
+    let mut parens = 0_usize;
+    let mut idx = start;
+    while let Some(tok) = toks.get(idx) {
+        if let Some(end) = skip_unit(toks, idx) {
+            idx = end;
+            continue;
+        }
+        match tok.kind {
+            '(' => parens += 1,
+            ')' => parens = parens.saturating_sub(1),
+            ';' if parens == 0 => return idx + 1,
+            '}' if parens == 0 => return idx,
+            '{' if parens == 0 => {
+                let end = match block_end(toks, idx) {
+                    Some(end) => end,
+                    None => return toks.len(),
+                };
+                if is_if {
+                    let next = skip_whitespace_or_comment(toks, end);
+                    if matches!(at_rule_name(toks, next), Some((name, ..)) if name == "else") {
+                        idx = next;
+                        continue;
+                    }
+                }
+                return end;
+            }
+            _ => {}
+        }
+        idx += 1;
+    }
+    toks.len()
+}
+
+/// Whether `toks` can be parsed as an expression on its own, without
+/// reaching anything that would end the statement it's part of
+fn is_simple_expression(toks: &[Token], allows_interpolation: bool) -> bool {
+    let mut idx = 0;
+    while let Some(tok) = toks.get(idx) {
+        match tok.kind {
+            '#' if next_is(toks, idx + 1, '{') => {
+                if !allows_interpolation {
+                    return false;
+                }
+                idx = match block_end(toks, idx + 1) {
+                    Some(end) => end,
+                    None => return false,
+                };
+                continue;
+            }
+            q @ '"' | q @ '\'' => {
+                idx = string_end(toks, idx + 1, q);
+                continue;
+            }
+            '\\' => idx += 1,
+            '/' if next_is(toks, idx + 1, '/') || next_is(toks, idx + 1, '*') => return false,
+            ';' | '{' | '}' => return false,
+            _ => {}
+        }
+        idx += 1;
+    }
+    true
+}
+
+/// Whether `toks` can be parsed as a selector without reaching anything that
+/// would end the statement it's part of
+fn is_plain_selector(toks: &[Token]) -> bool {
+    let mut idx = 0;
+    while let Some(tok) = toks.get(idx) {
+        match tok.kind {
+            '#' if next_is(toks, idx + 1, '{') => {
+                idx = match block_end(toks, idx + 1) {
+                    Some(end) => end,
+                    None => return false,
+                };
+                continue;
+            }
+            '"' | '\'' | '\\' | '/' | ';' | '!' | '{' | '}' => return false,
+            _ => {}
+        }
+        idx += 1;
+    }
+    true
+}
@@ -41,7 +41,7 @@ impl<T> NeverEmptyVec<T> {
 #[derive(Debug)]
 pub(super) enum SelectorOrStyle {
     Selector(String),
-    Style(InternedString, Option<Box<Spanned<Value>>>),
+    Style(Spanned<InternedString>, Option<Box<Spanned<Value>>>),
     ModuleVariableRedeclaration(Identifier),
 }
 
@@ -5,12 +5,12 @@ use peekmore::PeekMore;
 use crate::{
     common::Identifier,
     error::SassResult,
-    parse::{ContextFlags, Parser, Stmt},
+    parse::{AstStmt, ContextFlags, Parser, Stmt},
     unit::Unit,
     utils::{
         peek_ident_no_interpolation, read_until_closing_curly_brace, read_until_open_curly_brace,
     },
-    value::{Number, Value},
+    value::{Division, Number, Value},
     Token,
 };
 
@@ -46,10 +46,14 @@ impl<'a> Parser<'a> {
                 at_root: self.at_root,
                 at_root_has_selector: self.at_root_has_selector,
                 extender: self.extender,
+                media_queries: self.media_queries,
                 content_scopes: self.content_scopes,
                 options: self.options,
                 modules: self.modules,
                 module_config: self.module_config,
+                forwarded: self.forwarded,
+                call_stack: self.call_stack,
+                custom_function_args: self.custom_function_args,
             }
             .parse_stmt()?;
         } else {
@@ -102,10 +106,14 @@ impl<'a> Parser<'a> {
                                 at_root: self.at_root,
                                 at_root_has_selector: self.at_root_has_selector,
                                 extender: self.extender,
+                                media_queries: self.media_queries,
                                 content_scopes: self.content_scopes,
                                 options: self.options,
                                 modules: self.modules,
                                 module_config: self.module_config,
+                                forwarded: self.forwarded,
+                                call_stack: self.call_stack,
+                                custom_function_args: self.custom_function_args,
                             }
                             .parse_stmt()?;
                         } else {
@@ -132,10 +140,14 @@ impl<'a> Parser<'a> {
                                 at_root: self.at_root,
                                 at_root_has_selector: self.at_root_has_selector,
                                 extender: self.extender,
+                                media_queries: self.media_queries,
                                 content_scopes: self.content_scopes,
                                 options: self.options,
                                 modules: self.modules,
                                 module_config: self.module_config,
+                                forwarded: self.forwarded,
+                                call_stack: self.call_stack,
+                                custom_function_args: self.custom_function_args,
                             }
                             .parse_stmt();
                         }
@@ -154,6 +166,20 @@ impl<'a> Parser<'a> {
     }
 
     pub(super) fn parse_for(&mut self) -> SassResult<Vec<Stmt>> {
+        let header = self.parse_for_header()?;
+
+        let body = read_until_closing_curly_brace(self.toks)?;
+
+        self.expect_char('}')?;
+
+        let body = self.parse_ast(&body);
+
+        self.eval_for(header, &body)
+    }
+
+    /// Parse everything from after `@for` up to and including the `{` that
+    /// begins its body
+    pub(super) fn parse_for_header(&mut self) -> SassResult<ForHeader> {
         self.whitespace_or_comment();
         self.expect_char('$')?;
 
@@ -236,9 +262,25 @@ impl<'a> Parser<'a> {
 
         self.expect_char('{')?;
 
-        let body = read_until_closing_curly_brace(self.toks)?;
+        Ok(ForHeader {
+            var,
+            from,
+            to,
+            through,
+        })
+    }
 
-        self.expect_char('}')?;
+    pub(super) fn eval_for(
+        &mut self,
+        header: ForHeader,
+        body: &[AstStmt],
+    ) -> SassResult<Vec<Stmt>> {
+        let ForHeader {
+            var,
+            from,
+            to,
+            through,
+        } = header;
 
         let (mut x, mut y);
         // we can't use an inclusive range here
@@ -258,53 +300,15 @@ impl<'a> Parser<'a> {
         for i in iter {
             self.scopes.insert_var_last(
                 var.node,
-                Value::Dimension(Some(Number::from(i)), Unit::None, true),
+                Value::Dimension(Some(Number::from(i)), Unit::None, Division::Computed),
             );
+            let mut these_stmts = self.eval_control_flow_body(body)?;
             if self.flags.in_function() {
-                let these_stmts = Parser {
-                    toks: &mut body.clone().into_iter().peekmore(),
-                    map: self.map,
-                    path: self.path,
-                    scopes: self.scopes,
-                    global_scope: self.global_scope,
-                    super_selectors: self.super_selectors,
-                    span_before: self.span_before,
-                    content: self.content,
-                    flags: self.flags | ContextFlags::IN_CONTROL_FLOW,
-                    at_root: self.at_root,
-                    at_root_has_selector: self.at_root_has_selector,
-                    extender: self.extender,
-                    content_scopes: self.content_scopes,
-                    options: self.options,
-                    modules: self.modules,
-                    module_config: self.module_config,
-                }
-                .parse_stmt()?;
                 if !these_stmts.is_empty() {
                     return Ok(these_stmts);
                 }
             } else {
-                stmts.append(
-                    &mut Parser {
-                        toks: &mut body.clone().into_iter().peekmore(),
-                        map: self.map,
-                        path: self.path,
-                        scopes: self.scopes,
-                        global_scope: self.global_scope,
-                        super_selectors: self.super_selectors,
-                        span_before: self.span_before,
-                        content: self.content,
-                        flags: self.flags | ContextFlags::IN_CONTROL_FLOW,
-                        at_root: self.at_root,
-                        at_root_has_selector: self.at_root_has_selector,
-                        extender: self.extender,
-                        content_scopes: self.content_scopes,
-                        options: self.options,
-                        modules: self.modules,
-                        module_config: self.module_config,
-                    }
-                    .parse_stmt()?,
-                );
+                stmts.append(&mut these_stmts);
             }
         }
 
@@ -314,6 +318,22 @@ impl<'a> Parser<'a> {
     }
 
     pub(super) fn parse_while(&mut self) -> SassResult<Vec<Stmt>> {
+        let cond = self.parse_while_header()?;
+
+        let body = read_until_closing_curly_brace(self.toks)?;
+
+        if self.toks.next().is_none() {
+            return Err(("expected \"}\".", self.span_before).into());
+        }
+
+        let body = self.parse_ast(&body);
+
+        self.eval_while(&cond, &body)
+    }
+
+    /// Parse everything from after `@while` up to and including the `{` that
+    /// begins its body, returning the condition
+    pub(super) fn parse_while_header(&mut self) -> SassResult<Vec<Token>> {
         // technically not necessary to eat whitespace here, but since we
         // operate on raw tokens rather than an AST, it potentially saves a lot of
         // time in re-parsing
@@ -326,64 +346,23 @@ impl<'a> Parser<'a> {
 
         self.toks.next();
 
-        let mut body = read_until_closing_curly_brace(self.toks)?;
-
-        body.push(match self.toks.next() {
-            Some(tok) => tok,
-            None => return Err(("expected \"}\".", self.span_before).into()),
-        });
+        Ok(cond)
+    }
 
+    pub(super) fn eval_while(&mut self, cond: &[Token], body: &[AstStmt]) -> SassResult<Vec<Stmt>> {
         let mut stmts = Vec::new();
-        let mut val = self.parse_value_from_vec(cond.clone(), true)?;
+        let mut val = self.parse_value_from_vec(cond.to_vec(), true)?;
         self.scopes.enter_new_scope();
         while val.node.is_true() {
+            let mut these_stmts = self.eval_control_flow_body(body)?;
             if self.flags.in_function() {
-                let these_stmts = Parser {
-                    toks: &mut body.clone().into_iter().peekmore(),
-                    map: self.map,
-                    path: self.path,
-                    scopes: self.scopes,
-                    global_scope: self.global_scope,
-                    super_selectors: self.super_selectors,
-                    span_before: self.span_before,
-                    content: self.content,
-                    flags: self.flags | ContextFlags::IN_CONTROL_FLOW,
-                    at_root: self.at_root,
-                    at_root_has_selector: self.at_root_has_selector,
-                    extender: self.extender,
-                    content_scopes: self.content_scopes,
-                    options: self.options,
-                    modules: self.modules,
-                    module_config: self.module_config,
-                }
-                .parse_stmt()?;
                 if !these_stmts.is_empty() {
                     return Ok(these_stmts);
                 }
             } else {
-                stmts.append(
-                    &mut Parser {
-                        toks: &mut body.clone().into_iter().peekmore(),
-                        map: self.map,
-                        path: self.path,
-                        scopes: self.scopes,
-                        global_scope: self.global_scope,
-                        super_selectors: self.super_selectors,
-                        span_before: self.span_before,
-                        content: self.content,
-                        flags: self.flags | ContextFlags::IN_CONTROL_FLOW,
-                        at_root: self.at_root,
-                        at_root_has_selector: self.at_root_has_selector,
-                        extender: self.extender,
-                        content_scopes: self.content_scopes,
-                        options: self.options,
-                        modules: self.modules,
-                        module_config: self.module_config,
-                    }
-                    .parse_stmt()?,
-                );
+                stmts.append(&mut these_stmts);
             }
-            val = self.parse_value_from_vec(cond.clone(), true)?;
+            val = self.parse_value_from_vec(cond.to_vec(), true)?;
         }
         self.scopes.exit_scope();
 
@@ -391,6 +370,23 @@ impl<'a> Parser<'a> {
     }
 
     pub(super) fn parse_each(&mut self) -> SassResult<Vec<Stmt>> {
+        let header = self.parse_each_header()?;
+
+        self.whitespace();
+        let body = read_until_closing_curly_brace(self.toks)?;
+        if self.toks.next().is_none() {
+            return Err(("expected \"}\".", self.span_before).into());
+        }
+        self.whitespace();
+
+        let body = self.parse_ast(&body);
+
+        self.eval_each(header, &body)
+    }
+
+    /// Parse everything from after `@each` up to and including the `{` that
+    /// begins its body
+    pub(super) fn parse_each_header(&mut self) -> SassResult<EachHeader> {
         let mut vars: Vec<Spanned<Identifier>> = Vec::new();
 
         self.whitespace_or_comment();
@@ -424,13 +420,16 @@ impl<'a> Parser<'a> {
             .node
             .as_list();
         self.toks.next();
-        self.whitespace();
-        let mut body = read_until_closing_curly_brace(self.toks)?;
-        body.push(match self.toks.next() {
-            Some(tok) => tok,
-            None => return Err(("expected \"}\".", self.span_before).into()),
-        });
-        self.whitespace();
+
+        Ok(EachHeader { vars, iter })
+    }
+
+    pub(super) fn eval_each(
+        &mut self,
+        header: EachHeader,
+        body: &[AstStmt],
+    ) -> SassResult<Vec<Stmt>> {
+        let EachHeader { vars, iter } = header;
 
         let mut stmts = Vec::new();
 
@@ -449,51 +448,13 @@ impl<'a> Parser<'a> {
                 }
             }
 
+            let mut these_stmts = self.eval_control_flow_body(body)?;
             if self.flags.in_function() {
-                let these_stmts = Parser {
-                    toks: &mut body.clone().into_iter().peekmore(),
-                    map: self.map,
-                    path: self.path,
-                    scopes: self.scopes,
-                    global_scope: self.global_scope,
-                    super_selectors: self.super_selectors,
-                    span_before: self.span_before,
-                    content: self.content,
-                    flags: self.flags | ContextFlags::IN_CONTROL_FLOW,
-                    at_root: self.at_root,
-                    at_root_has_selector: self.at_root_has_selector,
-                    extender: self.extender,
-                    content_scopes: self.content_scopes,
-                    options: self.options,
-                    modules: self.modules,
-                    module_config: self.module_config,
-                }
-                .parse_stmt()?;
                 if !these_stmts.is_empty() {
                     return Ok(these_stmts);
                 }
             } else {
-                stmts.append(
-                    &mut Parser {
-                        toks: &mut body.clone().into_iter().peekmore(),
-                        map: self.map,
-                        path: self.path,
-                        scopes: self.scopes,
-                        global_scope: self.global_scope,
-                        super_selectors: self.super_selectors,
-                        span_before: self.span_before,
-                        content: self.content,
-                        flags: self.flags | ContextFlags::IN_CONTROL_FLOW,
-                        at_root: self.at_root,
-                        at_root_has_selector: self.at_root_has_selector,
-                        extender: self.extender,
-                        content_scopes: self.content_scopes,
-                        options: self.options,
-                        modules: self.modules,
-                        module_config: self.module_config,
-                    }
-                    .parse_stmt()?,
-                );
+                stmts.append(&mut these_stmts);
             }
         }
 
@@ -501,4 +462,46 @@ impl<'a> Parser<'a> {
 
         Ok(stmts)
     }
+
+    /// Evaluate the body of a control flow rule
+    pub(super) fn eval_control_flow_body(&mut self, body: &[AstStmt]) -> SassResult<Vec<Stmt>> {
+        Parser {
+            toks: &mut Vec::new().into_iter().peekmore(),
+            map: self.map,
+            path: self.path,
+            scopes: self.scopes,
+            global_scope: self.global_scope,
+            super_selectors: self.super_selectors,
+            span_before: self.span_before,
+            content: self.content,
+            flags: self.flags | ContextFlags::IN_CONTROL_FLOW,
+            at_root: self.at_root,
+            at_root_has_selector: self.at_root_has_selector,
+            extender: self.extender,
+            media_queries: self.media_queries,
+            content_scopes: self.content_scopes,
+            options: self.options,
+            modules: self.modules,
+            module_config: self.module_config,
+            forwarded: self.forwarded,
+            call_stack: self.call_stack,
+            custom_function_args: self.custom_function_args,
+        }
+        .eval_ast(body)
+    }
+}
+
+/// The evaluated header of an `@for` rule
+pub(super) struct ForHeader {
+    var: Spanned<Identifier>,
+    from: i32,
+    to: i32,
+    /// 1 if the range includes `to`, or 0 otherwise
+    through: i32,
+}
+
+/// The evaluated header of an `@each` rule
+pub(super) struct EachHeader {
+    vars: Vec<Spanned<Identifier>>,
+    iter: Vec<Value>,
 }
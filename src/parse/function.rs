@@ -6,6 +6,7 @@ use crate::{
     atrule::Function,
     common::{unvendor, Identifier},
     error::SassResult,
+    sass_value::SassValue,
     scope::Scopes,
     utils::{read_until_closing_curly_brace, read_until_semicolon_or_closing_curly_brace},
     value::{SassFunction, Value},
@@ -46,11 +47,11 @@ impl<'a> Parser<'a> {
 
         self.whitespace();
 
-        let mut body = read_until_closing_curly_brace(self.toks)?;
-        body.push(match self.toks.next() {
-            Some(tok) => tok,
-            None => return Err(("expected \"}\".", self.span_before).into()),
-        });
+        let body = read_until_closing_curly_brace(self.toks)?;
+        if self.toks.next().is_none() {
+            return Err(("expected \"}\".", self.span_before).into());
+        }
+        let body = self.parse_ast(&body);
         self.whitespace();
 
         let function = Function::new(args, body, self.at_root, span);
@@ -100,7 +101,7 @@ impl<'a> Parser<'a> {
         };
 
         let mut return_value = Parser {
-            toks: &mut body.into_iter().peekmore(),
+            toks: &mut Vec::new().into_iter().peekmore(),
             map: self.map,
             path: self.path,
             scopes: if declared_at_root {
@@ -116,12 +117,16 @@ impl<'a> Parser<'a> {
             at_root: false,
             at_root_has_selector: self.at_root_has_selector,
             extender: self.extender,
+            media_queries: self.media_queries,
             content_scopes: self.content_scopes,
             options: self.options,
             modules: self.modules,
             module_config: self.module_config,
+            forwarded: self.forwarded,
+            call_stack: self.call_stack,
+            custom_function_args: self.custom_function_args,
         }
-        .parse_stmt()?;
+        .eval_ast(&body)?;
 
         if entered_scope {
             self.scopes.exit_scope();
@@ -136,4 +141,37 @@ impl<'a> Parser<'a> {
             _ => todo!("should be unreachable"),
         }
     }
+
+    /// Call a function registered through `Options::add_function`
+    pub fn eval_custom_function(&mut self, name: Identifier, args: CallArgs) -> SassResult<Value> {
+        let span = args.span();
+        let function = match self.options.custom_function(name) {
+            Some(f) => f,
+            None => return Err((format!("Function not found: {}", name), span).into()),
+        };
+
+        // the signature is parsed once per compilation, as its spans
+        // must refer to the codemap of the current compilation
+        let fn_args = match self.custom_function_args.get(&name) {
+            Some(fn_args) => fn_args.clone(),
+            None => {
+                let fn_args = function.parse_args(self.map, self.options)?;
+                self.custom_function_args.insert(name, fn_args.clone());
+                fn_args
+            }
+        };
+
+        let names: Vec<Identifier> = fn_args.0.iter().map(|arg| arg.name).collect();
+        let mut scope = self.eval_args(fn_args, args)?;
+
+        let args: Vec<SassValue> = names
+            .into_iter()
+            .map(|name| SassValue::from_value(scope.vars.remove(&name).unwrap_or(Value::Null)))
+            .collect();
+
+        match (function.callback)(&args) {
+            Ok(value) => Ok(value.to_value()),
+            Err(message) => Err((message, span).into()),
+        }
+    }
 }
@@ -1,4 +1,4 @@
-use std::{ffi::OsStr, fs, path::Path, path::PathBuf};
+use std::{iter, path::PathBuf};
 
 use codemap::{Span, Spanned};
 use peekmore::PeekMore;
@@ -6,7 +6,8 @@ use peekmore::PeekMore;
 use crate::{
     common::{ListSeparator::Comma, QuoteKind},
     error::SassResult,
-    lexer::Lexer,
+    importer::{FilesystemImporter, Importer},
+    lexer::{is_indented_path, tokenize},
     value::Value,
     Token,
 };
@@ -25,67 +26,34 @@ fn is_plain_css_import(url: &str) -> bool {
 }
 
 impl<'a> Parser<'a> {
-    /// Searches the current directory of the file then searches in `load_paths` directories
-    /// if the import has not yet been found.
+    /// Resolves `url` using the importers registered on `Options`, falling back
+    /// to the filesystem, and loads the stylesheet it refers to
     ///
-    /// <https://sass-lang.com/documentation/at-rules/import#finding-the-file>
-    /// <https://sass-lang.com/documentation/at-rules/import#load-paths>
-    pub(super) fn find_import(&self, path: &Path) -> Option<PathBuf> {
-        let path_buf = if path.is_absolute() {
-            // todo: test for absolute path imports
-            path.into()
-        } else {
-            self.path
-                .parent()
-                .unwrap_or_else(|| Path::new(""))
-                .join(path)
-        };
-
-        let name = path_buf.file_name().unwrap_or_else(|| OsStr::new(".."));
-
-        let paths = [
-            path_buf.with_file_name(name).with_extension("scss"),
-            path_buf
-                .with_file_name(format!("_{}", name.to_str().unwrap()))
-                .with_extension("scss"),
-            path_buf.clone(),
-            path_buf.join("index.scss"),
-            path_buf.join("_index.scss"),
-        ];
-
-        for name in &paths {
-            if name.is_file() {
-                return Some(name.to_path_buf());
-            }
-        }
-
-        for path in &self.options.load_paths {
-            let paths: Vec<PathBuf> = if path.is_dir() {
-                vec![
-                    path.join(format!("{}.scss", name.to_str().unwrap())),
-                    path.join(format!("_{}.scss", name.to_str().unwrap())),
-                    path.join("index.scss"),
-                    path.join("_index.scss"),
-                ]
-            } else {
-                vec![
-                    path.to_path_buf(),
-                    path.with_file_name(name).with_extension("scss"),
-                    path.with_file_name(format!("_{}", name.to_str().unwrap()))
-                        .with_extension("scss"),
-                    path.join("index.scss"),
-                    path.join("_index.scss"),
-                ]
-            };
-
-            for name in paths {
-                if name.is_file() {
-                    return Some(name);
+    /// Returns the canonical path of the stylesheet along with its contents
+    pub(super) fn load_import(
+        &self,
+        url: &str,
+        span: Span,
+    ) -> SassResult<Option<(PathBuf, String)>> {
+        let filesystem = FilesystemImporter::new(&self.options.load_paths);
+
+        for importer in self
+            .options
+            .importers
+            .iter()
+            .copied()
+            .chain(iter::once(&filesystem as &dyn Importer))
+        {
+            if let Some(canonical) = importer.canonicalize(url, self.path) {
+                if let Some(graph) = self.options.dependencies {
+                    graph.record(self.path, &canonical);
                 }
+                let contents = importer.load(&canonical).map_err(|e| e.with_span(span))?;
+                return Ok(Some((canonical, contents)));
             }
         }
 
-        None
+        Ok(None)
     }
 
     pub(crate) fn parse_single_import(
@@ -93,35 +61,34 @@ impl<'a> Parser<'a> {
         file_name: &str,
         span: Span,
     ) -> SassResult<Vec<Stmt>> {
-        let path: &Path = file_name.as_ref();
-
-        if let Some(name) = self.find_import(path) {
-            let file = self.map.add_file(
-                name.to_string_lossy().into(),
-                String::from_utf8(fs::read(&name)?)?,
-            );
-            return Parser {
-                toks: &mut Lexer::new(&file)
-                    .collect::<Vec<Token>>()
-                    .into_iter()
-                    .peekmore(),
-                map: self.map,
-                path: &name,
-                scopes: self.scopes,
-                global_scope: self.global_scope,
-                super_selectors: self.super_selectors,
-                span_before: file.span.subspan(0, 0),
-                content: self.content,
-                flags: self.flags,
-                at_root: self.at_root,
-                at_root_has_selector: self.at_root_has_selector,
-                extender: self.extender,
-                content_scopes: self.content_scopes,
-                options: self.options,
-                modules: self.modules,
-                module_config: self.module_config,
-            }
-            .parse();
+        if let Some((name, contents)) = self.load_import(file_name, span)? {
+            let file = self.map.add_file(name.to_string_lossy().into(), contents);
+            let toks = tokenize(&file, is_indented_path(&name))?;
+            return self.with_call("@import".to_owned(), span, |parser| {
+                Parser {
+                    toks: &mut toks.into_iter().peekmore(),
+                    map: parser.map,
+                    path: &name,
+                    scopes: parser.scopes,
+                    global_scope: parser.global_scope,
+                    super_selectors: parser.super_selectors,
+                    span_before: file.span.subspan(0, 0),
+                    content: parser.content,
+                    flags: parser.flags,
+                    at_root: parser.at_root,
+                    at_root_has_selector: parser.at_root_has_selector,
+                    extender: parser.extender,
+                    media_queries: parser.media_queries,
+                    content_scopes: parser.content_scopes,
+                    options: parser.options,
+                    modules: parser.modules,
+                    module_config: parser.module_config,
+                    forwarded: parser.forwarded,
+                    call_stack: parser.call_stack,
+                    custom_function_args: parser.custom_function_args,
+                }
+                .parse()
+            });
         }
 
         Err(("Can't find stylesheet to import.", span).into())
@@ -1,5 +1,7 @@
 use std::fmt;
 
+use codemap::Span;
+
 use peekmore::PeekMore;
 
 use crate::{
@@ -170,10 +172,14 @@ impl<'a> Parser<'a> {
                         at_root: self.at_root,
                         at_root_has_selector: self.at_root_has_selector,
                         extender: self.extender,
+                        media_queries: self.media_queries,
                         content_scopes: self.content_scopes,
                         options: self.options,
                         modules: self.modules,
                         module_config: self.module_config,
+                        forwarded: self.forwarded,
+                        call_stack: self.call_stack,
+                        custom_function_args: self.custom_function_args,
                     })
                     .parse_keyframes_selector()?;
 
@@ -185,7 +191,8 @@ impl<'a> Parser<'a> {
         Err(("expected \"{\".", span).into())
     }
 
-    pub(super) fn parse_keyframes(&mut self, rule: String) -> SassResult<Stmt> {
+    /// Parse a `@keyframes` rule, which begins at `span`
+    pub(super) fn parse_keyframes(&mut self, rule: String, span: Span) -> SassResult<Stmt> {
         if self.flags.in_function() {
             return Err(("This at-rule is not allowed here.", self.span_before).into());
         }
@@ -207,13 +214,22 @@ impl<'a> Parser<'a> {
             at_root: false,
             at_root_has_selector: self.at_root_has_selector,
             extender: self.extender,
+            media_queries: self.media_queries,
             content_scopes: self.content_scopes,
             options: self.options,
             modules: self.modules,
             module_config: self.module_config,
+            forwarded: self.forwarded,
+            call_stack: self.call_stack,
+            custom_function_args: self.custom_function_args,
         }
         .parse_stmt()?;
 
-        Ok(Stmt::Keyframes(Box::new(Keyframes { rule, name, body })))
+        Ok(Stmt::Keyframes(Box::new(Keyframes {
+            rule,
+            name,
+            body,
+            span,
+        })))
     }
 }
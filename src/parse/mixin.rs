@@ -42,11 +42,11 @@ impl<'a> Parser<'a> {
 
         self.whitespace();
 
-        let mut body = read_until_closing_curly_brace(self.toks)?;
-        body.push(match self.toks.next() {
-            Some(tok) => tok,
-            None => return Err(("expected \"}\".", self.span_before).into()),
-        });
+        let body = read_until_closing_curly_brace(self.toks)?;
+        if self.toks.next().is_none() {
+            return Err(("expected \"}\".", self.span_before).into());
+        }
+        let body = self.parse_ast(&body);
 
         // todo: `@include` can only give content when `@content` is present within the body
         // if `@content` is *not* present and `@include` attempts to give a body, we throw an error
@@ -165,25 +165,31 @@ impl<'a> Parser<'a> {
             declared_at_root,
         });
 
-        let body = Parser {
-            toks: &mut body.into_iter().peekmore(),
-            map: self.map,
-            path: self.path,
-            scopes: self.scopes,
-            global_scope: self.global_scope,
-            super_selectors: self.super_selectors,
-            span_before: self.span_before,
-            flags: self.flags | ContextFlags::IN_MIXIN,
-            content: self.content,
-            at_root: false,
-            at_root_has_selector: self.at_root_has_selector,
-            extender: self.extender,
-            content_scopes: self.content_scopes,
-            options: self.options,
-            modules: self.modules,
-            module_config: self.module_config,
-        }
-        .parse_stmt()?;
+        let body = self.with_call(format!("{}()", name.node), name.span, |parser| {
+            Parser {
+                toks: &mut Vec::new().into_iter().peekmore(),
+                map: parser.map,
+                path: parser.path,
+                scopes: parser.scopes,
+                global_scope: parser.global_scope,
+                super_selectors: parser.super_selectors,
+                span_before: parser.span_before,
+                flags: parser.flags | ContextFlags::IN_MIXIN,
+                content: parser.content,
+                at_root: false,
+                at_root_has_selector: parser.at_root_has_selector,
+                extender: parser.extender,
+                media_queries: parser.media_queries,
+                content_scopes: parser.content_scopes,
+                options: parser.options,
+                modules: parser.modules,
+                module_config: parser.module_config,
+                forwarded: parser.forwarded,
+                call_stack: parser.call_stack,
+                custom_function_args: parser.custom_function_args,
+            }
+            .eval_ast(&body)
+        })?;
 
         self.content.pop();
         self.scopes.exit_scope();
@@ -228,25 +234,31 @@ impl<'a> Parser<'a> {
             }
 
             let stmts = if let Some(body) = content.content.clone() {
-                Parser {
-                    toks: &mut body.into_iter().peekmore(),
-                    map: self.map,
-                    path: self.path,
-                    scopes: &mut scope_at_decl,
-                    global_scope: self.global_scope,
-                    super_selectors: self.super_selectors,
-                    span_before: self.span_before,
-                    flags: self.flags,
-                    content: self.content,
-                    at_root: self.at_root,
-                    at_root_has_selector: self.at_root_has_selector,
-                    extender: self.extender,
-                    content_scopes: self.scopes,
-                    options: self.options,
-                    modules: self.modules,
-                    module_config: self.module_config,
-                }
-                .parse_stmt()?
+                self.with_call("@content".to_owned(), self.span_before, |parser| {
+                    Parser {
+                        toks: &mut body.into_iter().peekmore(),
+                        map: parser.map,
+                        path: parser.path,
+                        scopes: &mut scope_at_decl,
+                        global_scope: parser.global_scope,
+                        super_selectors: parser.super_selectors,
+                        span_before: parser.span_before,
+                        flags: parser.flags,
+                        content: parser.content,
+                        at_root: parser.at_root,
+                        at_root_has_selector: parser.at_root_has_selector,
+                        extender: parser.extender,
+                        media_queries: parser.media_queries,
+                        content_scopes: parser.scopes,
+                        options: parser.options,
+                        modules: parser.modules,
+                        module_config: parser.module_config,
+                        forwarded: parser.forwarded,
+                        call_stack: parser.call_stack,
+                        custom_function_args: parser.custom_function_args,
+                    }
+                    .parse_stmt()
+                })?
             } else {
                 Vec::new()
             };
@@ -1,32 +1,36 @@
-use std::{convert::TryFrom, path::Path, vec::IntoIter};
+use std::{collections::HashMap, convert::TryFrom, path::Path, vec::IntoIter};
 
 use codemap::{CodeMap, Span, Spanned};
 use peekmore::{PeekMore, PeekMoreIterator};
 
 use crate::{
+    args::FuncArgs,
     atrule::{
         keyframes::{Keyframes, KeyframesRuleSet},
-        media::MediaRule,
+        media::{MediaQuery, MediaQueryParser, MediaRule},
         mixin::Content,
         AtRuleKind, SupportsRule, UnknownAtRule,
     },
     builtin::modules::{ModuleConfig, Modules},
-    error::SassResult,
+    common::Identifier,
+    error::{stack_trace, Call, SassResult},
     scope::{Scope, Scopes},
     selector::{
         ComplexSelectorComponent, ExtendRule, ExtendedSelector, Extender, Selector, SelectorParser,
     },
     style::Style,
     utils::{read_until_closing_curly_brace, read_until_semicolon_or_closing_curly_brace},
-    value::Value,
+    value::{Division, Value},
     Options, {Cow, Token},
 };
 
+pub(crate) use ast::AstStmt;
 use common::{Comment, ContextFlags, NeverEmptyVec, SelectorOrStyle};
-pub(crate) use value::{HigherIntermediateValue, ValueVisitor};
+pub(crate) use value::{divide, HigherIntermediateValue, ValueVisitor};
 use variable::VariableValue;
 
 mod args;
+mod ast;
 pub mod common;
 mod control_flow;
 mod function;
@@ -37,6 +41,7 @@ mod media;
 mod mixin;
 mod module;
 mod style;
+mod supports;
 mod throw_away;
 mod value;
 mod variable;
@@ -85,11 +90,22 @@ pub(crate) struct Parser<'a> {
     /// not the `@at-rule` block has a super selector
     pub at_root_has_selector: bool,
     pub extender: &'a mut Extender,
+    /// The queries of the innermost `@media` rule, merged with those of any
+    /// rules it's nested within, or `None` outside of `@media`
+    pub media_queries: &'a mut Option<Vec<MediaQuery>>,
 
     pub options: &'a Options<'a>,
 
     pub modules: &'a mut Modules,
     pub module_config: &'a mut ModuleConfig,
+    /// Members made available to downstream modules through `@forward`
+    pub forwarded: &'a mut Scope,
+    /// The mixins, functions, and stylesheets currently being evaluated,
+    /// outermost first
+    pub call_stack: &'a mut Vec<Call>,
+    /// The arguments of the functions registered through
+    /// `Options::add_function`, parsed once per compilation
+    pub custom_function_args: &'a mut HashMap<Identifier, FuncArgs>,
 }
 
 impl<'a> Parser<'a> {
@@ -109,6 +125,46 @@ impl<'a> Parser<'a> {
         Ok(stmts)
     }
 
+    /// Run `f` within a call to the mixin, function, or stylesheet `name`,
+    /// made at `span`, so that errors it returns know where they came from
+    pub fn with_call<T>(
+        &mut self,
+        name: String,
+        span: Span,
+        f: impl FnOnce(&mut Self) -> SassResult<T>,
+    ) -> SassResult<T> {
+        self.call_stack.push(Call { name, span });
+        let result = f(self).map_err(|e| e.with_call_stack(self.call_stack));
+        self.call_stack.pop();
+        result
+    }
+
+    /// Run `f` on a parser reading from `toks` rather than `self.toks`
+    pub fn with_toks<T>(&mut self, toks: Vec<Token>, f: impl FnOnce(&mut Parser<'_>) -> T) -> T {
+        f(&mut Parser {
+            toks: &mut toks.into_iter().peekmore(),
+            map: self.map,
+            path: self.path,
+            scopes: self.scopes,
+            global_scope: self.global_scope,
+            super_selectors: self.super_selectors,
+            span_before: self.span_before,
+            content: self.content,
+            flags: self.flags,
+            at_root: self.at_root,
+            at_root_has_selector: self.at_root_has_selector,
+            extender: self.extender,
+            media_queries: self.media_queries,
+            content_scopes: self.content_scopes,
+            options: self.options,
+            modules: self.modules,
+            module_config: self.module_config,
+            forwarded: self.forwarded,
+            call_stack: self.call_stack,
+            custom_function_args: self.custom_function_args,
+        })
+    }
+
     pub fn expect_char(&mut self, c: char) -> SassResult<()> {
         match self.toks.peek() {
             Some(Token { kind, pos }) if *kind == c => {
@@ -134,7 +190,7 @@ impl<'a> Parser<'a> {
     pub fn expect_identifier(&mut self, ident: &'static str) -> SassResult<()> {
         let this_ident = self.parse_identifier_no_interpolation(false)?;
         self.span_before = this_ident.span;
-        if this_ident.node == ident {
+        if this_ident.node.eq_ignore_ascii_case(ident) {
             return Ok(());
         }
 
@@ -150,9 +206,11 @@ impl<'a> Parser<'a> {
             self.span_before = *pos;
             match kind {
                 '@' => {
+                    let at_rule_start = self.span_before;
                     self.toks.next();
                     let kind_string = self.parse_identifier()?;
                     self.span_before = kind_string.span;
+                    let at_rule_span = at_rule_start.merge(kind_string.span);
                     match AtRuleKind::try_from(&kind_string)? {
                         AtRuleKind::Import => stmts.append(&mut self.import()?),
                         AtRuleKind::Mixin => self.parse_mixin()?,
@@ -209,8 +267,13 @@ impl<'a> Parser<'a> {
                                 kind_string.span.merge(*pos);
                                 self.toks.next();
                             }
+                            // like dart-sass, strings are written without quotes
+                            let message = match message {
+                                Value::String(s, ..) => Cow::owned(s),
+                                message => message.to_css_string(span)?,
+                            };
                             self.warn(&Spanned {
-                                node: message.to_css_string(span)?,
+                                node: message,
                                 span,
                             })
                         }
@@ -248,9 +311,9 @@ impl<'a> Parser<'a> {
                             }
                             continue;
                         }
-                        AtRuleKind::Media => stmts.push(self.parse_media()?),
+                        AtRuleKind::Media => stmts.push(self.parse_media(at_rule_span)?),
                         AtRuleKind::Unknown(_) => {
-                            stmts.push(self.parse_unknown_at_rule(kind_string.node)?)
+                            stmts.push(self.parse_unknown_at_rule(kind_string.node, at_rule_span)?)
                         }
                         AtRuleKind::Use => {
                             return Err((
@@ -259,11 +322,17 @@ impl<'a> Parser<'a> {
                             )
                                 .into())
                         }
-                        AtRuleKind::Forward => todo!("@forward not yet implemented"),
+                        AtRuleKind::Forward => {
+                            return Err((
+                                "@forward rules must be written before any other rules.",
+                                kind_string.span,
+                            )
+                                .into())
+                        }
                         AtRuleKind::Extend => self.parse_extend()?,
-                        AtRuleKind::Supports => stmts.push(self.parse_supports()?),
+                        AtRuleKind::Supports => stmts.push(self.parse_supports(at_rule_span)?),
                         AtRuleKind::Keyframes => {
-                            stmts.push(self.parse_keyframes(kind_string.node)?)
+                            stmts.push(self.parse_keyframes(kind_string.node, at_rule_span)?)
                         }
                     }
                 }
@@ -295,6 +364,7 @@ impl<'a> Parser<'a> {
                 // dart-sass seems to special-case the error message here?
                 '!' | '{' => return Err(("expected \"}\".", *pos).into()),
                 _ => {
+                    let selector_start = *pos;
                     if self.flags.in_function() {
                         return Err((
                         "Functions can only contain variable declarations and control directives.",
@@ -351,17 +421,22 @@ impl<'a> Parser<'a> {
                         SelectorOrStyle::Selector(init) => {
                             let at_root = self.at_root;
                             self.at_root = false;
-                            let selector = self
+                            let mut selector = self
                                 .parse_selector(!self.super_selectors.is_empty(), false, init)?
-                                .0
-                                .resolve_parent_selectors(
-                                    self.super_selectors.last(),
-                                    !at_root || self.at_root_has_selector,
-                                )?;
+                                .0;
+                            // part of the selector may already have been consumed while
+                            // checking whether this is a declaration
+                            selector.0.span = selector_start.merge(selector.0.span);
+                            let selector = selector.resolve_parent_selectors(
+                                self.super_selectors.last(),
+                                !at_root || self.at_root_has_selector,
+                            )?;
                             self.scopes.enter_new_scope();
                             self.super_selectors.push(selector.clone());
 
-                            let extended_selector = self.extender.add_selector(selector.0, None);
+                            let extended_selector = self
+                                .extender
+                                .add_selector(selector.0, self.media_queries.clone())?;
 
                             let body = self.parse_stmt()?;
                             self.scopes.exit_scope();
@@ -464,10 +539,14 @@ impl<'a> Parser<'a> {
                 at_root: self.at_root,
                 at_root_has_selector: self.at_root_has_selector,
                 extender: self.extender,
+                media_queries: self.media_queries,
                 content_scopes: self.content_scopes,
                 options: self.options,
                 modules: self.modules,
                 module_config: self.module_config,
+                forwarded: self.forwarded,
+                call_stack: self.call_stack,
+                custom_function_args: self.custom_function_args,
             },
             allows_parent,
             true,
@@ -609,7 +688,8 @@ impl<'a> Parser<'a> {
 }
 
 impl<'a> Parser<'a> {
-    fn parse_unknown_at_rule(&mut self, name: String) -> SassResult<Stmt> {
+    /// Parse an at-rule that Sass doesn't know about, which begins at `span`
+    fn parse_unknown_at_rule(&mut self, name: String, span: Span) -> SassResult<Stmt> {
         if self.flags.in_function() {
             return Err(("This at-rule is not allowed here.", self.span_before).into());
         }
@@ -623,6 +703,7 @@ impl<'a> Parser<'a> {
                 super_selector: Selector::new(self.span_before),
                 params: String::new(),
                 body: Vec::new(),
+                span,
             })));
         }
         while let Some(tok) = self.toks.next() {
@@ -673,22 +754,53 @@ impl<'a> Parser<'a> {
             super_selector: Selector::new(self.span_before),
             params: params.trim().to_owned(),
             body,
+            span,
         })))
     }
 
-    fn parse_media(&mut self) -> SassResult<Stmt> {
+    /// Parse an `@media` rule, which begins at `span`
+    fn parse_media(&mut self, span: Span) -> SassResult<Stmt> {
         if self.flags.in_function() {
             return Err(("This at-rule is not allowed here.", self.span_before).into());
         }
 
         let query = self.parse_media_query_list()?;
+        let query_span = self.span_before;
+
+        let queries = self.with_toks(
+            query.chars().map(|c| Token::new(query_span, c)).collect(),
+            |parser| MediaQueryParser::new(parser, query_span).parse(),
+        )?;
+
+        let merged_queries = self
+            .media_queries
+            .as_ref()
+            .and_then(|outer| MediaQuery::merge_lists(outer, &queries));
 
         self.whitespace();
 
         self.expect_char('{')?;
 
+        let is_merged = merged_queries.is_some();
+        let queries = merged_queries.unwrap_or(queries);
+
+        let outer_queries = self.media_queries.replace(queries.clone());
+
         let raw_body = self.parse_stmt()?;
 
+        *self.media_queries = outer_queries;
+
+        // no media can match both this query and the one enclosing it
+        if queries.is_empty() {
+            return Ok(Stmt::Media(Box::new(MediaRule {
+                super_selector: Selector::new(self.span_before),
+                query: queries,
+                body: Vec::new(),
+                is_merged,
+                span,
+            })));
+        }
+
         let mut rules = Vec::with_capacity(raw_body.len());
         let mut body = Vec::new();
 
@@ -710,8 +822,10 @@ impl<'a> Parser<'a> {
 
         Ok(Stmt::Media(Box::new(MediaRule {
             super_selector: Selector::new(self.span_before),
-            query,
+            query: queries,
             body,
+            is_merged,
+            span,
         })))
     }
 
@@ -751,10 +865,14 @@ impl<'a> Parser<'a> {
             at_root: true,
             at_root_has_selector,
             extender: self.extender,
+            media_queries: self.media_queries,
             content_scopes: self.content_scopes,
             options: self.options,
             modules: self.modules,
             module_config: self.module_config,
+            forwarded: self.forwarded,
+            call_stack: self.call_stack,
+            custom_function_args: self.custom_function_args,
         }
         .parse_stmt()?
         .into_iter()
@@ -797,10 +915,14 @@ impl<'a> Parser<'a> {
             at_root: self.at_root,
             at_root_has_selector: self.at_root_has_selector,
             extender: self.extender,
+            media_queries: self.media_queries,
             content_scopes: self.content_scopes,
             options: self.options,
             modules: self.modules,
             module_config: self.module_config,
+            forwarded: self.forwarded,
+            call_stack: self.call_stack,
+            custom_function_args: self.custom_function_args,
         }
         .parse_selector(false, true, String::new())?;
 
@@ -837,24 +959,27 @@ impl<'a> Parser<'a> {
                 super_selector.clone().0,
                 compound.components.first().unwrap(),
                 &extend_rule,
-                &None,
+                self.media_queries,
                 self.span_before,
-            )
+            )?;
         }
 
         Ok(())
     }
 
-    fn parse_supports(&mut self) -> SassResult<Stmt> {
+    /// Parse a `@supports` rule, which begins at `span`
+    fn parse_supports(&mut self, span: Span) -> SassResult<Stmt> {
         if self.flags.in_function() {
             return Err(("This at-rule is not allowed here.", self.span_before).into());
         }
 
-        let params = self.parse_media_args()?;
+        self.whitespace_or_comment();
 
-        if params.is_empty() {
-            return Err(("Expected \"not\".", self.span_before).into());
-        }
+        let condition = self.parse_supports_condition()?;
+
+        self.whitespace_or_comment();
+
+        self.expect_char('{')?;
 
         let raw_body = self.parse_stmt()?;
 
@@ -878,67 +1003,97 @@ impl<'a> Parser<'a> {
         body.append(&mut rules);
 
         Ok(Stmt::Supports(Box::new(SupportsRule {
-            params: params.trim().to_owned(),
+            condition,
             body,
+            span,
         })))
     }
-
-    // todo: we should use a specialized struct to represent these
-    fn parse_media_args(&mut self) -> SassResult<String> {
-        let mut params = String::new();
-        self.whitespace();
-        while let Some(tok) = self.toks.next() {
-            match tok.kind {
-                '{' => break,
-                '#' => {
-                    if let Some(Token { kind: '{', pos }) = self.toks.peek().cloned() {
-                        self.toks.next();
-                        self.span_before = pos;
-                        let interpolation = self.parse_interpolation()?;
-                        params.push_str(&interpolation.node.to_css_string(interpolation.span)?);
-                        continue;
-                    } else {
-                        params.push(tok.kind);
-                    }
-                }
-                '\n' | ' ' | '\t' => {
-                    self.whitespace();
-                    params.push(' ');
-                    continue;
-                }
-                _ => {}
-            }
-            params.push(tok.kind);
-        }
-        Ok(params)
-    }
 }
 
 impl<'a> Parser<'a> {
     fn debug(&self, message: &Spanned<Cow<'a, str>>) {
-        if self.options.quiet {
-            return;
+        if let Some(logger) = self.options.active_logger() {
+            let trace = stack_trace(self.map, message.span, self.call_stack);
+            logger.debug(&message.node, &trace[0]);
         }
-        let loc = self.map.look_up_span(message.span);
-        eprintln!(
-            "{}:{} Debug: {}",
-            loc.file.name(),
-            loc.begin.line + 1,
-            message.node
-        );
     }
 
     fn warn(&self, message: &Spanned<Cow<'a, str>>) {
-        if self.options.quiet {
-            return;
+        if let Some(logger) = self.options.active_logger() {
+            logger.warn(
+                &message.node,
+                false,
+                &stack_trace(self.map, message.span, self.call_stack),
+            );
+        }
+    }
+
+    /// Warn that a deprecated feature was used at `span`
+    pub(crate) fn deprecation_warning(&self, message: &str, span: Span) {
+        if let Some(logger) = self.options.active_logger() {
+            logger.warn(message, true, &stack_trace(self.map, span, self.call_stack));
+        }
+    }
+
+    /// Warn that `/` was used at `span` to divide `left` by `right`,
+    /// if this has been enabled through `Options::warn_slash_division`
+    pub(crate) fn slash_division_warning(
+        &self,
+        left: &Value,
+        right: &Value,
+        span: Span,
+    ) -> SassResult<()> {
+        if !self.options.warn_slash_division {
+            return Ok(());
         }
-        let loc = self.map.look_up_span(message.span);
-        eprintln!(
-            "Warning: {}\n    {} {}:{}  root stylesheet",
-            message.node,
-            loc.file.name(),
-            loc.begin.line + 1,
-            loc.begin.column + 1
+
+        self.deprecation_warning(
+            &format!(
+                "Using / for division outside of calc() is deprecated and will be removed in Dart Sass 2.0.0.\n\n\
+                Recommendation: math.div({}, {}) or calc({} / {})\n\n\
+                More info and automated migrator: https://sass-lang.com/d/slash-div",
+                Self::slash_recommendation(left, true, span)?,
+                Self::slash_recommendation(right, true, span)?,
+                Self::slash_recommendation(left, false, span)?,
+                Self::slash_recommendation(right, false, span)?,
+            ),
+            span,
         );
+
+        Ok(())
+    }
+
+    /// `value` as it is written in a recommendation to replace `/`, in
+    /// which numbers that `/` separated are divided with `math.div()` if
+    /// `math_div` is true, or with `/` inside `calc()` otherwise
+    fn slash_recommendation(value: &Value, math_div: bool, span: Span) -> SassResult<String> {
+        Ok(match value {
+            Value::Dimension(_, _, Division::Slash(operands)) => {
+                let left = Self::slash_recommendation(&operands.0, math_div, span)?;
+                let right = Self::slash_recommendation(&operands.1, math_div, span)?;
+                if math_div {
+                    format!("math.div({}, {})", left, right)
+                } else {
+                    format!("{} / {}", left, right)
+                }
+            }
+            v => v.inspect(span)?.into_owned(),
+        })
+    }
+
+    /// Divide `value` if `/` separated it into two numbers rather than
+    /// dividing them, now that it is used as a number, and warn about it
+    /// as `slash_division_warning` does
+    pub(crate) fn without_slash(&self, value: Value, span: Span) -> SassResult<Value> {
+        Ok(match value {
+            Value::Dimension(num, unit, Division::Slash(operands)) => {
+                self.slash_division_warning(&operands.0, &operands.1, span)?;
+                Value::Dimension(num, unit, Division::Computed)
+            }
+            Value::Dimension(num, unit, Division::Literal) => {
+                Value::Dimension(num, unit, Division::Computed)
+            }
+            v => v,
+        })
     }
 }
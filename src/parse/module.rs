@@ -1,4 +1,4 @@
-use std::{convert::TryFrom, fs};
+use std::{collections::BTreeSet, convert::TryFrom};
 
 use codemap::Spanned;
 use peekmore::PeekMore;
@@ -7,17 +7,29 @@ use crate::{
     atrule::AtRuleKind,
     builtin::modules::{
         declare_module_color, declare_module_list, declare_module_map, declare_module_math,
-        declare_module_meta, declare_module_selector, declare_module_string, Module, ModuleConfig,
+        declare_module_meta, declare_module_selector, declare_module_string, ForwardVisibility,
+        Module, ModuleConfig,
     },
     common::Identifier,
     error::SassResult,
-    lexer::Lexer,
+    lexer::{is_indented_path, tokenize},
     parse::{common::Comment, Parser, Stmt, VariableValue},
     scope::Scope,
     utils::peek_ident_no_interpolation,
     Token,
 };
 
+/// The namespace of a module loaded without `as`, which is the last component
+/// of its url without any scheme, leading underscore, or extension
+///
+/// e.g. `sass:math` => `math`, `pkg:foo/_bar.scss` => `bar`
+fn default_namespace(url: &str) -> String {
+    let name = url.rsplit('/').next().unwrap_or(url);
+    let name = name.rsplit(':').next().unwrap_or(name);
+    let name = name.strip_prefix('_').unwrap_or(name);
+    name.split('.').next().unwrap_or(name).to_owned()
+}
+
 impl<'a> Parser<'a> {
     fn parse_module_alias(&mut self) -> SassResult<Option<String>> {
         if let Some(Token { kind: 'a', .. }) | Some(Token { kind: 'A', .. }) = self.toks.peek() {
@@ -43,7 +55,19 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_module_config(&mut self) -> SassResult<ModuleConfig> {
+        Ok(self.parse_configuration(false)?.0)
+    }
+
+    /// Parses the `with (...)` clause of `@use` and `@forward`
+    ///
+    /// Returns both the configured variables and, if `allows_default` is set,
+    /// the variables that were marked `!default`
+    fn parse_configuration(
+        &mut self,
+        allows_default: bool,
+    ) -> SassResult<(ModuleConfig, ModuleConfig)> {
         let mut config = ModuleConfig::default();
+        let mut default_config = ModuleConfig::default();
 
         if let Some(Token { kind: 'w', .. }) | Some(Token { kind: 'W', .. }) = self.toks.peek() {
             let mut ident = peek_ident_no_interpolation(self.toks, false, self.span_before)?;
@@ -70,10 +94,33 @@ impl<'a> Parser<'a> {
 
                 let value = self.parse_value(false, &|toks| match toks.peek() {
                     Some(Token { kind: ',', .. }) | Some(Token { kind: ')', .. }) => true,
+                    Some(Token { kind: '!', .. }) => allows_default,
                     _ => false,
                 })?;
 
-                config.insert(name.map_node(|n| n.into()), value)?;
+                let is_default = if allows_default && self.consume_char_if_exists('!') {
+                    self.expect_identifier("default")?;
+                    self.whitespace_or_comment();
+                    true
+                } else {
+                    false
+                };
+
+                let name = name.map_node(|n| n.into());
+
+                if config.contains(name.node) || default_config.contains(name.node) {
+                    return Err((
+                        "The same variable may only be configured once.",
+                        name.span.merge(value.span),
+                    )
+                        .into());
+                }
+
+                if is_default {
+                    default_config.insert(name, value)?;
+                } else {
+                    config.insert(name, value)?;
+                }
 
                 match self.toks.next() {
                     Some(Token { kind: ',', .. }) => {
@@ -89,14 +136,119 @@ impl<'a> Parser<'a> {
             }
         }
 
-        Ok(config)
+        Ok((config, default_config))
     }
 
+    /// Consumes `keyword`, ignoring case, if it is the next identifier
+    fn scan_keyword(&mut self, keyword: &str) -> bool {
+        match peek_ident_no_interpolation(self.toks, false, self.span_before) {
+            Ok(ident) if ident.node.eq_ignore_ascii_case(keyword) => {
+                self.toks.truncate_iterator_to_cursor();
+                true
+            }
+            Ok(..) | Err(..) => {
+                self.toks.reset_cursor();
+                false
+            }
+        }
+    }
+
+    /// Parses the `as prefix-*` clause of `@forward`
+    fn parse_forward_prefix(&mut self) -> SassResult<Option<String>> {
+        if !self.scan_keyword("as") {
+            return Ok(None);
+        }
+
+        self.whitespace_or_comment();
+
+        let prefix = self.parse_identifier_no_interpolation(false)?;
+
+        self.span_before = prefix.span;
+        self.expect_char('*')?;
+
+        Ok(Some(prefix.node))
+    }
+
+    /// Parses the `show ...` or `hide ...` clause of `@forward`
+    fn parse_forward_visibility(&mut self) -> SassResult<ForwardVisibility> {
+        let is_show = if self.scan_keyword("show") {
+            true
+        } else if self.scan_keyword("hide") {
+            false
+        } else {
+            return Ok(ForwardVisibility::All);
+        };
+
+        let mut vars = BTreeSet::new();
+        let mut members = BTreeSet::new();
+
+        loop {
+            self.whitespace_or_comment();
+
+            if self.consume_char_if_exists('$') {
+                vars.insert(self.parse_identifier_no_interpolation(false)?.node.into());
+            } else {
+                members.insert(self.parse_identifier_no_interpolation(false)?.node.into());
+            }
+
+            self.whitespace_or_comment();
+
+            if !self.consume_char_if_exists(',') {
+                break;
+            }
+        }
+
+        Ok(if is_show {
+            ForwardVisibility::Show { vars, members }
+        } else {
+            ForwardVisibility::Hide { vars, members }
+        })
+    }
+
+    /// Loads the module at the url `name` for `rule`, such as `@use`,
+    /// which names the module in stack traces
     pub fn load_module(
         &mut self,
         name: &str,
+        rule: &str,
+        config: &mut ModuleConfig,
+    ) -> SassResult<(Module, Vec<Stmt>)> {
+        let (module, stmts) = self.load_module_with_unused_config(name, rule, config)?;
+
+        if !module.is_builtin && !config.is_empty() {
+            return Err((
+                "This variable was not declared with !default in the @used module.",
+                self.span_before,
+            )
+                .into());
+        }
+
+        Ok((module, stmts))
+    }
+
+    /// Loads a module, leaving any configured variables that it did not
+    /// declare in `config`
+    fn load_module_with_unused_config(
+        &mut self,
+        name: &str,
+        rule: &str,
         config: &mut ModuleConfig,
     ) -> SassResult<(Module, Vec<Stmt>)> {
+        for (var, value) in self.options.module_config_for(name) {
+            if !config.contains(var) {
+                config.insert(
+                    Spanned {
+                        node: var,
+                        span: self.span_before,
+                    },
+                    Spanned {
+                        node: value,
+                        span: self.span_before,
+                    },
+                )?;
+            }
+        }
+
         Ok(match name {
             "sass:color" => (declare_module_color(), Vec::new()),
             "sass:list" => (declare_module_list(), Vec::new()),
@@ -106,45 +258,44 @@ impl<'a> Parser<'a> {
             "sass:selector" => (declare_module_selector(), Vec::new()),
             "sass:string" => (declare_module_string(), Vec::new()),
             _ => {
-                if let Some(import) = self.find_import(name.as_ref()) {
+                if let Some((import, contents)) = self.load_import(name, self.span_before)? {
                     let mut global_scope = Scope::new();
+                    let mut forwarded = Scope::new();
+
+                    let file = self.map.add_file(import.to_string_lossy().into(), contents);
+
+                    let toks = tokenize(&file, is_indented_path(&import))?;
+                    let stmts = self.with_call(rule.to_owned(), self.span_before, |parser| {
+                        Parser {
+                            toks: &mut toks.into_iter().peekmore(),
+                            map: parser.map,
+                            path: &import,
+                            scopes: parser.scopes,
+                            global_scope: &mut global_scope,
+                            super_selectors: parser.super_selectors,
+                            span_before: file.span.subspan(0, 0),
+                            content: parser.content,
+                            flags: parser.flags,
+                            at_root: parser.at_root,
+                            at_root_has_selector: parser.at_root_has_selector,
+                            extender: parser.extender,
+                            media_queries: parser.media_queries,
+                            content_scopes: parser.content_scopes,
+                            options: parser.options,
+                            modules: parser.modules,
+                            module_config: config,
+                            forwarded: &mut forwarded,
+                            call_stack: parser.call_stack,
+                            custom_function_args: parser.custom_function_args,
+                        }
+                        .parse()
+                    })?;
 
-                    let file = self
-                        .map
-                        .add_file(name.to_owned(), String::from_utf8(fs::read(&import)?)?);
-
-                    let stmts = Parser {
-                        toks: &mut Lexer::new(&file)
-                            .collect::<Vec<Token>>()
-                            .into_iter()
-                            .peekmore(),
-                        map: self.map,
-                        path: &import,
-                        scopes: self.scopes,
-                        global_scope: &mut global_scope,
-                        super_selectors: self.super_selectors,
-                        span_before: file.span.subspan(0, 0),
-                        content: self.content,
-                        flags: self.flags,
-                        at_root: self.at_root,
-                        at_root_has_selector: self.at_root_has_selector,
-                        extender: self.extender,
-                        content_scopes: self.content_scopes,
-                        options: self.options,
-                        modules: self.modules,
-                        module_config: config,
-                    }
-                    .parse()?;
+                    // members declared in the module itself take precedence
+                    // over those it forwards
+                    forwarded.merge(global_scope);
 
-                    if !config.is_empty() {
-                        return Err((
-                            "This variable was not declared with !default in the @used module.",
-                            self.span_before,
-                        )
-                            .into());
-                    }
-
-                    (Module::new_from_scope(global_scope, false), stmts)
+                    (Module::new_from_scope(forwarded, false), stmts)
                 } else {
                     return Err(("Can't find stylesheet to import.", self.span_before).into());
                 }
@@ -152,6 +303,136 @@ impl<'a> Parser<'a> {
         })
     }
 
+    /// Parses and evaluates an `@forward` rule, assuming the `@forward` has
+    /// already been consumed
+    ///
+    /// Returns any CSS emitted by the forwarded module
+    fn parse_forward_rule(&mut self) -> SassResult<Vec<Stmt>> {
+        self.whitespace_or_comment();
+
+        let quote = match self.toks.next() {
+            Some(Token { kind: q @ '"', .. }) | Some(Token { kind: q @ '\'', .. }) => q,
+            Some(Token { pos, .. }) => return Err(("Expected string.", pos).into()),
+            None => return Err(("Expected string.", self.span_before).into()),
+        };
+
+        let Spanned { node: module, span } = self.parse_quoted_string(quote)?;
+        let module_name = module.unquote().to_css_string(span)?;
+
+        self.whitespace_or_comment();
+
+        let prefix = self.parse_forward_prefix()?.unwrap_or_default();
+
+        self.whitespace_or_comment();
+
+        let visibility = self.parse_forward_visibility()?;
+
+        self.whitespace_or_comment();
+
+        let (mut config, mut default_config) = self.parse_configuration(true)?;
+
+        self.whitespace_or_comment();
+        self.expect_char(';')?;
+
+        let prefixed = |name: Identifier| -> Identifier {
+            if prefix.is_empty() {
+                name
+            } else {
+                format!("{}{}", prefix, name).into()
+            }
+        };
+
+        // configuration passed to this module which targets members it forwards
+        // flows through to the forwarded module, unless overridden by a
+        // variable in `with` without `!default`
+        let mut passed_through = BTreeSet::new();
+
+        for (name, value) in self.module_config.remove_matching(|name| {
+            name.as_str().starts_with(prefix.as_str()) && visibility.is_var_visible(name)
+        }) {
+            let unprefixed: Identifier = name.as_str()[prefix.len()..].into();
+            if config.contains(unprefixed) {
+                continue;
+            }
+            config.insert(
+                Spanned {
+                    node: unprefixed,
+                    span,
+                },
+                Spanned { node: value, span },
+            )?;
+            passed_through.insert(unprefixed);
+        }
+
+        for (name, value) in default_config.remove_matching(|name| !config.contains(name)) {
+            config.insert(Spanned { node: name, span }, Spanned { node: value, span })?;
+        }
+
+        self.span_before = span;
+
+        let (module, stmts) =
+            self.load_module_with_unused_config(module_name.as_ref(), "@forward", &mut config)?;
+
+        if !config.is_empty() {
+            if module.is_builtin {
+                return Err(("Built-in modules can't be configured.", span).into());
+            }
+
+            for (name, value) in config.remove_matching(|_| true) {
+                if passed_through.contains(&name) {
+                    // the downstream module may still declare this variable itself
+                    self.module_config.insert(
+                        Spanned {
+                            node: prefixed(name),
+                            span,
+                        },
+                        Spanned { node: value, span },
+                    )?;
+                } else {
+                    return Err((
+                        "This variable was not declared with !default in the @used module.",
+                        span,
+                    )
+                        .into());
+                }
+            }
+        }
+
+        // private members stay private regardless of the prefix they're
+        // forwarded with
+        for (name, value) in module.scope.vars {
+            if name.as_str().starts_with('-') {
+                continue;
+            }
+            let name = prefixed(name);
+            if visibility.is_var_visible(name) {
+                self.forwarded.insert_var(name, value);
+            }
+        }
+
+        for (name, mixin) in module.scope.mixins {
+            if name.as_str().starts_with('-') {
+                continue;
+            }
+            let name = prefixed(name);
+            if visibility.is_member_visible(name) {
+                self.forwarded.insert_mixin(name, mixin);
+            }
+        }
+
+        for (name, function) in module.scope.functions {
+            if name.as_str().starts_with('-') {
+                continue;
+            }
+            let name = prefixed(name);
+            if visibility.is_member_visible(name) {
+                self.forwarded.insert_fn(name, function);
+            }
+        }
+
+        Ok(stmts)
+    }
+
     /// Returns any multiline comments that may have been found
     /// while loading modules
     pub(super) fn load_modules(&mut self) -> SassResult<Vec<Stmt>> {
@@ -177,6 +458,11 @@ impl<'a> Parser<'a> {
                         AtRuleKind::Use => {
                             self.toks.truncate_iterator_to_cursor();
                         }
+                        AtRuleKind::Forward => {
+                            self.toks.truncate_iterator_to_cursor();
+                            comments.append(&mut self.parse_forward_rule()?);
+                            continue;
+                        }
                         _ => {
                             break;
                         }
@@ -203,8 +489,10 @@ impl<'a> Parser<'a> {
                     self.whitespace_or_comment();
                     self.expect_char(';')?;
 
+                    self.span_before = span;
+
                     let (module, mut stmts) =
-                        self.load_module(module_name.as_ref(), &mut config)?;
+                        self.load_module(module_name.as_ref(), "@use", &mut config)?;
 
                     comments.append(&mut stmts);
 
@@ -220,16 +508,7 @@ impl<'a> Parser<'a> {
                             continue;
                         }
                         Some(..) => module_alias.unwrap(),
-                        None => match module_name.as_ref() {
-                            "sass:color" => "color".to_owned(),
-                            "sass:list" => "list".to_owned(),
-                            "sass:map" => "map".to_owned(),
-                            "sass:math" => "math".to_owned(),
-                            "sass:meta" => "meta".to_owned(),
-                            "sass:selector" => "selector".to_owned(),
-                            "sass:string" => "string".to_owned(),
-                            _ => module_name.into_owned(),
-                        },
+                        None => default_namespace(&module_name),
                     };
 
                     self.modules.insert(module_name.into(), module, span)?;
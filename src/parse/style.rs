@@ -108,7 +108,10 @@ impl<'a> Parser<'a> {
             }
         }
 
-        let mut property = self.parse_identifier()?.node;
+        let Spanned {
+            node: mut property,
+            span: property_span,
+        } = self.parse_identifier()?;
         let whitespace_after_property = self.whitespace_or_comment();
 
         match self.toks.peek() {
@@ -131,7 +134,10 @@ impl<'a> Parser<'a> {
                                 if let Ok(val) = self.parse_value_from_vec(toks, false) {
                                     self.toks.take(len).for_each(drop);
                                     return Ok(SelectorOrStyle::Style(
-                                        InternedString::get_or_intern(property),
+                                        Spanned {
+                                            node: InternedString::get_or_intern(property),
+                                            span: property_span,
+                                        },
                                         Some(Box::new(val)),
                                     ));
                                 }
@@ -143,7 +149,13 @@ impl<'a> Parser<'a> {
                             property.push(':');
                             return Ok(SelectorOrStyle::Selector(property));
                         }
-                        _ => SelectorOrStyle::Style(InternedString::get_or_intern(property), None),
+                        _ => SelectorOrStyle::Style(
+                            Spanned {
+                                node: InternedString::get_or_intern(property),
+                                span: property_span,
+                            },
+                            None,
+                        ),
                     });
                 }
             }
@@ -171,7 +183,10 @@ impl<'a> Parser<'a> {
         Err(("expected \"{\".", self.span_before).into())
     }
 
-    fn parse_property(&mut self, mut super_property: String) -> SassResult<String> {
+    fn parse_property(
+        &mut self,
+        mut super_property: String,
+    ) -> SassResult<Spanned<InternedString>> {
         let property = self.parse_identifier()?;
         self.whitespace_or_comment();
         if let Some(Token { kind: ':', .. }) = self.toks.peek() {
@@ -182,12 +197,15 @@ impl<'a> Parser<'a> {
         }
 
         if super_property.is_empty() {
-            Ok(property.node)
+            Ok(property.map_node(InternedString::get_or_intern))
         } else {
             super_property.reserve(1 + property.node.len());
             super_property.push('-');
             super_property.push_str(&property.node);
-            Ok(super_property)
+            Ok(Spanned {
+                node: InternedString::get_or_intern(super_property),
+                span: property.span,
+            })
         }
     }
 
@@ -197,7 +215,7 @@ impl<'a> Parser<'a> {
 
     pub(super) fn parse_style_group(
         &mut self,
-        super_property: InternedString,
+        super_property: Spanned<InternedString>,
     ) -> SassResult<Vec<Style>> {
         let mut styles = Vec::new();
         self.whitespace();
@@ -207,9 +225,7 @@ impl<'a> Parser<'a> {
                     self.toks.next();
                     self.whitespace();
                     loop {
-                        let property = InternedString::get_or_intern(
-                            self.parse_property(super_property.resolve())?,
-                        );
+                        let property = self.parse_property(super_property.node.resolve())?;
                         if let Some(tok) = self.toks.peek() {
                             if tok.kind == '{' {
                                 styles.append(&mut self.parse_style_group(property)?);
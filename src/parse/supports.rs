@@ -0,0 +1,211 @@
+use crate::{
+    atrule::{SupportsCondition, SupportsOperator},
+    error::SassResult,
+    utils::{is_name_start, read_until_closing_paren},
+    Token,
+};
+
+use super::Parser;
+
+impl<'a> Parser<'a> {
+    /// Parse the condition of an `@supports` rule, evaluating any Sass
+    /// expressions within it
+    pub(super) fn parse_supports_condition(&mut self) -> SassResult<SupportsCondition> {
+        if self.scan_identifier("not")? {
+            self.whitespace_or_comment();
+            return Ok(SupportsCondition::Negation(Box::new(
+                self.parse_supports_condition_in_parens()?,
+            )));
+        }
+
+        let mut condition = self.parse_supports_condition_in_parens()?;
+        self.whitespace_or_comment();
+
+        let mut operator = None;
+        while matches!(self.toks.peek(), Some(tok) if is_name_start(tok.kind)) {
+            // `and` and `or` may not be mixed without parentheses
+            let next_operator = match operator {
+                Some(SupportsOperator::And) => {
+                    self.expect_identifier("and")?;
+                    SupportsOperator::And
+                }
+                Some(SupportsOperator::Or) => {
+                    self.expect_identifier("or")?;
+                    SupportsOperator::Or
+                }
+                None if self.scan_identifier("or")? => SupportsOperator::Or,
+                None => {
+                    self.expect_identifier("and")?;
+                    SupportsOperator::And
+                }
+            };
+            operator = Some(next_operator);
+
+            self.whitespace_or_comment();
+            let right = self.parse_supports_condition_in_parens()?;
+            condition = SupportsCondition::Operation {
+                left: Box::new(condition),
+                operator: next_operator,
+                right: Box::new(right),
+            };
+            self.whitespace_or_comment();
+        }
+
+        Ok(condition)
+    }
+
+    fn parse_supports_condition_in_parens(&mut self) -> SassResult<SupportsCondition> {
+        match self.toks.peek() {
+            Some(Token { kind: '#', .. }) => {
+                self.toks.next();
+                self.expect_char('{')?;
+                let interpolation = self.parse_interpolation_as_string()?.into_owned();
+                if self.consume_char_if_exists('(') {
+                    return self.parse_supports_function(interpolation);
+                }
+                return Ok(SupportsCondition::Interpolation(interpolation));
+            }
+            Some(Token { kind, .. }) if is_name_start(*kind) || *kind == '-' => {
+                let name = self.parse_identifier()?;
+                if name.node.eq_ignore_ascii_case("not") {
+                    return Err(("\"not\" is not a valid identifier here.", name.span).into());
+                }
+                if !self.consume_char_if_exists('(') {
+                    return Err(("Expected @supports condition.", name.span).into());
+                }
+                return self.parse_supports_function(name.node);
+            }
+            _ => {}
+        }
+
+        self.expect_char('(')?;
+        self.whitespace_or_comment();
+
+        if self.scan_identifier("not")? {
+            self.whitespace_or_comment();
+            let condition = self.parse_supports_condition_in_parens()?;
+            self.whitespace_or_comment();
+            self.expect_char(')')?;
+            return Ok(SupportsCondition::Negation(Box::new(condition)));
+        }
+
+        if let Some(Token { kind: '(', .. }) = self.toks.peek() {
+            let condition = self.parse_supports_condition()?;
+            self.whitespace_or_comment();
+            self.expect_char(')')?;
+            return Ok(condition);
+        }
+
+        let mut contents = self.read_supports_parens()?;
+
+        match top_level_colon(&contents) {
+            Some(idx) => {
+                let value = contents.split_off(idx + 1);
+                contents.pop();
+                self.parse_supports_declaration(contents, value)
+            }
+            None => Ok(SupportsCondition::Anything(
+                self.with_toks(contents, |parser| parser.parse_supports_raw_value())?
+                    .trim()
+                    .to_owned(),
+            )),
+        }
+    }
+
+    /// Parse the arguments to a function such as `selector()`, whose opening
+    /// parenthesis has already been consumed
+    fn parse_supports_function(&mut self, name: String) -> SassResult<SupportsCondition> {
+        let args = self.read_supports_parens()?;
+        let args = self.with_toks(args, |parser| parser.parse_supports_raw_value())?;
+        Ok(SupportsCondition::Function {
+            name,
+            args: args.trim().to_owned(),
+        })
+    }
+
+    fn parse_supports_declaration(
+        &mut self,
+        name: Vec<Token>,
+        value: Vec<Token>,
+    ) -> SassResult<SupportsCondition> {
+        // the values of custom properties aren't SassScript
+        let is_custom_property = matches!(
+            name.as_slice(),
+            [Token { kind: '-', .. }, Token { kind: '-', .. }, ..]
+        );
+
+        let name = self.parse_value_from_vec(name, false)?;
+        let name = name.node.to_css_string(name.span)?.into_owned();
+
+        let value = if is_custom_property {
+            self.with_toks(value, |parser| parser.parse_supports_raw_value())?
+                .trim_end()
+                .to_owned()
+        } else {
+            let value = self.parse_value_from_vec(value, false)?;
+            value.node.to_css_string(value.span)?.into_owned()
+        };
+
+        Ok(SupportsCondition::Declaration {
+            name,
+            value,
+            is_custom_property,
+        })
+    }
+
+    /// Consume the tokens up to and including the next unmatched closing
+    /// parenthesis, returning all but that parenthesis
+    fn read_supports_parens(&mut self) -> SassResult<Vec<Token>> {
+        let mut toks = read_until_closing_paren(self.toks)?;
+        match toks.pop() {
+            Some(Token { kind: ')', .. }) => Ok(toks),
+            Some(Token { pos, .. }) => Err(("expected \")\".", pos).into()),
+            None => Err(("expected \")\".", self.span_before).into()),
+        }
+    }
+
+    /// Resolve the interpolation in the remaining tokens, which are otherwise
+    /// emitted as written, with runs of whitespace collapsed
+    fn parse_supports_raw_value(&mut self) -> SassResult<String> {
+        let mut buf = String::new();
+        while let Some(tok) = self.toks.next() {
+            match tok.kind {
+                '#' if matches!(self.toks.peek(), Some(Token { kind: '{', .. })) => {
+                    self.toks.next();
+                    buf.push_str(&self.parse_interpolation_as_string()?);
+                }
+                ' ' | '\t' | '\n' => {
+                    self.whitespace();
+                    buf.push(' ');
+                }
+                c => buf.push(c),
+            }
+        }
+        Ok(buf)
+    }
+}
+
+/// The index of the first colon in `toks` that isn't nested within brackets
+/// or a string
+fn top_level_colon(toks: &[Token]) -> Option<usize> {
+    let mut depth = 0_usize;
+    let mut quote = None;
+    let mut is_escaped = false;
+    for (idx, tok) in toks.iter().enumerate() {
+        if is_escaped {
+            is_escaped = false;
+            continue;
+        }
+        match (tok.kind, quote) {
+            ('\\', _) => is_escaped = true,
+            (c, Some(q)) if c == q => quote = None,
+            (_, Some(..)) => {}
+            ('"', None) | ('\'', None) => quote = Some(tok.kind),
+            ('(', None) | ('[', None) | ('{', None) => depth += 1,
+            (')', None) | (']', None) | ('}', None) => depth = depth.saturating_sub(1),
+            (':', None) if depth == 0 => return Some(idx),
+            _ => {}
+        }
+    }
+    None
+}
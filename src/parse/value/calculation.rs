@@ -0,0 +1,320 @@
+use codemap::Span;
+
+use crate::{
+    common::Op,
+    error::SassResult,
+    utils::{is_name, is_name_start, read_until_closing_curly_brace, read_until_closing_paren},
+    value::{CalculationArg, CalculationName, SassCalculation, Value},
+    Token,
+};
+
+use super::super::Parser;
+
+/// A calculation as it was written, before the SassScript within it
+/// is evaluated
+#[derive(Debug)]
+pub(super) enum CalculationAst {
+    /// A number, variable, or function call
+    Expression(Vec<Token>),
+    /// The contents of `#{...}`
+    Interpolation(Vec<Token>),
+    Calculation(CalculationName, Vec<CalculationAst>),
+    Operation(Box<CalculationAst>, Op, Box<CalculationAst>),
+}
+
+impl<'a> Parser<'a> {
+    /// Parse the arguments to a calculation, up to and including the
+    /// closing parenthesis
+    ///
+    /// If this fails, `min()` and `max()` are parsed as function calls
+    /// instead, as are calculations containing syntax such as interpolation
+    /// that is only valid in their plain CSS form.
+    pub(super) fn parse_calculation_args(
+        &mut self,
+        name: CalculationName,
+    ) -> SassResult<Vec<CalculationAst>> {
+        let max_args = match name {
+            CalculationName::Calc => Some(1),
+            CalculationName::Clamp => Some(3),
+            CalculationName::Min | CalculationName::Max => None,
+        };
+
+        let mut args = Vec::new();
+        loop {
+            self.whitespace();
+            args.push(self.parse_calculation_sum()?);
+            let is_last = max_args == Some(args.len());
+            match self.toks.next() {
+                Some(Token { kind: ',', .. }) if !is_last => {}
+                Some(Token { kind: ')', .. }) => break,
+                Some(Token { pos, .. }) if is_last => {
+                    return Err(("expected \"+\", \"-\", \"*\", \"/\", or \")\".", pos).into())
+                }
+                Some(Token { pos, .. }) => {
+                    return Err(
+                        ("expected \"+\", \"-\", \"*\", \"/\", \",\", or \")\".", pos).into(),
+                    )
+                }
+                None => return Err(("expected \")\".", self.span_before).into()),
+            }
+        }
+
+        Ok(args)
+    }
+
+    /// Parse terms separated by `+` and `-`, as well as any whitespace
+    /// that follows them
+    fn parse_calculation_sum(&mut self) -> SassResult<CalculationAst> {
+        let (mut sum, mut found_whitespace) = self.parse_calculation_product()?;
+        loop {
+            let (op, span) = match self.toks.peek() {
+                Some(Token { kind: '+', pos }) => (Op::Plus, *pos),
+                Some(Token { kind: '-', pos }) => (Op::Minus, *pos),
+                _ => return Ok(sum),
+            };
+
+            // `+` and `-` must be surrounded by whitespace to distinguish
+            // them from the signs of numbers
+            self.toks.next();
+            if !found_whitespace || !self.whitespace() {
+                return Err((
+                    "\"+\" and \"-\" must be surrounded by whitespace in calculations.",
+                    span,
+                )
+                    .into());
+            }
+
+            let (right, whitespace) = self.parse_calculation_product()?;
+            sum = CalculationAst::Operation(Box::new(sum), op, Box::new(right));
+            found_whitespace = whitespace;
+        }
+    }
+
+    /// Parse terms separated by `*` and `/`, as well as any whitespace that
+    /// follows them, returning whether there was any such whitespace
+    fn parse_calculation_product(&mut self) -> SassResult<(CalculationAst, bool)> {
+        let mut product = self.parse_calculation_value()?;
+        loop {
+            let found_whitespace = self.whitespace();
+            let op = match self.toks.peek() {
+                Some(Token { kind: '*', .. }) => Op::Mul,
+                Some(Token { kind: '/', .. }) => Op::Div,
+                _ => return Ok((product, found_whitespace)),
+            };
+            self.toks.next();
+
+            // comments aren't allowed within calculations
+            if let Some(Token { kind: '*', pos }) | Some(Token { kind: '/', pos }) =
+                self.toks.peek()
+            {
+                return Err(("Comments aren't allowed in calculations.", *pos).into());
+            }
+
+            self.whitespace();
+            let right = self.parse_calculation_value()?;
+            product = CalculationAst::Operation(Box::new(product), op, Box::new(right));
+        }
+    }
+
+    fn parse_calculation_value(&mut self) -> SassResult<CalculationAst> {
+        let expected = "Expected number, variable, function, or calculation.";
+        let tok = match self.toks.peek() {
+            Some(tok) => *tok,
+            None => return Err((expected, self.span_before).into()),
+        };
+        match tok.kind {
+            '(' => {
+                self.toks.next();
+                self.whitespace();
+                let sum = self.parse_calculation_sum()?;
+                self.expect_char(')')?;
+                Ok(sum)
+            }
+            '#' => {
+                self.toks.next();
+                self.expect_char('{')?;
+                let toks = read_until_closing_curly_brace(self.toks)?;
+                self.toks.next();
+                Ok(CalculationAst::Interpolation(toks))
+            }
+            '$' => {
+                let mut toks = vec![tok];
+                self.toks.next();
+                self.eat_calculation_name(&mut toks);
+                Ok(CalculationAst::Expression(toks))
+            }
+            '+' | '-' | '.' | '0'..='9' if self.next_is_calculation_number() => {
+                Ok(CalculationAst::Expression(self.eat_calculation_number()))
+            }
+            c if is_name_start(c) || c == '-' => self.parse_calculation_function(),
+            _ => Err((expected, tok.pos).into()),
+        }
+    }
+
+    /// Parse a call to a function, which may be another calculation, or
+    /// a variable or function from a module
+    fn parse_calculation_function(&mut self) -> SassResult<CalculationAst> {
+        let mut toks = Vec::new();
+        self.eat_calculation_name(&mut toks);
+
+        let mut expected = "Expected \"(\" or \".\".";
+        if let Some(tok @ Token { kind: '.', .. }) = self.toks.peek() {
+            toks.push(*tok);
+            self.toks.next();
+            if let Some(tok @ Token { kind: '$', .. }) = self.toks.peek() {
+                toks.push(*tok);
+                self.toks.next();
+                self.eat_calculation_name(&mut toks);
+                return Ok(CalculationAst::Expression(toks));
+            }
+            self.eat_calculation_name(&mut toks);
+            expected = "expected \"(\".";
+        }
+
+        let open_paren = match self.toks.peek() {
+            Some(tok @ Token { kind: '(', .. }) => *tok,
+            Some(Token { pos, .. }) => return Err((expected, *pos).into()),
+            None => return Err((expected, self.span_before).into()),
+        };
+        self.toks.next();
+
+        let name: String = toks.iter().map(|tok| tok.kind).collect();
+        if let Some(name) = CalculationName::from_name(&name.to_ascii_lowercase()) {
+            let args = self.parse_calculation_args(name)?;
+            return Ok(CalculationAst::Calculation(name, args));
+        }
+
+        toks.push(open_paren);
+        toks.extend(read_until_closing_paren(self.toks)?);
+        Ok(CalculationAst::Expression(toks))
+    }
+
+    /// Whether the arguments to a calculation contain interpolation or
+    /// comments, which are kept as they are in a plain CSS function
+    pub(super) fn has_special_calculation_syntax(toks: &[Token]) -> bool {
+        toks.windows(2).any(|pair| {
+            matches!(
+                (pair[0].kind, pair[1].kind),
+                ('#', '{') | ('/', '*') | ('/', '/')
+            )
+        })
+    }
+
+    fn eat_calculation_name(&mut self, toks: &mut Vec<Token>) {
+        while let Some(tok) = self.toks.peek() {
+            if !is_name(tok.kind) {
+                break;
+            }
+            toks.push(*tok);
+            self.toks.next();
+        }
+    }
+
+    fn next_is_calculation_number(&mut self) -> bool {
+        let mut next = match self.toks.peek() {
+            Some(tok) => tok.kind,
+            None => return false,
+        };
+        let mut offset = 0;
+        if next == '+' || next == '-' {
+            offset += 1;
+            next = match self.toks.peek_nth(offset) {
+                Some(tok) => tok.kind,
+                None => return false,
+            };
+        }
+        if next == '.' {
+            offset += 1;
+            next = match self.toks.peek_nth(offset) {
+                Some(tok) => tok.kind,
+                None => return false,
+            };
+        }
+        next.is_ascii_digit()
+    }
+
+    /// Consume a number along with its sign and unit
+    fn eat_calculation_number(&mut self) -> Vec<Token> {
+        let mut toks = Vec::new();
+        if let Some(tok @ Token { kind: '+', .. }) | Some(tok @ Token { kind: '-', .. }) =
+            self.toks.peek()
+        {
+            toks.push(*tok);
+            self.toks.next();
+        }
+
+        while let Some(tok) = self.toks.peek() {
+            if !tok.kind.is_ascii_digit() && tok.kind != '.' {
+                break;
+            }
+            toks.push(*tok);
+            self.toks.next();
+        }
+
+        match self.toks.peek() {
+            Some(tok @ Token { kind: '%', .. }) => {
+                toks.push(*tok);
+                self.toks.next();
+            }
+            Some(tok) if is_name_start(tok.kind) && !tok.kind.is_ascii_digit() => {
+                self.eat_calculation_name(&mut toks);
+            }
+            _ => {}
+        }
+
+        toks
+    }
+
+    /// Evaluate a calculation, which is simplified to a number if possible
+    pub(super) fn eval_calculation(
+        &mut self,
+        name: CalculationName,
+        args: Vec<CalculationAst>,
+        span: Span,
+    ) -> SassResult<Value> {
+        let mut args = args
+            .into_iter()
+            .map(|arg| self.eval_calculation_arg(arg, span))
+            .collect::<SassResult<Vec<CalculationArg>>>()?;
+
+        match name {
+            CalculationName::Calc => Ok(SassCalculation::calc(args.remove(0))),
+            CalculationName::Min | CalculationName::Max => {
+                SassCalculation::min_max(name, args, span)
+            }
+            CalculationName::Clamp => {
+                let mut args = args.into_iter();
+                let min = args.next().unwrap();
+                SassCalculation::clamp(min, args.next(), args.next(), span)
+            }
+        }
+    }
+
+    fn eval_calculation_arg(
+        &mut self,
+        arg: CalculationAst,
+        span: Span,
+    ) -> SassResult<CalculationArg> {
+        match arg {
+            CalculationAst::Expression(toks) => {
+                let value = self.parse_value_from_vec(toks, false)?;
+                CalculationArg::from_value(value.node, value.span)
+            }
+            CalculationAst::Interpolation(toks) => {
+                let value = self.parse_value_from_vec(toks, true)?;
+                Ok(CalculationArg::Interpolation(match value.node.unquote() {
+                    Value::String(s, ..) => s,
+                    v => v.to_css_string(value.span)?.into_owned(),
+                }))
+            }
+            CalculationAst::Calculation(name, args) => {
+                CalculationArg::from_value(self.eval_calculation(name, args, span)?, span)
+            }
+            CalculationAst::Operation(left, op, right) => {
+                let left = self.eval_calculation_arg(*left, span)?;
+                let right = self.eval_calculation_arg(*right, span)?;
+                CalculationArg::operate(op, left, right, span)
+            }
+        }
+    }
+}
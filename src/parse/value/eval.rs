@@ -3,14 +3,14 @@
 use std::cmp::Ordering;
 
 use codemap::{Span, Spanned};
-use num_traits::Zero;
+use num_traits::{One, Zero};
 
 use crate::{
     args::CallArgs,
     common::{Op, QuoteKind},
     error::SassResult,
-    unit::Unit,
-    value::{SassFunction, Value},
+    unit::{div_units, mul_units, Unit},
+    value::{Division, Number, SassFunction, Value},
 };
 
 use super::super::Parser;
@@ -28,6 +28,33 @@ impl HigherIntermediateValue {
     pub const fn span(self, span: Span) -> Spanned<Self> {
         Spanned { node: self, span }
     }
+
+    /// Whether `/` may separate this value from another rather than divide it
+    fn allows_slash(&self) -> bool {
+        match self {
+            Self::UnaryOp(Op::Minus, val) | Self::UnaryOp(Op::Plus, val) => val.allows_slash(),
+            Self::Literal(Value::Dimension(_, _, Division::Computed))
+            | Self::Function(..)
+            | Self::UnaryOp(..) => false,
+            Self::Literal(..) | Self::BinaryOp(..) => true,
+        }
+    }
+}
+
+/// Divide one number by another, as both the `/` operator and
+/// `math.div()` do
+pub(crate) fn divide(num: Option<Number>, unit: Unit, num2: Option<Number>, unit2: Unit) -> Value {
+    match (num, num2) {
+        // `0 / 0` is NaN, which keeps the units of the division
+        (Some(num), Some(num2)) if !(num.is_zero() && num2.is_zero()) => {
+            let (num, unit) = div_units(num, unit, num2, unit2);
+            Value::Dimension(Some(num), unit, Division::Computed)
+        }
+        _ => {
+            let (_, unit) = div_units(Number::one(), unit, Number::one(), unit2);
+            Value::Dimension(None, unit, Division::Computed)
+        }
+    }
 }
 
 impl<'a> Parser<'a> {
@@ -48,8 +75,8 @@ impl<'a, 'b: 'a> ValueVisitor<'a, 'b> {
 
     pub fn eval(&mut self, value: HigherIntermediateValue, in_parens: bool) -> SassResult<Value> {
         match value {
-            HigherIntermediateValue::Literal(Value::Dimension(n, u, _)) if in_parens => {
-                Ok(Value::Dimension(n, u, true))
+            HigherIntermediateValue::Literal(v @ Value::Dimension(..)) if in_parens => {
+                self.parser.without_slash(v, self.span)
             }
             HigherIntermediateValue::Literal(v) => Ok(v),
             HigherIntermediateValue::BinaryOp(v1, op, v2) => self.bin_op(*v1, op, *v2, in_parens),
@@ -67,8 +94,11 @@ impl<'a, 'b: 'a> ValueVisitor<'a, 'b> {
         val2: HigherIntermediateValue,
         in_parens: bool,
     ) -> SassResult<Value> {
+        // `/` only separates numbers that are written out, not the results
+        // of functions or other operations
+        let allows_slash = val1.allows_slash() && val2.allows_slash();
         let mut val1 = self.unary(val1, in_parens)?;
-        let val2 = self.unary(val2, in_parens)?;
+        let mut val2 = self.unary(val2, in_parens)?;
 
         if let HigherIntermediateValue::BinaryOp(val1_1, op2, val1_2) = val1 {
             let in_parens = op != Op::Div || op2 != Op::Div;
@@ -83,11 +113,26 @@ impl<'a, 'b: 'a> ValueVisitor<'a, 'b> {
             }
         }
 
+        if matches!(
+            op,
+            Op::Plus
+                | Op::Minus
+                | Op::Mul
+                | Op::Rem
+                | Op::GreaterThan
+                | Op::GreaterThanEqual
+                | Op::LessThan
+                | Op::LessThanEqual
+        ) {
+            val1 = self.without_slash(val1)?;
+            val2 = self.without_slash(val2)?;
+        }
+
         Ok(match op {
             Op::Plus => self.add(val1, val2)?,
             Op::Minus => self.sub(val1, val2)?,
             Op::Mul => self.mul(val1, val2)?,
-            Op::Div => self.div(val1, val2, in_parens)?,
+            Op::Div => self.div(val1, val2, in_parens || !allows_slash)?,
             Op::Rem => self.rem(val1, val2)?,
             Op::And => Self::and(val1, val2)?,
             Op::Or => Self::or(val1, val2)?,
@@ -117,12 +162,16 @@ impl<'a, 'b: 'a> ValueVisitor<'a, 'b> {
     }
 
     fn unary_minus(&self, val: Value) -> SassResult<Value> {
-        Ok(match val {
-            Value::Dimension(Some(n), u, should_divide) => {
-                Value::Dimension(Some(-n), u, should_divide)
+        let val = match val {
+            v @ Value::Dimension(_, _, Division::Slash(..)) => {
+                self.parser.without_slash(v, self.span)?
             }
+            v => v,
+        };
+        Ok(match val {
+            Value::Dimension(Some(n), u, division) => Value::Dimension(Some(-n), u, division),
             // todo: NaN test
-            Value::Dimension(None, u, should_divide) => Value::Dimension(None, u, should_divide),
+            Value::Dimension(None, u, division) => Value::Dimension(None, u, division),
             v => Value::String(format!("-{}", v.to_css_string(self.span)?), QuoteKind::None),
         })
     }
@@ -138,6 +187,16 @@ impl<'a, 'b: 'a> ValueVisitor<'a, 'b> {
         Ok(Value::bool(!val.is_true()))
     }
 
+    /// Divide a number that `/` separated, now that it is used in arithmetic
+    fn without_slash(&self, val: HigherIntermediateValue) -> SassResult<HigherIntermediateValue> {
+        Ok(match val {
+            HigherIntermediateValue::Literal(v) => {
+                HigherIntermediateValue::Literal(self.parser.without_slash(v, self.span)?)
+            }
+            val => val,
+        })
+    }
+
     fn unary(
         &mut self,
         val: HigherIntermediateValue,
@@ -175,6 +234,22 @@ impl<'a, 'b: 'a> ValueVisitor<'a, 'b> {
                 )
                     .into())
             }
+            Value::Calculation(..) => match right {
+                Value::String(s, q) => {
+                    Value::String(format!("{}{}", left.to_css_string(self.span)?, s), q)
+                }
+                _ => {
+                    return Err((
+                        format!(
+                            "Undefined operation \"{} + {}\".",
+                            left.inspect(self.span)?,
+                            right.inspect(self.span)?
+                        ),
+                        self.span,
+                    )
+                        .into())
+                }
+            },
             Value::Important | Value::True | Value::False => match right {
                 Value::String(s, QuoteKind::Quoted) => Value::String(
                     format!("{}{}", left.to_css_string(self.span)?, s),
@@ -211,13 +286,17 @@ impl<'a, 'b: 'a> ValueVisitor<'a, 'b> {
                             .into());
                     }
                     if unit == unit2 {
-                        Value::Dimension(Some(num + num2), unit, true)
+                        Value::Dimension(Some(num + num2), unit, Division::Computed)
                     } else if unit == Unit::None {
-                        Value::Dimension(Some(num + num2), unit2, true)
+                        Value::Dimension(Some(num + num2), unit2, Division::Computed)
                     } else if unit2 == Unit::None {
-                        Value::Dimension(Some(num + num2), unit, true)
+                        Value::Dimension(Some(num + num2), unit, Division::Computed)
                     } else {
-                        Value::Dimension(Some(num + num2.convert(&unit2, &unit)), unit, true)
+                        Value::Dimension(
+                            Some(num + num2.convert(&unit2, &unit)),
+                            unit,
+                            Division::Computed,
+                        )
                     }
                 }
                 Value::String(s, q) => Value::String(format!("{}{}{}", num, unit, s), q),
@@ -315,13 +394,17 @@ impl<'a, 'b: 'a> ValueVisitor<'a, 'b> {
                             .into());
                     }
                     if unit == unit2 {
-                        Value::Dimension(Some(num - num2), unit, true)
+                        Value::Dimension(Some(num - num2), unit, Division::Computed)
                     } else if unit == Unit::None {
-                        Value::Dimension(Some(num - num2), unit2, true)
+                        Value::Dimension(Some(num - num2), unit2, Division::Computed)
                     } else if unit2 == Unit::None {
-                        Value::Dimension(Some(num - num2), unit, true)
+                        Value::Dimension(Some(num - num2), unit, Division::Computed)
                     } else {
-                        Value::Dimension(Some(num - num2.convert(&unit2, &unit)), unit, true)
+                        Value::Dimension(
+                            Some(num - num2.convert(&unit2, &unit)),
+                            unit,
+                            Division::Computed,
+                        )
                     }
                 }
                 Value::List(..)
@@ -340,7 +423,7 @@ impl<'a, 'b: 'a> ValueVisitor<'a, 'b> {
                     )
                         .into())
                 }
-                Value::Color(..) => {
+                Value::Color(..) | Value::Calculation(..) => {
                     return Err((
                         format!(
                             "Undefined operation \"{}{} - {}\".",
@@ -383,6 +466,17 @@ impl<'a, 'b: 'a> ValueVisitor<'a, 'b> {
                 ),
                 QuoteKind::None,
             ),
+            Value::Calculation(..) => {
+                return Err((
+                    format!(
+                        "Undefined operation \"{} - {}\".",
+                        left.inspect(self.span)?,
+                        right.inspect(self.span)?
+                    ),
+                    self.span,
+                )
+                    .into())
+            }
             _ => match right {
                 Value::String(s, q) => Value::String(
                     format!("{}-{}{}{}", left.to_css_string(self.span)?, q, s, q),
@@ -422,13 +516,8 @@ impl<'a, 'b: 'a> ValueVisitor<'a, 'b> {
             Value::Dimension(Some(num), unit, _) => match right {
                 Value::Dimension(None, ..) => todo!(),
                 Value::Dimension(Some(num2), unit2, _) => {
-                    if unit == Unit::None {
-                        Value::Dimension(Some(num * num2), unit2, true)
-                    } else if unit2 == Unit::None {
-                        Value::Dimension(Some(num * num2), unit, true)
-                    } else {
-                        Value::Dimension(Some(num * num2), unit * unit2, true)
-                    }
+                    let (num, unit) = mul_units(num, unit, num2, unit2);
+                    Value::Dimension(Some(num), unit, Division::Computed)
                 }
                 _ => {
                     return Err((
@@ -476,61 +565,69 @@ impl<'a, 'b: 'a> ValueVisitor<'a, 'b> {
                 format!("/{}", right.to_css_string(self.span)?),
                 QuoteKind::None,
             ),
-            Value::Dimension(None, ..) => todo!(),
-            Value::Dimension(Some(num), unit, should_divide1) => match right {
-                Value::Dimension(None, ..) => todo!(),
-                Value::Dimension(Some(num2), unit2, should_divide2) => {
-                    if should_divide1 || should_divide2 || in_parens {
-                        if num.is_zero() && num2.is_zero() {
-                            return Ok(Value::Dimension(None, Unit::None, true));
-                        }
-
-                        // `unit(1em / 1em)` => `""`
-                        if unit == unit2 {
-                            Value::Dimension(Some(num / num2), Unit::None, true)
-
-                        // `unit(1 / 1em)` => `"em^-1"`
-                        } else if unit == Unit::None {
-                            Value::Dimension(Some(num / num2), Unit::None / unit2, true)
-
-                        // `unit(1em / 1)` => `"em"`
-                        } else if unit2 == Unit::None {
-                            Value::Dimension(Some(num / num2), unit, true)
-
-                        // `unit(1in / 1px)` => `""`
-                        } else if unit.comparable(&unit2) {
-                            Value::Dimension(
-                                Some(num / num2.convert(&unit2, &unit)),
-                                Unit::None,
-                                true,
-                            )
-                        // `unit(1em / 1px)` => `"em/px"`
-                        // todo: this should probably be its own variant
-                        // within the `Value` enum
-                        } else {
-                            // todo: remember to account for `Mul` and `Div`
-                            todo!("non-comparable inverse units")
-                        }
+            Value::Dimension(num, unit, division1) => match right {
+                Value::Dimension(num2, unit2, division2) => {
+                    let is_slash = |division: &Division| {
+                        matches!(division, Division::Literal | Division::Slash(..))
+                    };
+                    if in_parens || !is_slash(&division1) || !is_slash(&division2) {
+                        let left = self.parser.without_slash(
+                            Value::Dimension(num.clone(), unit.clone(), division1),
+                            self.span,
+                        )?;
+                        let right = self.parser.without_slash(
+                            Value::Dimension(num2.clone(), unit2.clone(), division2),
+                            self.span,
+                        )?;
+                        self.parser
+                            .slash_division_warning(&left, &right, self.span)?;
+                        divide(num, unit, num2, unit2)
                     } else {
-                        Value::String(
-                            format!("{}{}/{}{}", num, unit, num2, unit2),
-                            QuoteKind::None,
-                        )
+                        // not division, but a slash separating two numbers,
+                        // such as in `font: 12px/30px`
+                        let operands = (
+                            Value::Dimension(num.clone(), unit.clone(), division1),
+                            Value::Dimension(num2.clone(), unit2.clone(), division2),
+                        );
+                        match divide(num, unit, num2, unit2) {
+                            Value::Dimension(num, unit, _) => {
+                                Value::Dimension(num, unit, Division::Slash(Box::new(operands)))
+                            }
+                            v => v,
+                        }
                     }
                 }
-                Value::String(s, q) => {
-                    Value::String(format!("{}{}/{}{}{}", num, unit, q, s, q), QuoteKind::None)
-                }
+                Value::String(s, q) => Value::String(
+                    format!(
+                        "{}/{}{}{}",
+                        Value::Dimension(num, unit, division1).to_css_string(self.span)?,
+                        q,
+                        s,
+                        q
+                    ),
+                    QuoteKind::None,
+                ),
                 Value::List(..)
                 | Value::True
                 | Value::False
                 | Value::Important
                 | Value::Color(..)
-                | Value::ArgList(..) => Value::String(
-                    format!("{}{}/{}", num, unit, right.to_css_string(self.span)?),
+                | Value::ArgList(..)
+                | Value::Calculation(..) => Value::String(
+                    format!(
+                        "{}/{}",
+                        Value::Dimension(num, unit, division1).to_css_string(self.span)?,
+                        right.to_css_string(self.span)?
+                    ),
+                    QuoteKind::None,
+                ),
+                Value::Null => Value::String(
+                    format!(
+                        "{}/",
+                        Value::Dimension(num, unit, division1).to_css_string(self.span)?
+                    ),
                     QuoteKind::None,
                 ),
-                Value::Null => Value::String(format!("{}{}/", num, unit), QuoteKind::None),
                 Value::Map(..) | Value::FunctionRef(..) => {
                     return Err((
                         format!("{} isn't a valid CSS value.", right.inspect(self.span)?),
@@ -571,7 +668,8 @@ impl<'a, 'b: 'a> ValueVisitor<'a, 'b> {
                 | Value::Dimension(..)
                 | Value::Color(..)
                 | Value::List(..)
-                | Value::ArgList(..) => Value::String(
+                | Value::ArgList(..)
+                | Value::Calculation(..) => Value::String(
                     format!("{}{}{}/{}", q1, s1, q1, right.to_css_string(self.span)?),
                     QuoteKind::None,
                 ),
@@ -629,20 +727,20 @@ impl<'a, 'b: 'a> ValueVisitor<'a, 'b> {
                         );
                     }
                     if u == u2 {
-                        Value::Dimension(Some(n % n2), u, true)
+                        Value::Dimension(Some(n % n2), u, Division::Computed)
                     } else if u == Unit::None {
-                        Value::Dimension(Some(n % n2), u2, true)
+                        Value::Dimension(Some(n % n2), u2, Division::Computed)
                     } else if u2 == Unit::None {
-                        Value::Dimension(Some(n % n2), u, true)
+                        Value::Dimension(Some(n % n2), u, Division::Computed)
                     } else {
-                        Value::Dimension(Some(n), u, true)
+                        Value::Dimension(Some(n), u, Division::Computed)
                     }
                 }
                 _ => {
                     return Err((
                         format!(
                             "Undefined operation \"{} % {}\".",
-                            Value::Dimension(Some(n), u, true).inspect(self.span)?,
+                            Value::Dimension(Some(n), u, Division::Computed).inspect(self.span)?,
                             right.inspect(self.span)?
                         ),
                         self.span,
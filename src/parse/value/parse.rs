@@ -14,8 +14,8 @@ use crate::{
     common::{unvendor, Brackets, Identifier, ListSeparator, Op, QuoteKind},
     error::SassResult,
     unit::Unit,
-    utils::{eat_whole_number, is_name, IsWhitespace, ParsedNumber},
-    value::{Number, SassFunction, SassMap, Value},
+    utils::{eat_whole_number, is_name, read_until_closing_paren, IsWhitespace, ParsedNumber},
+    value::{CalculationName, Division, Number, SassFunction, SassMap, Value},
     Token,
 };
 
@@ -46,6 +46,18 @@ impl IsWhitespace for IntermediateValue {
     }
 }
 
+/// Whether `value` is written as a literal number or a special function
+/// such as `var()`, so that `/` between two of them is a separator
+fn is_slash_operand(value: &HigherIntermediateValue) -> bool {
+    match value {
+        HigherIntermediateValue::Literal(Value::Dimension(_, _, division)) => {
+            matches!(division, Division::Literal | Division::Slash(..))
+        }
+        HigherIntermediateValue::Literal(v) => v.is_special_function(),
+        _ => false,
+    }
+}
+
 impl<'a> Parser<'a> {
     /// Parse a value from a stream of tokens
     ///
@@ -54,6 +66,18 @@ impl<'a> Parser<'a> {
         &mut self,
         in_paren: bool,
         predicate: &dyn Fn(&mut PeekMoreIterator<IntoIter<Token>>) -> bool,
+    ) -> SassResult<Spanned<Value>> {
+        self.parse_value_with_slash(in_paren, false, predicate)
+    }
+
+    /// Parse a value, keeping a trailing `a / b` in a space-separated
+    /// list as a slash-separated list if `keep_slash` is true, e.g.
+    /// `50% 40 59.5 / 0.5` becomes `list.slash(50% 40 59.5, 0.5)`
+    pub(crate) fn parse_value_with_slash(
+        &mut self,
+        in_paren: bool,
+        keep_slash: bool,
+        predicate: &dyn Fn(&mut PeekMoreIterator<IntoIter<Token>>) -> bool,
     ) -> SassResult<Spanned<Value>> {
         self.whitespace();
 
@@ -125,6 +149,38 @@ impl<'a> Parser<'a> {
             }
         }
 
+        let ends_with_slash = matches!(
+            space_separated.last(),
+            Some(Spanned {
+                node: HigherIntermediateValue::BinaryOp(left, Op::Div, right),
+                ..
+            }) if is_slash_operand(left) && is_slash_operand(right)
+        );
+
+        if keep_slash && ends_with_slash && comma_separated.is_empty() && space_separated.len() > 1
+        {
+            if let Some(Spanned {
+                node: HigherIntermediateValue::BinaryOp(left, _, right),
+                ..
+            }) = space_separated.pop()
+            {
+                space_separated.push((*left).span(span));
+                let before = space_separated
+                    .into_iter()
+                    .map(|a| ValueVisitor::new(self, span).eval(a.node, in_paren))
+                    .collect::<SassResult<Vec<Value>>>()?;
+                return Ok(Value::List(
+                    vec![
+                        Value::List(before, ListSeparator::Space, Brackets::None),
+                        ValueVisitor::new(self, span).eval(*right, in_paren)?,
+                    ],
+                    ListSeparator::Slash,
+                    Brackets::None,
+                )
+                .span(span));
+            }
+        }
+
         Ok(if !comma_separated.is_empty() {
             if space_separated.len() == 1 {
                 comma_separated.push(space_separated.pop().unwrap());
@@ -185,10 +241,14 @@ impl<'a> Parser<'a> {
             at_root: self.at_root,
             at_root_has_selector: self.at_root_has_selector,
             extender: self.extender,
+            media_queries: self.media_queries,
             content_scopes: self.content_scopes,
             options: self.options,
             modules: self.modules,
             module_config: self.module_config,
+            forwarded: self.forwarded,
+            call_stack: self.call_stack,
+            custom_function_args: self.custom_function_args,
         }
         .parse_value(in_paren, &|_| false)
     }
@@ -224,7 +284,8 @@ impl<'a> Parser<'a> {
                     todo!()
                 }
 
-                let call_args = self.parse_call_args()?;
+                let mut call_args = self.parse_call_args()?;
+                call_args.1 = module_span.merge(call_args.1);
 
                 HigherIntermediateValue::Function(function, call_args)
             })
@@ -259,30 +320,57 @@ impl<'a> Parser<'a> {
             Some(Token { kind: '(', .. }) => {
                 self.toks.next();
 
-                if lower == "min" || lower == "max" {
-                    match self.try_parse_min_max(&lower, true)? {
-                        Some(val) => {
-                            self.toks.truncate_iterator_to_cursor();
+                // arguments that have already been parsed, if this looked like
+                // a calculation but turned out not to be one
+                let mut parsed_args = None;
+
+                if let Some(name) = CalculationName::from_name(&lower) {
+                    let toks = read_until_closing_paren(self.toks)?;
+                    match self.with_toks(toks.clone(), |parser| parser.parse_calculation_args(name))
+                    {
+                        Ok(args) => {
+                            let span = toks.last().map_or(span, |tok| span.merge(tok.pos));
                             return Ok(IntermediateValue::Value(HigherIntermediateValue::Literal(
-                                Value::String(val, QuoteKind::None),
+                                self.eval_calculation(name, args, span)?,
                             ))
                             .span(span));
                         }
-                        None => {
-                            self.toks.reset_cursor();
-                        }
+                        // `min()` and `max()` may be calls to the Sass functions
+                        Err(..)
+                            if matches!(name, CalculationName::Min | CalculationName::Max)
+                                || Self::has_special_calculation_syntax(&toks) => {}
+                        Err(e) => return Err(e),
                     }
+
+                    if name == CalculationName::Calc {
+                        s = lower;
+                        self.with_toks(toks, |parser| parser.parse_calc_args(&mut s))?;
+                        return Ok(IntermediateValue::Value(HigherIntermediateValue::Literal(
+                            Value::String(s, QuoteKind::None),
+                        ))
+                        .span(span));
+                    }
+
+                    parsed_args = Some(self.with_toks(toks, |parser| parser.parse_call_args())?);
                 }
 
                 let as_ident = Identifier::from(&s);
                 let func = match self.scopes.get_fn(as_ident, self.global_scope) {
                     Some(f) => f,
+                    None if self.options.custom_function(as_ident).is_some() => {
+                        SassFunction::Custom(as_ident)
+                    }
                     None => {
                         if let Some(f) = GLOBAL_FUNCTIONS.get(as_ident.as_str()) {
+                            let args = match parsed_args {
+                                Some(args) => args,
+                                None if f.2 => self.parse_slash_call_args()?,
+                                None => self.parse_call_args()?,
+                            };
                             return Ok(IntermediateValue::Value(
                                 HigherIntermediateValue::Function(
                                     SassFunction::Builtin(f.clone(), as_ident),
-                                    self.parse_call_args()?,
+                                    args,
                                 ),
                             )
                             .span(span));
@@ -297,7 +385,13 @@ impl<'a> Parser<'a> {
                                     Some(val) => s = val,
                                     None => s.push_str(&self.parse_call_args()?.to_css_string()?),
                                 },
-                                _ => s.push_str(&self.parse_call_args()?.to_css_string()?),
+                                _ => {
+                                    let args = match parsed_args {
+                                        Some(args) => args,
+                                        None => self.parse_call_args()?,
+                                    };
+                                    s.push_str(&args.to_css_string()?);
+                                }
                             }
 
                             return Ok(IntermediateValue::Value(HigherIntermediateValue::Literal(
@@ -308,7 +402,13 @@ impl<'a> Parser<'a> {
                     }
                 };
 
-                let call_args = self.parse_call_args()?;
+                // the call includes the function's name, which is where
+                // stack traces point to
+                let mut call_args = match parsed_args {
+                    Some(args) => args,
+                    None => self.parse_call_args()?,
+                };
+                call_args.1 = span.merge(call_args.1);
                 return Ok(IntermediateValue::Value(HigherIntermediateValue::Function(
                     func, call_args,
                 ))
@@ -695,7 +795,7 @@ impl<'a> Parser<'a> {
                             HigherIntermediateValue::Literal(Value::Dimension(
                                 Some(Number::new_small(n)),
                                 unit,
-                                false,
+                                Division::Literal,
                             )),
                         )
                         .span(span)));
@@ -708,7 +808,7 @@ impl<'a> Parser<'a> {
                             HigherIntermediateValue::Literal(Value::Dimension(
                                 Some(Number::new_small(n)),
                                 unit,
-                                false,
+                                Division::Literal,
                             )),
                         )
                         .span(span)));
@@ -721,7 +821,7 @@ impl<'a> Parser<'a> {
                         HigherIntermediateValue::Literal(Value::Dimension(
                             Some(Number::new_big(n)),
                             unit,
-                            false,
+                            Division::Literal,
                         )),
                     )
                     .span(span)));
@@ -750,7 +850,7 @@ impl<'a> Parser<'a> {
                 IntermediateValue::Value(HigherIntermediateValue::Literal(Value::Dimension(
                     Some(Number::new_big(n * times_ten)),
                     unit,
-                    false,
+                    Division::Literal,
                 )))
                 .span(span)
             }
@@ -837,13 +937,17 @@ impl<'a> Parser<'a> {
                     Ok(v) => v.map_node(|i| i.into()),
                     Err(e) => return Some(Err(e)),
                 };
-                IntermediateValue::Value(HigherIntermediateValue::Literal(
-                    match self.scopes.get_var(val, self.global_scope) {
-                        Ok(v) => v.clone(),
-                        Err(e) => return Some(Err(e)),
-                    },
-                ))
-                .span(val.span)
+                // `/` divides variables rather than separating them
+                let value = match self
+                    .scopes
+                    .get_var(val, self.global_scope)
+                    .map(Clone::clone)
+                    .and_then(|v| self.without_slash(v, val.span))
+                {
+                    Ok(v) => v,
+                    Err(e) => return Some(Err(e)),
+                };
+                IntermediateValue::Value(HigherIntermediateValue::Literal(value)).span(val.span)
             }
             '+' => {
                 let span = self.toks.next().unwrap().pos();
@@ -7,7 +7,7 @@ use crate::{
 
 use super::Parser;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub(crate) struct VariableValue {
     pub val_toks: Vec<Token>,
     pub global: bool,
@@ -32,11 +32,22 @@ impl<'a> Parser<'a> {
 
         self.expect_char(':')?;
 
+        let value = self.parse_variable_value()?;
+
+        self.declare_variable(ident, value)
+    }
+
+    /// Evaluate the value of a variable declaration and assign it to `ident`
+    pub(super) fn declare_variable(
+        &mut self,
+        ident: Identifier,
+        value: VariableValue,
+    ) -> SassResult<()> {
         let VariableValue {
             val_toks,
             global,
             default,
-        } = self.parse_variable_value()?;
+        } = value;
 
         if default {
             let config_val = self.module_config.get(ident);
@@ -0,0 +1,149 @@
+//! # Evaluating SassScript interactively
+use std::{collections::HashMap, path::Path};
+
+use codemap::{CodeMap, Span};
+use peekmore::PeekMore;
+
+use crate::{
+    builtin::modules::{ModuleConfig, Modules},
+    common::Identifier,
+    error::SassResult,
+    lexer::tokenize,
+    parse::{
+        common::{ContextFlags, NeverEmptyVec},
+        Parser,
+    },
+    raw_to_parse_error,
+    scope::{Scope, Scopes},
+    selector::{Extender, Selector},
+    Options, Result, Token,
+};
+
+/// Evaluates SassScript one line at a time, as `grass --interactive` does
+///
+/// Each line may be an expression, a variable declaration, or a `@use`
+/// rule. Variables and modules persist between lines.
+///
+/// ```
+/// fn main() -> Result<(), Box<grass::Error>> {
+///     let options = grass::Options::default();
+///     let mut repl = grass::Repl::new(&options);
+///     assert_eq!(repl.evaluate("@use \"sass:math\"")?, None);
+///     assert_eq!(repl.evaluate("$width: 10px")?, Some("10px".to_string()));
+///     assert_eq!(repl.evaluate("math.max($width, 12px)")?, Some("12px".to_string()));
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Repl<'a> {
+    options: &'a Options<'a>,
+    map: CodeMap,
+    global_scope: Scope,
+    modules: Modules,
+}
+
+impl<'a> Repl<'a> {
+    #[must_use]
+    #[inline]
+    pub fn new(options: &'a Options<'a>) -> Self {
+        Repl {
+            options,
+            map: CodeMap::new(),
+            global_scope: options.global_scope(),
+            modules: Modules::default(),
+        }
+    }
+
+    /// Evaluate a single line, returning the resulting value as it would
+    /// be written by `inspect()`
+    ///
+    /// Variable declarations return the new value of the variable, and
+    /// `@use` rules return `None`. A trailing semicolon is optional.
+    #[inline]
+    pub fn evaluate(&mut self, line: &str) -> Result<Option<String>> {
+        let file = self.map.add_file("stdin".into(), line.to_owned());
+
+        let result = tokenize(&file, false).and_then(|toks| {
+            let span = file.span.subspan(file.span.len(), file.span.len());
+            self.evaluate_tokens(toks, line, span)
+        });
+
+        result.map_err(|e| raw_to_parse_error(&self.map, *e, self.options.unicode_error_messages))
+    }
+
+    fn evaluate_tokens(
+        &mut self,
+        mut toks: Vec<Token>,
+        line: &str,
+        end: Span,
+    ) -> SassResult<Option<String>> {
+        let variable = declared_variable(line);
+        let is_statement = variable.is_some() || line.trim_start().starts_with('@');
+
+        if is_statement && !line.trim_end().ends_with(';') {
+            toks.push(Token::new(end, ';'));
+        }
+
+        let empty_span = end.subspan(0, 0);
+
+        let mut parser = Parser {
+            toks: &mut toks.into_iter().peekmore(),
+            map: &mut self.map,
+            path: Path::new(""),
+            scopes: &mut Scopes::new(),
+            global_scope: &mut self.global_scope,
+            super_selectors: &mut NeverEmptyVec::new(Selector::new(empty_span)),
+            span_before: empty_span,
+            content: &mut Vec::new(),
+            flags: ContextFlags::empty(),
+            at_root: true,
+            at_root_has_selector: false,
+            extender: &mut Extender::new(empty_span),
+            media_queries: &mut None,
+            content_scopes: &mut Scopes::new(),
+            options: self.options,
+            modules: &mut self.modules,
+            module_config: &mut ModuleConfig::default(),
+            forwarded: &mut Scope::new(),
+            call_stack: &mut Vec::new(),
+            custom_function_args: &mut HashMap::new(),
+        };
+
+        if is_statement {
+            parser.parse()?;
+
+            return match variable {
+                Some(name) => match self.global_scope.vars.get(&name) {
+                    Some(value) => Ok(Some(value.inspect(end)?.into_owned())),
+                    None => Ok(None),
+                },
+                None => Ok(None),
+            };
+        }
+
+        parser.whitespace();
+        let value = parser.parse_value(true, &|_| false)?;
+        parser.whitespace();
+
+        if let Some(tok) = parser.toks.peek() {
+            return Err(("expected no more input.", tok.pos).into());
+        }
+
+        Ok(Some(value.node.inspect(value.span)?.into_owned()))
+    }
+}
+
+/// The name of the variable declared by `line`, if it is a declaration
+/// such as `$width: 10px`
+fn declared_variable(line: &str) -> Option<Identifier> {
+    let rest = line.trim_start().strip_prefix('$')?;
+    let end = rest
+        .find(|c: char| !(c.is_alphanumeric() || c == '-' || c == '_' || !c.is_ascii()))
+        .unwrap_or(rest.len());
+
+    if end == 0 || !rest[end..].trim_start().starts_with(':') {
+        return None;
+    }
+
+    Some(Identifier::from(&rest[..end]))
+}
@@ -0,0 +1,98 @@
+use crate::{color::Color, value::Number};
+
+/// A Sass color
+///
+/// Colors keep the representation they were written with, so a
+/// color such as `red` passed to a custom function and returned
+/// unchanged is written as `red` rather than `#ff0000`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SassColor(pub(super) Box<Color>);
+
+impl SassColor {
+    /// A color from red, green, and blue channels between `0` and `255`
+    /// and an alpha channel between `0` and `1`
+    ///
+    /// Channels outside of these ranges are clamped.
+    #[must_use]
+    #[inline]
+    pub fn rgb(red: f64, green: f64, blue: f64, alpha: f64) -> Self {
+        SassColor(Box::new(Color::from_rgba(
+            channel(red),
+            channel(green),
+            channel(blue),
+            channel(alpha),
+        )))
+    }
+
+    /// A color from a hue in degrees, saturation and lightness as
+    /// percentages between `0` and `100`, and an alpha channel between
+    /// `0` and `1`
+    #[must_use]
+    #[inline]
+    pub fn hsl(hue: f64, saturation: f64, lightness: f64, alpha: f64) -> Self {
+        SassColor(Box::new(Color::from_hsla(
+            channel(hue),
+            channel(saturation) / Number::from(100),
+            channel(lightness) / Number::from(100),
+            channel(alpha),
+        )))
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn red(&self) -> f64 {
+        float(self.0.red())
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn green(&self) -> f64 {
+        float(self.0.green())
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn blue(&self) -> f64 {
+        float(self.0.blue())
+    }
+
+    /// The hue of this color in degrees
+    #[must_use]
+    #[inline]
+    pub fn hue(&self) -> f64 {
+        float(self.0.hue())
+    }
+
+    /// The saturation of this color as a percentage
+    #[must_use]
+    #[inline]
+    pub fn saturation(&self) -> f64 {
+        float(self.0.saturation())
+    }
+
+    /// The lightness of this color as a percentage
+    #[must_use]
+    #[inline]
+    pub fn lightness(&self) -> f64 {
+        float(self.0.lightness())
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn alpha(&self) -> f64 {
+        float(self.0.alpha())
+    }
+}
+
+/// Non-finite channels are treated as `0`
+fn channel(n: f64) -> Number {
+    if n.is_finite() {
+        Number::from(n)
+    } else {
+        Number::from(0)
+    }
+}
+
+fn float(n: Number) -> f64 {
+    n.as_float().unwrap_or(f64::NAN)
+}
@@ -0,0 +1,102 @@
+use crate::{common, value::Value};
+
+use super::SassValue;
+
+/// The separator between the elements of a [`SassList`](SassList)
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ListSeparator {
+    Space,
+    Comma,
+    Slash,
+}
+
+impl From<common::ListSeparator> for ListSeparator {
+    #[inline]
+    fn from(separator: common::ListSeparator) -> Self {
+        match separator {
+            common::ListSeparator::Space => ListSeparator::Space,
+            common::ListSeparator::Comma => ListSeparator::Comma,
+            common::ListSeparator::Slash => ListSeparator::Slash,
+        }
+    }
+}
+
+impl From<ListSeparator> for common::ListSeparator {
+    fn from(separator: ListSeparator) -> Self {
+        match separator {
+            ListSeparator::Space => common::ListSeparator::Space,
+            ListSeparator::Comma => common::ListSeparator::Comma,
+            ListSeparator::Slash => common::ListSeparator::Slash,
+        }
+    }
+}
+
+/// A Sass list, which may be surrounded by square brackets
+///
+/// Lists are equal only if they have the same separator and
+/// brackets as well as equal elements.
+#[derive(Debug, Clone)]
+pub struct SassList {
+    elements: Vec<SassValue>,
+    separator: ListSeparator,
+    brackets: bool,
+}
+
+impl SassList {
+    #[must_use]
+    #[inline]
+    pub const fn new(elements: Vec<SassValue>, separator: ListSeparator, brackets: bool) -> Self {
+        SassList {
+            elements,
+            separator,
+            brackets,
+        }
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn elements(&self) -> &[SassValue] {
+        &self.elements
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn into_elements(self) -> Vec<SassValue> {
+        self.elements
+    }
+
+    #[must_use]
+    #[inline]
+    pub const fn separator(&self) -> ListSeparator {
+        self.separator
+    }
+
+    /// Whether this list is surrounded by square brackets
+    #[must_use]
+    #[inline]
+    pub const fn has_brackets(&self) -> bool {
+        self.brackets
+    }
+
+    pub(super) fn to_value(self) -> Value {
+        Value::List(
+            self.elements.into_iter().map(SassValue::to_value).collect(),
+            self.separator.into(),
+            if self.brackets {
+                common::Brackets::Bracketed
+            } else {
+                common::Brackets::None
+            },
+        )
+    }
+}
+
+impl PartialEq for SassList {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.clone().to_value() == other.clone().to_value()
+    }
+}
+
+impl Eq for SassList {}
@@ -0,0 +1,112 @@
+use std::slice::Iter;
+
+use crate::value;
+
+use super::SassValue;
+
+/// A Sass map, which preserves the order its keys were inserted in
+///
+/// Keys are compared using Sass equality, so inserting `1in` into a
+/// map containing `96px` replaces the existing entry. Maps are equal
+/// if they contain the same entries, regardless of order.
+#[derive(Debug, Clone, Default)]
+pub struct SassMap(Vec<(SassValue, SassValue)>);
+
+impl SassMap {
+    #[must_use]
+    #[inline]
+    pub const fn new() -> Self {
+        SassMap(Vec::new())
+    }
+
+    /// The value associated with `key`, if any
+    #[must_use]
+    #[inline]
+    pub fn get(&self, key: &SassValue) -> Option<&SassValue> {
+        self.0.iter().find(|(k, _)| k == key).map(|(_, v)| v)
+    }
+
+    /// Associate `value` with `key`, replacing and returning any
+    /// existing value
+    #[inline]
+    pub fn insert(&mut self, key: SassValue, value: SassValue) -> Option<SassValue> {
+        for (k, v) in &mut self.0 {
+            if *k == key {
+                return Some(std::mem::replace(v, value));
+            }
+        }
+        self.0.push((key, value));
+        None
+    }
+
+    /// Remove and return the value associated with `key`, if any
+    #[inline]
+    pub fn remove(&mut self, key: &SassValue) -> Option<SassValue> {
+        let idx = self.0.iter().position(|(k, _)| k == key)?;
+        Some(self.0.remove(idx).1)
+    }
+
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, (SassValue, SassValue)> {
+        self.0.iter()
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub(super) fn from_internal(map: value::SassMap) -> Self {
+        SassMap(
+            map.entries()
+                .into_iter()
+                .map(|(k, v)| (SassValue::from_value(k), SassValue::from_value(v)))
+                .collect(),
+        )
+    }
+
+    pub(super) fn to_internal(self) -> value::SassMap {
+        value::SassMap::new_with(
+            self.0
+                .into_iter()
+                .map(|(k, v)| (k.to_value(), v.to_value()))
+                .collect(),
+        )
+    }
+}
+
+impl PartialEq for SassMap {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.clone().to_internal() == other.clone().to_internal()
+    }
+}
+
+impl Eq for SassMap {}
+
+impl IntoIterator for SassMap {
+    type Item = (SassValue, SassValue);
+    type IntoIter = std::vec::IntoIter<Self::Item>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a SassMap {
+    type Item = &'a (SassValue, SassValue);
+    type IntoIter = Iter<'a, (SassValue, SassValue)>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
@@ -0,0 +1,359 @@
+//! The public representation of Sass values, used to pass arguments
+//! to and return results from custom functions
+//!
+//! Each type converts losslessly to and from the internal `Value` used
+//! by the parser, and equality and `inspect()` defer to it so that values
+//! behave exactly as they would in a stylesheet.
+
+use codemap::{CodeMap, Span};
+
+use crate::{
+    common::{Brackets, QuoteKind},
+    unit::Unit,
+    value::{self, Division, Number, SassFunction, Value},
+};
+
+pub use color::SassColor;
+pub use list::{ListSeparator, SassList};
+pub use map::SassMap;
+pub use number::SassNumber;
+
+mod color;
+mod list;
+mod map;
+mod number;
+
+/// A Sass value, as received by and returned from functions
+/// registered through [`Options::add_function`](crate::Options::add_function)
+///
+/// Equality follows the rules of Sass' `==` operator, so, for example,
+/// `1in` is equal to `96px` and quoted strings are equal to their
+/// unquoted counterparts.
+#[derive(Debug, Clone)]
+pub enum SassValue {
+    Null,
+    Bool(bool),
+    Number(SassNumber),
+    Color(SassColor),
+    String(SassString),
+    List(SassList),
+    Map(SassMap),
+    /// A reference to a function, as returned by `get-function()`
+    Function(SassFunctionRef),
+    /// A `calc()`, `min()`, `max()`, or `clamp()` that could not be
+    /// simplified to a number
+    Calculation(SassCalculation),
+}
+
+impl SassValue {
+    /// The value `null`
+    #[must_use]
+    #[inline]
+    pub const fn null() -> Self {
+        SassValue::Null
+    }
+
+    /// Either `true` or `false`
+    #[must_use]
+    #[inline]
+    pub const fn bool(b: bool) -> Self {
+        SassValue::Bool(b)
+    }
+
+    /// A number with an optional unit, e.g. `SassValue::number(1.5, "px")`
+    ///
+    /// Pass an empty string for a unitless number.
+    #[must_use]
+    #[inline]
+    pub fn number(n: f64, unit: &str) -> Self {
+        SassValue::Number(SassNumber::new(n, unit))
+    }
+
+    /// A string, which is written with quotes if `quoted` is `true`
+    #[must_use]
+    #[inline]
+    pub fn string(text: &str, quoted: bool) -> Self {
+        SassValue::String(SassString::new(text, quoted))
+    }
+
+    /// Whether this value is `null`
+    #[must_use]
+    #[inline]
+    pub const fn is_null(&self) -> bool {
+        matches!(self, SassValue::Null)
+    }
+
+    /// Whether this value is considered true by `@if` and other
+    /// conditionals. Only `false` and `null` are falsey.
+    #[must_use]
+    #[inline]
+    pub const fn is_truthy(&self) -> bool {
+        !matches!(self, SassValue::Null | SassValue::Bool(false))
+    }
+
+    /// The numeric value and unit of this value, if it is a number
+    ///
+    /// Unitless numbers have an empty unit.
+    #[must_use]
+    #[inline]
+    pub fn as_number(&self) -> Option<(f64, String)> {
+        match self {
+            SassValue::Number(n) => Some((n.value(), n.unit())),
+            _ => None,
+        }
+    }
+
+    /// The text of this value, without quotes, if it is a string
+    #[must_use]
+    #[inline]
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            SassValue::String(s) => Some(s.text()),
+            _ => None,
+        }
+    }
+
+    /// The elements of this value when treated as a list
+    ///
+    /// Maps are lists of two-element pairs and all other values
+    /// are lists containing only themselves.
+    #[must_use]
+    #[inline]
+    pub fn as_list(&self) -> Vec<SassValue> {
+        match self {
+            SassValue::List(list) => list.elements().to_vec(),
+            SassValue::Map(map) => map
+                .iter()
+                .map(|(key, value)| {
+                    SassValue::List(SassList::new(
+                        vec![key.clone(), value.clone()],
+                        ListSeparator::Space,
+                        false,
+                    ))
+                })
+                .collect(),
+            value => vec![value.clone()],
+        }
+    }
+
+    /// The name of this value's type, as returned by `type-of()`
+    #[must_use]
+    #[inline]
+    pub fn kind(&self) -> &'static str {
+        self.clone().to_value().kind()
+    }
+
+    /// A representation of this value as it would be written in
+    /// a stylesheet, matching the builtin function `inspect()`
+    #[must_use]
+    #[inline]
+    pub fn inspect(&self) -> String {
+        let value = self.clone().to_value();
+        // inspecting a value only fails for values that have no
+        // representation, which cannot be constructed
+        with_span(|span| value.inspect(span).map(|s| s.to_string())).unwrap_or_default()
+    }
+
+    /// Convert a value received from the parser. Argument lists
+    /// become plain comma separated lists.
+    pub(crate) fn from_value(value: Value) -> Self {
+        match value {
+            Value::Null => SassValue::Null,
+            Value::True => SassValue::Bool(true),
+            Value::False => SassValue::Bool(false),
+            Value::Important => SassValue::string("!important", false),
+            Value::Dimension(num, unit, _) => SassValue::Number(SassNumber { num, unit }),
+            Value::Color(color) => SassValue::Color(SassColor(color)),
+            Value::String(text, quotes) => SassValue::String(SassString {
+                text,
+                quoted: quotes == QuoteKind::Quoted,
+            }),
+            Value::List(elements, separator, brackets) => SassValue::List(SassList::new(
+                elements.into_iter().map(SassValue::from_value).collect(),
+                separator.into(),
+                brackets == Brackets::Bracketed,
+            )),
+            Value::ArgList(args) => SassValue::List(SassList::new(
+                args.into_iter()
+                    .map(|arg| SassValue::from_value(arg.node))
+                    .collect(),
+                ListSeparator::Comma,
+                false,
+            )),
+            Value::Map(map) => SassValue::Map(SassMap::from_internal(map)),
+            Value::FunctionRef(f) => SassValue::Function(SassFunctionRef(f)),
+            Value::Calculation(calc) => SassValue::Calculation(SassCalculation(calc)),
+        }
+    }
+
+    pub(crate) fn to_value(self) -> Value {
+        match self {
+            SassValue::Null => Value::Null,
+            SassValue::Bool(b) => Value::bool(b),
+            SassValue::Number(SassNumber { num, unit }) => {
+                Value::Dimension(num, unit, Division::Computed)
+            }
+            SassValue::Color(SassColor(color)) => Value::Color(color),
+            SassValue::String(SassString { text, quoted }) => Value::String(
+                text,
+                if quoted {
+                    QuoteKind::Quoted
+                } else {
+                    QuoteKind::None
+                },
+            ),
+            SassValue::List(list) => list.to_value(),
+            SassValue::Map(map) => Value::Map(map.to_internal()),
+            SassValue::Function(SassFunctionRef(f)) => Value::FunctionRef(f),
+            SassValue::Calculation(SassCalculation(calc)) => Value::Calculation(calc),
+        }
+    }
+}
+
+impl PartialEq for SassValue {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.clone().to_value() == other.clone().to_value()
+    }
+}
+
+impl Eq for SassValue {}
+
+impl From<bool> for SassValue {
+    #[inline]
+    fn from(b: bool) -> Self {
+        SassValue::Bool(b)
+    }
+}
+
+impl From<SassNumber> for SassValue {
+    #[inline]
+    fn from(n: SassNumber) -> Self {
+        SassValue::Number(n)
+    }
+}
+
+impl From<SassColor> for SassValue {
+    #[inline]
+    fn from(color: SassColor) -> Self {
+        SassValue::Color(color)
+    }
+}
+
+impl From<SassString> for SassValue {
+    #[inline]
+    fn from(s: SassString) -> Self {
+        SassValue::String(s)
+    }
+}
+
+impl From<SassList> for SassValue {
+    #[inline]
+    fn from(list: SassList) -> Self {
+        SassValue::List(list)
+    }
+}
+
+impl From<SassMap> for SassValue {
+    #[inline]
+    fn from(map: SassMap) -> Self {
+        SassValue::Map(map)
+    }
+}
+
+/// A Sass string, which may or may not be quoted
+///
+/// Quoted and unquoted strings with the same text are equal.
+#[derive(Debug, Clone)]
+pub struct SassString {
+    text: String,
+    quoted: bool,
+}
+
+impl SassString {
+    #[must_use]
+    #[inline]
+    pub fn new(text: &str, quoted: bool) -> Self {
+        SassString {
+            text: text.to_owned(),
+            quoted,
+        }
+    }
+
+    /// The contents of this string, without quotes
+    #[must_use]
+    #[inline]
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Whether this string is written with quotes
+    #[must_use]
+    #[inline]
+    pub const fn is_quoted(&self) -> bool {
+        self.quoted
+    }
+}
+
+impl PartialEq for SassString {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.text == other.text
+    }
+}
+
+impl Eq for SassString {}
+
+/// A reference to a Sass function, as returned by `get-function()`
+///
+/// A reference may be passed back to Sass, e.g. to be invoked
+/// with `call()`, but cannot be constructed from Rust.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SassFunctionRef(SassFunction);
+
+impl SassFunctionRef {
+    /// The name of the referenced function
+    #[must_use]
+    #[inline]
+    pub fn name(&self) -> String {
+        self.0.name().to_string()
+    }
+}
+
+/// A calculation that could not be simplified, such as `calc(1px + 1em)`
+///
+/// A calculation may be passed back to Sass, but cannot be constructed
+/// from Rust.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SassCalculation(value::SassCalculation);
+
+impl SassCalculation {
+    /// The name of the calculation, e.g. `calc` or `clamp`
+    #[must_use]
+    #[inline]
+    pub fn name(&self) -> String {
+        self.0.name().to_string()
+    }
+}
+
+/// Run a fallible operation on an internal value that requires a span
+/// for its errors, outside of any compilation
+fn with_span<T>(f: impl FnOnce(Span) -> crate::error::SassResult<T>) -> Option<T> {
+    let mut map = CodeMap::new();
+    let span = map.add_file(String::new(), String::new()).span;
+    f(span).ok()
+}
+
+/// Parse a unit given by the host, where an empty string is unitless
+fn unit_from_str(unit: &str) -> Unit {
+    if unit.is_empty() {
+        Unit::None
+    } else {
+        Unit::from(unit.to_owned())
+    }
+}
+
+/// Convert a number given by the host, where `None` is `NaN`
+fn number_from_f64(n: f64) -> Option<Number> {
+    (!n.is_nan()).then(|| Number::from(n))
+}
@@ -0,0 +1,119 @@
+use num_traits::Signed;
+
+use crate::{
+    unit::Unit,
+    value::{Division, Number, Value},
+};
+
+use super::{number_from_f64, unit_from_str};
+
+/// A Sass number, which may have a unit
+///
+/// Numbers with compatible units are compared after conversion, so
+/// `SassNumber::new(1.0, "in") == SassNumber::new(96.0, "px")`.
+#[derive(Debug, Clone)]
+pub struct SassNumber {
+    /// A `None` value indicates a `NaN` value
+    pub(super) num: Option<Number>,
+    pub(super) unit: Unit,
+}
+
+impl SassNumber {
+    /// A number with a unit, e.g. `SassNumber::new(1.5, "px")`
+    ///
+    /// Pass an empty string for a unitless number.
+    #[must_use]
+    #[inline]
+    pub fn new(value: f64, unit: &str) -> Self {
+        SassNumber {
+            num: number_from_f64(value),
+            unit: unit_from_str(unit),
+        }
+    }
+
+    /// A number without a unit
+    #[must_use]
+    #[inline]
+    pub fn unitless(value: f64) -> Self {
+        SassNumber::new(value, "")
+    }
+
+    /// The value of this number, ignoring its unit
+    ///
+    /// Numbers too large to be represented as an `f64` are infinite.
+    #[must_use]
+    #[inline]
+    pub fn value(&self) -> f64 {
+        match &self.num {
+            Some(num) => num.clone().as_float().unwrap_or_else(|| {
+                if num.is_negative() {
+                    f64::NEG_INFINITY
+                } else {
+                    f64::INFINITY
+                }
+            }),
+            None => f64::NAN,
+        }
+    }
+
+    /// The unit of this number, as it would be written in CSS,
+    /// e.g. `px` or `px*em`. Unitless numbers have an empty unit.
+    #[must_use]
+    #[inline]
+    pub fn unit(&self) -> String {
+        self.unit.to_string()
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn is_unitless(&self) -> bool {
+        self.unit == Unit::None
+    }
+
+    /// Whether this number is a whole number
+    #[must_use]
+    #[inline]
+    pub fn is_int(&self) -> bool {
+        self.num.as_ref().map_or(false, |n| !n.is_decimal())
+    }
+
+    /// Whether this number could be converted to, compared with,
+    /// or added to a number with the given unit
+    #[must_use]
+    #[inline]
+    pub fn is_comparable_to(&self, unit: &str) -> bool {
+        self.unit.comparable(&unit_from_str(unit))
+    }
+
+    /// This number converted to the given unit, or `None` if the
+    /// units are not compatible
+    ///
+    /// As in Sass, a unitless number can't be converted to a unit, nor can a
+    /// number with a unit be converted to a unitless number, even though
+    /// they are comparable.
+    #[must_use]
+    #[inline]
+    pub fn convert(&self, unit: &str) -> Option<Self> {
+        let unit = unit_from_str(unit);
+        if self.unit == unit {
+            return Some(self.clone());
+        }
+        if self.unit == Unit::None || unit == Unit::None || !self.unit.comparable(&unit) {
+            return None;
+        }
+        Some(SassNumber {
+            num: self.num.clone().map(|n| n.convert(&self.unit, &unit)),
+            unit,
+        })
+    }
+}
+
+impl PartialEq for SassNumber {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        Value::Dimension(self.num.clone(), self.unit.clone(), Division::Computed)
+            == Value::Dimension(other.num.clone(), other.unit.clone(), Division::Computed)
+    }
+}
+
+impl Eq for SassNumber {}
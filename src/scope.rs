@@ -74,7 +74,7 @@ impl Scope {
         self.functions.contains_key(&name)
     }
 
-    fn merge(&mut self, other: Scope) {
+    pub fn merge(&mut self, other: Scope) {
         self.vars.extend(other.vars);
         self.mixins.extend(other.mixins);
         self.functions.extend(other.functions);
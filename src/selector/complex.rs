@@ -43,13 +43,18 @@ impl Hash for ComplexSelector {
     }
 }
 
+/// The alternate flag (`{:#}`) prints the selector as it
+/// should appear in compressed output
 impl fmt::Display for ComplexSelector {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let mut last_component = None;
+        let is_compressed = f.alternate();
 
         for component in &self.components {
             if let Some(c) = last_component {
-                if !omit_spaces_around(c) && !omit_spaces_around(component) {
+                if !omit_spaces_around(c, is_compressed)
+                    && !omit_spaces_around(component, is_compressed)
+                {
                     f.write_char(' ')?;
                 }
             }
@@ -61,9 +66,7 @@ impl fmt::Display for ComplexSelector {
 }
 
 /// When `style` is `OutputStyle::compressed`, omit spaces around combinators.
-fn omit_spaces_around(component: &ComplexSelectorComponent) -> bool {
-    // todo: compressed
-    let is_compressed = false;
+fn omit_spaces_around(component: &ComplexSelectorComponent, is_compressed: bool) -> bool {
     is_compressed && matches!(component, ComplexSelectorComponent::Combinator(..))
 }
 
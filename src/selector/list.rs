@@ -42,6 +42,8 @@ impl Hash for SelectorList {
     }
 }
 
+/// The alternate flag (`{:#}`) prints the selector as it
+/// should appear in compressed output
 impl fmt::Display for SelectorList {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let complexes = self.components.iter().filter(|c| !c.is_invisible());
@@ -53,14 +55,15 @@ impl fmt::Display for SelectorList {
                 first = false;
             } else {
                 f.write_char(',')?;
-                if complex.line_break {
-                    f.write_char('\n')?;
-                } else {
-                    // todo: not emitted in compressed
-                    f.write_char(' ')?;
+                if !f.alternate() {
+                    f.write_char(if complex.line_break { '\n' } else { ' ' })?;
                 }
             }
-            write!(f, "{}", complex)?;
+            if f.alternate() {
+                write!(f, "{:#}", complex)?;
+            } else {
+                write!(f, "{}", complex)?;
+            }
         }
         Ok(())
     }
@@ -0,0 +1,398 @@
+//! # Source Map v3 generation
+//!
+//! <https://sourcemaps.info/spec.html>
+use std::{
+    collections::HashMap,
+    fmt::Write,
+    path::{Component, Path, PathBuf},
+};
+
+use codemap::{CodeMap, Span};
+
+/// How a source map links to the files it was generated from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SourceMapUrls {
+    /// Link to source files using paths relative to the source map
+    Relative,
+    /// Link to source files using absolute `file:` URLs
+    Absolute,
+}
+
+#[derive(Debug, Clone)]
+struct Source {
+    url: String,
+    /// `None` for sources that do not exist on disk, e.g. stdin
+    path: Option<PathBuf>,
+    content: String,
+}
+
+/// A source map linking the generated CSS back to the Sass it was compiled from
+///
+/// Every emitted selector, declaration, and at-rule is mapped to the location
+/// in the stylesheet that produced it.
+///
+/// By default, source URLs are the paths of the source files as they were
+/// found during compilation and file contents are not embedded.
+#[derive(Debug, Clone)]
+pub struct SourceMap {
+    file: Option<String>,
+    sources: Vec<Source>,
+    mappings: String,
+    embed_sources: bool,
+}
+
+impl SourceMap {
+    /// Set the name of the generated CSS file this source map describes
+    #[must_use]
+    #[inline]
+    pub fn file(mut self, file: String) -> Self {
+        self.file = Some(file);
+        self
+    }
+
+    /// Whether to include the full contents of every source file
+    /// in the source map. By default, contents are not embedded.
+    #[must_use]
+    #[inline]
+    pub fn embed_sources(mut self, embed_sources: bool) -> Self {
+        self.embed_sources = embed_sources;
+        self
+    }
+
+    /// Rewrite the URL of each source file on disk to be either
+    /// relative to `map_dir`, the directory the source map will be
+    /// written to, or an absolute `file:` URL
+    #[must_use]
+    #[inline]
+    pub fn source_urls(mut self, urls: SourceMapUrls, map_dir: &Path) -> Self {
+        for source in &mut self.sources {
+            let path = match &source.path {
+                Some(path) => absolute_path(path),
+                None => continue,
+            };
+
+            source.url = match urls {
+                SourceMapUrls::Relative => {
+                    path_to_url(&relative_path(&path, &absolute_path(map_dir)))
+                }
+                SourceMapUrls::Absolute => format!("file://{}", path_to_url(&path)),
+            };
+        }
+        self
+    }
+
+    /// The URLs of the files this source map refers to
+    #[must_use]
+    #[inline]
+    pub fn sources(&self) -> Vec<&str> {
+        self.sources.iter().map(|s| s.url.as_str()).collect()
+    }
+
+    /// The encoded `mappings` field of this source map
+    #[must_use]
+    #[inline]
+    pub fn mappings(&self) -> &str {
+        &self.mappings
+    }
+
+    /// Serialize this source map as JSON
+    #[must_use]
+    #[inline]
+    pub fn to_json(&self) -> String {
+        let mut json = String::from("{\"version\":3");
+
+        if let Some(file) = &self.file {
+            json.push_str(",\"file\":");
+            json.push_str(&json_string(file));
+        }
+
+        json.push_str(",\"sources\":[");
+        json.push_str(
+            &self
+                .sources
+                .iter()
+                .map(|s| json_string(&s.url))
+                .collect::<Vec<String>>()
+                .join(","),
+        );
+        json.push(']');
+
+        if self.embed_sources {
+            json.push_str(",\"sourcesContent\":[");
+            json.push_str(
+                &self
+                    .sources
+                    .iter()
+                    .map(|s| json_string(&s.content))
+                    .collect::<Vec<String>>()
+                    .join(","),
+            );
+            json.push(']');
+        }
+
+        json.push_str(",\"names\":[],\"mappings\":");
+        json.push_str(&json_string(&self.mappings));
+        json.push('}');
+
+        json
+    }
+
+    /// Serialize this source map as a base64 `data:` URL, suitable
+    /// for embedding in a `sourceMappingURL` comment
+    #[must_use]
+    #[inline]
+    pub fn to_data_url(&self) -> String {
+        format!(
+            "data:application/json;charset=utf-8;base64,{}",
+            base64_encode(self.to_json().as_bytes())
+        )
+    }
+}
+
+/// Columns are counted in UTF-16 code units, as the specification requires
+#[derive(Debug, Clone, Copy)]
+struct Mapping {
+    generated_line: usize,
+    generated_column: usize,
+    span: Span,
+}
+
+/// Records the source location of each selector, declaration,
+/// and at-rule as the CSS is written
+#[derive(Debug)]
+pub(crate) struct SourceMapBuilder {
+    is_enabled: bool,
+    mappings: Vec<Mapping>,
+    /// How far into the output buffer we have counted lines and columns
+    scanned: usize,
+    line: usize,
+    column: usize,
+}
+
+impl SourceMapBuilder {
+    pub const fn new(is_enabled: bool) -> Self {
+        SourceMapBuilder {
+            is_enabled,
+            mappings: Vec::new(),
+            scanned: 0,
+            line: 0,
+            column: 0,
+        }
+    }
+
+    /// Map the current end of `buf` to the start of `span`
+    pub fn add_mapping(&mut self, buf: &[u8], span: Span) {
+        if !self.is_enabled {
+            return;
+        }
+
+        // the buffer is always valid UTF-8 at the boundaries we are called at
+        for c in String::from_utf8_lossy(&buf[self.scanned..]).chars() {
+            if c == '\n' {
+                self.line += 1;
+                self.column = 0;
+            } else {
+                self.column += c.len_utf16();
+            }
+        }
+        self.scanned = buf.len();
+
+        self.mappings.push(Mapping {
+            generated_line: self.line,
+            generated_column: self.column,
+            span,
+        });
+    }
+
+    /// Account for text inserted at the very start of the output
+    /// after it was written, such as `@charset` or a byte-order mark
+    pub fn shift(&mut self, lines: usize, columns: usize) {
+        for mapping in &mut self.mappings {
+            if mapping.generated_line == 0 {
+                mapping.generated_column += columns;
+            }
+            mapping.generated_line += lines;
+        }
+    }
+
+    pub fn build(self, map: &CodeMap) -> SourceMap {
+        let mut sources: Vec<Source> = Vec::new();
+        let mut source_indices: HashMap<String, usize> = HashMap::new();
+
+        let mut mappings = String::new();
+        let mut prev_line = 0;
+        let mut prev_generated_column = 0;
+        let mut prev_source = 0;
+        let mut prev_source_line = 0;
+        let mut prev_source_column = 0;
+        let mut is_first_in_line = true;
+
+        for mapping in self.mappings {
+            let loc = map.look_up_span(mapping.span);
+            let name = loc.file.name().to_owned();
+            // codemap counts columns in chars
+            let source_column = loc
+                .file
+                .source_line(loc.begin.line)
+                .chars()
+                .take(loc.begin.column)
+                .map(char::len_utf16)
+                .sum();
+
+            let source = match source_indices.get(&name) {
+                Some(idx) => *idx,
+                None => {
+                    let path = Path::new(&name);
+                    sources.push(Source {
+                        url: path_to_url(path),
+                        path: path.is_file().then(|| path.to_path_buf()),
+                        content: loc.file.source().to_owned(),
+                    });
+                    source_indices.insert(name, sources.len() - 1);
+                    sources.len() - 1
+                }
+            };
+
+            while prev_line < mapping.generated_line {
+                mappings.push(';');
+                prev_line += 1;
+                prev_generated_column = 0;
+                is_first_in_line = true;
+            }
+
+            if !is_first_in_line {
+                mappings.push(',');
+            }
+            is_first_in_line = false;
+
+            encode_vlq(
+                &mut mappings,
+                mapping.generated_column,
+                prev_generated_column,
+            );
+            encode_vlq(&mut mappings, source, prev_source);
+            encode_vlq(&mut mappings, loc.begin.line, prev_source_line);
+            encode_vlq(&mut mappings, source_column, prev_source_column);
+
+            prev_generated_column = mapping.generated_column;
+            prev_source = source;
+            prev_source_line = loc.begin.line;
+            prev_source_column = source_column;
+        }
+
+        SourceMap {
+            file: None,
+            sources,
+            mappings,
+            embed_sources: false,
+        }
+    }
+}
+
+const BASE64_CHARS: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Encode the difference between two values as a base64 VLQ
+fn encode_vlq(buf: &mut String, current: usize, previous: usize) {
+    // the lowest bit holds the sign
+    let mut vlq = if current < previous {
+        ((previous - current) << 1) | 1
+    } else {
+        (current - previous) << 1
+    };
+
+    loop {
+        let mut digit = vlq & 0b1_1111;
+        vlq >>= 5;
+        if vlq > 0 {
+            digit |= 0b10_0000;
+        }
+        buf.push(BASE64_CHARS[digit] as char);
+        if vlq == 0 {
+            break;
+        }
+    }
+}
+
+fn base64_encode(bytes: &[u8]) -> String {
+    let mut buf = String::new();
+
+    for chunk in bytes.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = (u32::from(b[0]) << 16) | (u32::from(b[1]) << 8) | u32::from(b[2]);
+
+        buf.push(BASE64_CHARS[(n >> 18) as usize & 63] as char);
+        buf.push(BASE64_CHARS[(n >> 12) as usize & 63] as char);
+        if chunk.len() > 1 {
+            buf.push(BASE64_CHARS[(n >> 6) as usize & 63] as char);
+        } else {
+            buf.push('=');
+        }
+        if chunk.len() > 2 {
+            buf.push(BASE64_CHARS[n as usize & 63] as char);
+        } else {
+            buf.push('=');
+        }
+    }
+
+    buf
+}
+
+fn json_string(s: &str) -> String {
+    let mut buf = String::with_capacity(s.len() + 2);
+    buf.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => buf.push_str("\\\""),
+            '\\' => buf.push_str("\\\\"),
+            '\n' => buf.push_str("\\n"),
+            '\r' => buf.push_str("\\r"),
+            '\t' => buf.push_str("\\t"),
+            '\u{0}'..='\u{1f}' => {
+                let _ = write!(buf, "\\u{:04x}", c as u32);
+            }
+            _ => buf.push(c),
+        }
+    }
+    buf.push('"');
+    buf
+}
+
+/// URLs always use forward slashes, regardless of platform
+fn path_to_url(path: &Path) -> String {
+    path.components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+        .replacen("//", "/", 1)
+}
+
+fn absolute_path(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| {
+        std::env::current_dir().map_or_else(|_| path.to_path_buf(), |dir| dir.join(path))
+    })
+}
+
+/// Both paths are assumed to be absolute
+fn relative_path(path: &Path, base: &Path) -> PathBuf {
+    let path: Vec<Component<'_>> = path.components().collect();
+    let base: Vec<Component<'_>> = base.components().collect();
+
+    let common = path
+        .iter()
+        .zip(base.iter())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let mut relative = PathBuf::new();
+    for _ in common..base.len() {
+        relative.push("..");
+    }
+    for component in &path[common..] {
+        relative.push(component.as_os_str());
+    }
+    relative
+}
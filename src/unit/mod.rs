@@ -1,9 +1,8 @@
-use std::{
-    fmt,
-    ops::{Div, Mul},
-};
+use std::fmt;
 
-use crate::interner::InternedString;
+use num_traits::One;
+
+use crate::{interner::InternedString, value::Number};
 
 pub(crate) use conversion::UNIT_CONVERSION_TABLE;
 
@@ -140,100 +139,95 @@ impl DivUnit {
 
 impl fmt::Display for DivUnit {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        if self.numer == Unit::None {
-            write!(f, "{}^-1", self.denom)
-        } else {
-            write!(f, "{}/{}", self.numer, self.denom)
+        match (&self.numer, &self.denom) {
+            (Unit::None, Unit::Mul(..)) => write!(f, "({})^-1", self.denom),
+            (Unit::None, _) => write!(f, "{}^-1", self.denom),
+            _ => write!(f, "{}/{}", self.numer, self.denom),
         }
     }
 }
 
-#[allow(clippy::match_same_arms)]
-impl Mul<Unit> for DivUnit {
-    type Output = Unit;
-    fn mul(self, rhs: Unit) -> Self::Output {
-        match rhs {
-            Unit::Mul(..) => todo!(),
-            Unit::Div(..) => todo!(),
-            Unit::None => todo!(),
-            _ => {
-                if self.denom == rhs {
-                    self.numer
-                } else {
-                    match self.denom {
-                        Unit::Mul(..) => todo!(),
-                        Unit::Div(..) => unreachable!(),
-                        _ => match self.numer {
-                            Unit::Mul(..) => todo!(),
-                            Unit::Div(..) => unreachable!(),
-                            Unit::None => {
-                                let numer = Unit::Mul(Box::new(vec![rhs]));
-                                Unit::Div(Box::new(DivUnit::new(numer, self.denom)))
-                            }
-                            _ => {
-                                let numer = Unit::Mul(Box::new(vec![self.numer, rhs]));
-                                Unit::Div(Box::new(DivUnit::new(numer, self.denom)))
-                            }
-                        },
-                    }
+/// Multiply `num` with `unit` by `num2` with `unit2`
+///
+/// Units that appear in both the numerator and the denominator of the
+/// result cancel out, with the number converted between them if they are
+/// compatible but not equal, e.g. `1in * 1px^-1` is `96`.
+pub(crate) fn mul_units(num: Number, unit: Unit, num2: Number, unit2: Unit) -> (Number, Unit) {
+    let (mut numer, mut denom) = unit.into_parts();
+    let (numer2, denom2) = unit2.into_parts();
+    numer.extend(numer2);
+    denom.extend(denom2);
+    cancel_units(num * num2, numer, denom)
+}
+
+/// Divide `num` with `unit` by `num2` with `unit2`, cancelling units
+/// as [`mul_units`] does, e.g. `1in / 1px` is `96`
+pub(crate) fn div_units(num: Number, unit: Unit, num2: Number, unit2: Unit) -> (Number, Unit) {
+    let (mut numer, mut denom) = unit.into_parts();
+    let (numer2, denom2) = unit2.into_parts();
+    numer.extend(denom2);
+    denom.extend(numer2);
+    cancel_units(num / num2, numer, denom)
+}
+
+fn cancel_units(mut num: Number, mut numer: Vec<Unit>, denom: Vec<Unit>) -> (Number, Unit) {
+    let mut remaining_denom = Vec::new();
+
+    for unit in denom {
+        // prefer cancelling identical units, which requires no conversion
+        let idx = numer
+            .iter()
+            .position(|u| *u == unit)
+            .or_else(|| numer.iter().position(|u| u.comparable(&unit)));
+
+        match idx {
+            Some(idx) => {
+                let numer_unit = numer.remove(idx);
+                if numer_unit != unit {
+                    num /= Number::one().convert(&unit, &numer_unit);
                 }
             }
+            None => remaining_denom.push(unit),
         }
     }
-}
 
-// impl Div<Unit> for DivUnit {
-//     type Output = Unit;
-//     fn div(self, rhs: Unit) -> Self::Output {
-//         todo!()
-//     }
-// }
+    (num, Unit::from_parts(numer, remaining_denom))
+}
 
-impl Mul<Unit> for Unit {
-    type Output = Unit;
-    fn mul(self, rhs: Unit) -> Self::Output {
+impl Unit {
+    /// The units multiplied together in the numerator and denominator
+    /// of this unit
+    fn into_parts(self) -> (Vec<Unit>, Vec<Unit>) {
         match self {
-            Unit::Mul(u) => match rhs {
-                Unit::Mul(u2) => {
-                    let mut unit1 = *u;
-                    unit1.extend_from_slice(&*u2);
-                    Unit::Mul(Box::new(unit1))
-                }
-                Unit::Div(..) => todo!(),
-                _ => {
-                    let mut unit1 = *u;
-                    unit1.push(rhs);
-                    Unit::Mul(Box::new(unit1))
-                }
-            },
-            Unit::Div(div) => *div * rhs,
-            _ => match rhs {
-                Unit::Mul(u2) => {
-                    let mut unit1 = vec![self];
-                    unit1.extend_from_slice(&*u2);
-                    Unit::Mul(Box::new(unit1))
-                }
-                Unit::Div(..) => todo!(),
-                _ => Unit::Mul(Box::new(vec![self, rhs])),
-            },
+            Unit::None => (Vec::new(), Vec::new()),
+            Unit::Mul(units) => (*units, Vec::new()),
+            Unit::Div(div) => {
+                let (mut numer, mut denom) = div.numer.into_parts();
+                let (denom_numer, denom_denom) = div.denom.into_parts();
+                numer.extend(denom_denom);
+                denom.extend(denom_numer);
+                (numer, denom)
+            }
+            unit => (vec![unit], Vec::new()),
         }
     }
-}
 
-impl Div<Unit> for Unit {
-    type Output = Unit;
-    fn div(self, rhs: Unit) -> Self::Output {
-        if let Unit::Div(..) = self {
-            todo!()
-        } else if let Unit::Div(..) = rhs {
-            todo!()
+    fn from_parts(numer: Vec<Unit>, denom: Vec<Unit>) -> Self {
+        fn product(mut units: Vec<Unit>) -> Unit {
+            match units.len() {
+                0 => Unit::None,
+                1 => units.remove(0),
+                _ => Unit::Mul(Box::new(units)),
+            }
+        }
+
+        if denom.is_empty() {
+            product(numer)
         } else {
-            Unit::Div(Box::new(DivUnit::new(self, rhs)))
+            Unit::Div(Box::new(DivUnit::new(product(numer), product(denom))))
         }
     }
-}
 
-impl Unit {
     pub fn comparable(&self, other: &Unit) -> bool {
         if other == &Unit::None {
             return true;
@@ -245,6 +239,32 @@ impl Unit {
         }
     }
 
+    /// Whether numbers with these units can be combined as they are,
+    /// which, unlike [`comparable`](Unit::comparable), is never the case
+    /// for a unitless number and one with units
+    pub fn compatible(&self, other: &Unit) -> bool {
+        self == other || (self != &Unit::None && other != &Unit::None && self.comparable(other))
+    }
+
+    /// Whether these units may turn out to be compatible once the browser
+    /// knows the size of relative units, e.g. `px` and `em`, or anything
+    /// and `%`
+    pub fn possibly_compatible(&self, other: &Unit) -> bool {
+        if self.compatible(other) {
+            return true;
+        }
+        match (self.kind(), other.kind()) {
+            (UnitKind::None, _) | (_, UnitKind::None) => false,
+            (UnitKind::Other, _)
+            | (_, UnitKind::Other)
+            | (
+                UnitKind::Absolute | UnitKind::FontRelative | UnitKind::ViewportRelative,
+                UnitKind::Absolute | UnitKind::FontRelative | UnitKind::ViewportRelative,
+            ) => true,
+            _ => false,
+        }
+    }
+
     /// Used internally to determine if two units are comparable or not
     fn kind(&self) -> UnitKind {
         match self {
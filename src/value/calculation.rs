@@ -0,0 +1,394 @@
+//! CSS calculations: `calc()`, `min()`, `max()`, and `clamp()`
+//!
+//! Calculations are simplified as far as their units allow, so that
+//! `calc(1px + 2px)` is the number `3px`. Terms whose units are only known
+//! to the browser, such as `1px + 1em`, are kept as they were written.
+
+use std::{fmt, iter};
+
+use codemap::Span;
+use num_traits::Signed;
+
+use crate::{
+    common::{Op, QuoteKind},
+    error::SassResult,
+    unit::{div_units, mul_units, Unit},
+    value::{Division, Number, Value},
+};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) enum CalculationName {
+    Calc,
+    Min,
+    Max,
+    Clamp,
+}
+
+impl CalculationName {
+    /// The calculation with this name, which is expected to be lowercase
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "calc" => Some(Self::Calc),
+            "min" => Some(Self::Min),
+            "max" => Some(Self::Max),
+            "clamp" => Some(Self::Clamp),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for CalculationName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Calc => write!(f, "calc"),
+            Self::Min => write!(f, "min"),
+            Self::Max => write!(f, "max"),
+            Self::Clamp => write!(f, "clamp"),
+        }
+    }
+}
+
+/// A single argument to a calculation, or an operand within one
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum CalculationArg {
+    Number(Number, Unit),
+    Calculation(SassCalculation),
+    /// An unquoted string, such as `var(--a)`
+    String(String),
+    /// The result of interpolation, which is wrapped in parentheses when
+    /// it is an operand, as it may contain operators itself
+    Interpolation(String),
+    Operation(Box<CalculationArg>, Op, Box<CalculationArg>),
+}
+
+impl CalculationArg {
+    /// Convert a value resulting from SassScript within a calculation
+    pub fn from_value(value: Value, span: Span) -> SassResult<Self> {
+        match value {
+            Value::Dimension(Some(num), unit, _) => Ok(CalculationArg::Number(num, unit)),
+            // nested `calc()`s are redundant, although their contents may
+            // need parentheses once they're an operand
+            Value::Calculation(mut calc) if calc.name == CalculationName::Calc => {
+                Ok(calc.args.remove(0))
+            }
+            Value::Calculation(calc) => Ok(CalculationArg::Calculation(calc)),
+            Value::String(s, QuoteKind::None) => Ok(CalculationArg::String(s)),
+            v => Err((
+                format!("Value {} can't be used in a calculation.", v.inspect(span)?),
+                span,
+            )
+                .into()),
+        }
+    }
+
+    fn into_value(self) -> Value {
+        match self {
+            // infinity and NaN are only valid CSS within a calculation
+            CalculationArg::Number(num, unit) if num.is_finite() => {
+                Value::Dimension(Some(num), unit, Division::Computed)
+            }
+            CalculationArg::Calculation(calc) => Value::Calculation(calc),
+            arg => Value::Calculation(SassCalculation {
+                name: CalculationName::Calc,
+                args: vec![arg],
+            }),
+        }
+    }
+
+    /// Combine two operands, simplifying the operation if both are numbers
+    /// whose units allow it
+    pub fn operate(op: Op, left: Self, right: Self, span: Span) -> SassResult<Self> {
+        match (op, left, right) {
+            (
+                Op::Plus | Op::Minus,
+                CalculationArg::Number(num, unit),
+                CalculationArg::Number(num2, unit2),
+            ) if unit.compatible(&unit2) => {
+                let num2 = if unit == unit2 {
+                    num2
+                } else {
+                    num2.convert(&unit2, &unit)
+                };
+                Ok(CalculationArg::Number(
+                    if op == Op::Plus {
+                        num + num2
+                    } else {
+                        num - num2
+                    },
+                    unit,
+                ))
+            }
+            (Op::Mul, CalculationArg::Number(num, unit), CalculationArg::Number(num2, unit2)) => {
+                let (num, unit) = mul_units(num, unit, num2, unit2);
+                Ok(CalculationArg::Number(num, unit))
+            }
+            (Op::Div, CalculationArg::Number(num, unit), CalculationArg::Number(num2, unit2)) => {
+                let (num, unit) = div_units(num, unit, num2, unit2);
+                Ok(CalculationArg::Number(num, unit))
+            }
+            (op, left, right) => {
+                if matches!(op, Op::Plus | Op::Minus) {
+                    verify_compatible(&[&left, &right], span)?;
+                }
+                Ok(CalculationArg::Operation(
+                    Box::new(left),
+                    op,
+                    Box::new(right),
+                ))
+            }
+        }
+    }
+
+    fn serialize(&self, span: Span, is_compressed: bool, is_operand: bool) -> SassResult<String> {
+        Ok(match self {
+            CalculationArg::Number(num, unit) if !num.is_finite() => {
+                let mut buf = if num.is_positive() {
+                    "infinity"
+                } else if num.is_negative() {
+                    "-infinity"
+                } else {
+                    "NaN"
+                }
+                .to_owned();
+                if *unit != Unit::None {
+                    buf.push_str(if is_compressed { "*1" } else { " * 1" });
+                    buf.push_str(&unit.to_string());
+                }
+                buf
+            }
+            CalculationArg::Number(num, unit) => {
+                Value::Dimension(Some(num.clone()), unit.clone(), Division::Computed)
+                    .serialize(span, is_compressed)?
+                    .into_owned()
+            }
+            CalculationArg::Calculation(calc) => calc.serialize(span, is_compressed)?,
+            CalculationArg::String(s) => s.clone(),
+            CalculationArg::Interpolation(s) if is_operand => format!("({})", s),
+            CalculationArg::Interpolation(s) => s.clone(),
+            CalculationArg::Operation(left, op, right) => {
+                let left_parens = match **left {
+                    CalculationArg::Operation(_, left_op, _) => {
+                        left_op.precedence() < op.precedence()
+                    }
+                    _ => false,
+                };
+                let right_parens = match **right {
+                    CalculationArg::Operation(_, right_op, _) => match op {
+                        Op::Div => true,
+                        Op::Plus => false,
+                        _ => matches!(right_op, Op::Plus | Op::Minus),
+                    },
+                    _ => false,
+                };
+
+                let mut left = left.serialize(span, is_compressed, true)?;
+                if left_parens {
+                    left = format!("({})", left);
+                }
+
+                // `+` and `-` must always be surrounded by whitespace
+                let op = if is_compressed && matches!(op, Op::Mul | Op::Div) {
+                    op.to_string()
+                } else {
+                    format!(" {} ", op)
+                };
+
+                let right = right.serialize(span, is_compressed, true)?;
+                if right_parens {
+                    format!("{}{}({})", left, op, right)
+                } else {
+                    format!("{}{}{}", left, op, right)
+                }
+            }
+        })
+    }
+}
+
+/// Ensure that no two numbers are definitely incompatible, as `1px + 1s`
+/// is, even if the calculation can't be simplified
+fn verify_compatible(args: &[&CalculationArg], span: Span) -> SassResult<()> {
+    let numbers: Vec<(&Number, &Unit)> = args
+        .iter()
+        .filter_map(|arg| match arg {
+            CalculationArg::Number(num, unit) => Some((num, unit)),
+            _ => None,
+        })
+        .collect();
+
+    for (num, unit) in &numbers {
+        if matches!(unit, Unit::Mul(..) | Unit::Div(..)) {
+            return Err((
+                format!(
+                    "Number {}{} isn't compatible with CSS calculations.",
+                    num, unit
+                ),
+                span,
+            )
+                .into());
+        }
+    }
+
+    for (idx, (num, unit)) in numbers.iter().enumerate() {
+        for (num2, unit2) in numbers.iter().skip(idx + 1) {
+            if !unit.possibly_compatible(unit2) {
+                return Err((
+                    format!("{}{} and {}{} are incompatible.", num, unit, num2, unit2),
+                    span,
+                )
+                    .into());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// A calculation that could not be simplified to a single number
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct SassCalculation {
+    name: CalculationName,
+    args: Vec<CalculationArg>,
+}
+
+impl SassCalculation {
+    /// `calc(arg)`, which is unwrapped if `arg` is a number or
+    /// another calculation
+    pub fn calc(arg: CalculationArg) -> Value {
+        arg.into_value()
+    }
+
+    /// `min(args...)` or `max(args...)`, which is the smallest or largest of
+    /// `args` if they are numbers with compatible units
+    pub fn min_max(
+        name: CalculationName,
+        args: Vec<CalculationArg>,
+        span: Span,
+    ) -> SassResult<Value> {
+        let mut extremum: Option<(&Number, &Unit)> = None;
+        let mut simplifiable = true;
+        for arg in &args {
+            match (arg, extremum) {
+                (CalculationArg::Number(num, unit), None) => extremum = Some((num, unit)),
+                (CalculationArg::Number(num, unit), Some((num2, unit2)))
+                    if unit.compatible(unit2) =>
+                {
+                    let converted = if unit == unit2 {
+                        num.clone()
+                    } else {
+                        num.clone().convert(unit, unit2)
+                    };
+                    let replace = match name {
+                        CalculationName::Min => &converted < num2,
+                        _ => &converted > num2,
+                    };
+                    if replace {
+                        extremum = Some((num, unit));
+                    }
+                }
+                _ => {
+                    simplifiable = false;
+                    break;
+                }
+            }
+        }
+
+        if simplifiable {
+            if let Some((num, unit)) = extremum {
+                return Ok(CalculationArg::Number(num.clone(), unit.clone()).into_value());
+            }
+        }
+
+        verify_compatible(&args.iter().collect::<Vec<_>>(), span)?;
+
+        Ok(Value::Calculation(SassCalculation { name, args }))
+    }
+
+    /// `clamp(min, value, max)`, which is `value` restricted to be between
+    /// `min` and `max` if all three are numbers with compatible units
+    ///
+    /// Fewer than three arguments are only allowed if one of them is a
+    /// string such as `var(--a)`, which may expand to several arguments.
+    pub fn clamp(
+        min: CalculationArg,
+        value: Option<CalculationArg>,
+        max: Option<CalculationArg>,
+        span: Span,
+    ) -> SassResult<Value> {
+        let (value, max) = match (value, max) {
+            (Some(value), Some(max)) => (value, max),
+            (value, max) => {
+                let args: Vec<CalculationArg> = iter::once(min).chain(value).chain(max).collect();
+                if !args.iter().any(|arg| {
+                    matches!(
+                        arg,
+                        CalculationArg::String(..) | CalculationArg::Interpolation(..)
+                    )
+                }) {
+                    return Err((
+                        format!(
+                            "3 arguments required, but only {} {} passed.",
+                            args.len(),
+                            if args.len() == 1 { "was" } else { "were" }
+                        ),
+                        span,
+                    )
+                        .into());
+                }
+                return Ok(Value::Calculation(SassCalculation {
+                    name: CalculationName::Clamp,
+                    args,
+                }));
+            }
+        };
+
+        if let (
+            CalculationArg::Number(min_num, min_unit),
+            CalculationArg::Number(num, unit),
+            CalculationArg::Number(max_num, max_unit),
+        ) = (&min, &value, &max)
+        {
+            if unit.compatible(min_unit) && unit.compatible(max_unit) {
+                let convert = |num: &Number, from: &Unit| {
+                    if from == unit {
+                        num.clone()
+                    } else {
+                        num.clone().convert(from, unit)
+                    }
+                };
+                let min_num = convert(min_num, min_unit);
+                let max_num = convert(max_num, max_unit);
+                let num = if num < &min_num {
+                    min_num
+                } else if num > &max_num {
+                    max_num
+                } else {
+                    num.clone()
+                };
+                return Ok(CalculationArg::Number(num, unit.clone()).into_value());
+            }
+        }
+
+        verify_compatible(&[&min, &value, &max], span)?;
+
+        Ok(Value::Calculation(SassCalculation {
+            name: CalculationName::Clamp,
+            args: vec![min, value, max],
+        }))
+    }
+
+    pub const fn name(&self) -> CalculationName {
+        self.name
+    }
+
+    pub fn serialize(&self, span: Span, is_compressed: bool) -> SassResult<String> {
+        Ok(format!(
+            "{}({})",
+            self.name,
+            self.args
+                .iter()
+                .map(|arg| arg.serialize(span, is_compressed, false))
+                .collect::<SassResult<Vec<String>>>()?
+                .join(if is_compressed { "," } else { ", " })
+        ))
+    }
+}
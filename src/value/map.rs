@@ -2,7 +2,6 @@ use std::{slice::Iter, vec::IntoIter};
 
 use crate::{
     common::{Brackets, ListSeparator},
-    error::SassResult,
     value::Value,
 };
 
@@ -38,17 +37,72 @@ impl SassMap {
         SassMap(elements)
     }
 
-    /// We take by value here (consuming the map) in order to
-    /// save a clone of the value, since the only place this
-    /// should be called is in a builtin function, which throws
-    /// away the map immediately anyway
-    pub fn get(self, key: &Value) -> SassResult<Option<Value>> {
-        for (k, v) in self.0 {
-            if &k == key {
-                return Ok(Some(v));
-            }
+    pub fn get(&self, key: &Value) -> Option<&Value> {
+        self.0.iter().find(|(k, ..)| k == key).map(|(.., v)| v)
+    }
+
+    /// Follow the path of nested maps given by `keys`, returning the
+    /// value at its end, if every map along the path exists
+    pub fn get_nested(&self, keys: &[Value]) -> Option<&Value> {
+        let (last, path) = keys.split_last()?;
+        let mut map = self;
+        for key in path {
+            map = match map.get(key)? {
+                Value::Map(m) => m,
+                _ => return None,
+            };
+        }
+        map.get(last)
+    }
+
+    /// Replace the value at the end of the path of nested maps given by
+    /// `keys` with the result of `modify`, which receives the previous
+    /// value or `null`
+    ///
+    /// Values along the path that are not maps are replaced by new
+    /// maps if `add_nesting` is `true`. Otherwise the map is returned
+    /// unchanged. If `keys` is empty, `modify` receives the map itself.
+    pub fn modify_nested(
+        mut self,
+        keys: &[Value],
+        add_nesting: bool,
+        modify: impl FnOnce(Value) -> Value,
+    ) -> Value {
+        let (key, rest) = match keys.split_first() {
+            Some(path) => path,
+            None => return modify(Value::Map(self)),
+        };
+
+        if rest.is_empty() {
+            let old = self.get(key).cloned().unwrap_or(Value::Null);
+            self.insert(key.clone(), modify(old));
+            return Value::Map(self);
+        }
+
+        let nested = match self.get(key).cloned().and_then(Value::try_map) {
+            Some(nested) => nested,
+            None if add_nesting => SassMap::new(),
+            None => return Value::Map(self),
+        };
+
+        self.insert(key.clone(), nested.modify_nested(rest, add_nesting, modify));
+        Value::Map(self)
+    }
+
+    /// Merge `other` into this map, recursively merging values
+    /// that are maps in both
+    pub fn deep_merge(mut self, other: SassMap) -> SassMap {
+        for (key, value) in other {
+            let merged = match self.get(&key).cloned().and_then(Value::try_map) {
+                Some(nested) => match value.clone().try_map() {
+                    Some(value) => Value::Map(nested.deep_merge(value)),
+                    None => value,
+                },
+                None => value,
+            };
+            self.insert(key, merged);
         }
-        Ok(None)
+        self
     }
 
     pub fn remove(&mut self, key: &Value) {
@@ -15,11 +15,13 @@ use crate::{
     {Cow, Token},
 };
 
+pub(crate) use calculation::{CalculationArg, CalculationName, SassCalculation};
 use css_function::is_special_function;
 pub(crate) use map::SassMap;
 pub(crate) use number::Number;
 pub(crate) use sass_function::SassFunction;
 
+mod calculation;
 pub(crate) mod css_function;
 mod map;
 mod number;
@@ -32,7 +34,7 @@ pub(crate) enum Value {
     False,
     Null,
     /// A `None` value for `Number` indicates a `NaN` value
-    Dimension(Option<Number>, Unit, bool),
+    Dimension(Option<Number>, Unit, Division),
     List(Vec<Value>, ListSeparator, Brackets),
     Color(Box<Color>),
     String(String, QuoteKind),
@@ -40,6 +42,23 @@ pub(crate) enum Value {
     ArgList(Vec<Spanned<Value>>),
     /// Returned by `get-function()`
     FunctionRef(SassFunction),
+    /// A `calc()`, `min()`, `max()`, or `clamp()` that could not be
+    /// simplified to a number
+    Calculation(SassCalculation),
+}
+
+/// How `/` treats a number, which depends on where it came from
+#[derive(Debug, Clone)]
+pub(crate) enum Division {
+    /// A number written in the stylesheet, which `/` separates from
+    /// another such number rather than dividing it, as in `font: 12px/30px`
+    Literal,
+    /// The result of an operation or a function, which `/` divides
+    Computed,
+    /// Two numbers that `/` separated rather than divided, which are
+    /// written out as such. The number is their quotient, which is only
+    /// used once it is involved in arithmetic.
+    Slash(Box<(Value, Value)>),
 }
 
 impl PartialEq for Value {
@@ -111,6 +130,13 @@ impl PartialEq for Value {
                     false
                 }
             }
+            Value::Calculation(calc1) => {
+                if let Value::Calculation(calc2) = other {
+                    calc1 == calc2
+                } else {
+                    false
+                }
+            }
         }
     }
 }
@@ -197,8 +223,22 @@ impl Value {
     }
 
     pub fn to_css_string(&self, span: Span) -> SassResult<Cow<'static, str>> {
+        self.serialize(span, false)
+    }
+
+    /// Serialize this value as it should appear in compressed output
+    pub fn to_compressed_css_string(&self, span: Span) -> SassResult<Cow<'static, str>> {
+        self.serialize(span, true)
+    }
+
+    fn serialize(&self, span: Span, is_compressed: bool) -> SassResult<Cow<'static, str>> {
         Ok(match self {
             Value::Important => Cow::const_str("!important"),
+            Value::Dimension(_, _, Division::Slash(operands)) => Cow::owned(format!(
+                "{}/{}",
+                operands.0.serialize(span, is_compressed)?,
+                operands.1.serialize(span, is_compressed)?
+            )),
             Value::Dimension(num, unit, _) => match unit {
                 Unit::Mul(..) | Unit::Div(..) => {
                     if let Some(num) = num {
@@ -211,12 +251,21 @@ impl Value {
                 }
                 _ => {
                     if let Some(num) = num {
+                        let mut num = num.to_string();
+                        if is_compressed {
+                            if num.starts_with("0.") {
+                                num.remove(0);
+                            } else if num.starts_with("-0.") {
+                                num.remove(1);
+                            }
+                        }
                         Cow::owned(format!("{}{}", num, unit))
                     } else {
                         Cow::owned(format!("NaN{}", unit))
                     }
                 }
             },
+            Value::Calculation(calc) => Cow::owned(calc.serialize(span, is_compressed)?),
             Value::Map(..) | Value::FunctionRef(..) => {
                 return Err((
                     format!("{} isn't a valid CSS value.", self.inspect(span)?),
@@ -224,24 +273,30 @@ impl Value {
                 )
                     .into())
             }
-            Value::List(vals, sep, brackets) => match brackets {
-                Brackets::None => Cow::owned(
-                    vals.iter()
-                        .filter(|x| !x.is_null())
-                        .map(|x| x.to_css_string(span))
-                        .collect::<SassResult<Vec<Cow<'static, str>>>>()?
-                        .join(sep.as_str()),
-                ),
-                Brackets::Bracketed => Cow::owned(format!(
-                    "[{}]",
-                    vals.iter()
-                        .filter(|x| !x.is_null())
-                        .map(|x| x.to_css_string(span))
-                        .collect::<SassResult<Vec<Cow<'static, str>>>>()?
-                        .join(sep.as_str()),
-                )),
-            },
-            Value::Color(c) => Cow::owned(c.to_string()),
+            Value::List(vals, sep, brackets) => {
+                let sep = if is_compressed {
+                    sep.as_compressed_str()
+                } else {
+                    sep.as_str()
+                };
+                let elems = vals
+                    .iter()
+                    .filter(|x| !x.is_null())
+                    .map(|x| x.serialize(span, is_compressed))
+                    .collect::<SassResult<Vec<Cow<'static, str>>>>()?
+                    .join(sep);
+                match brackets {
+                    Brackets::None => Cow::owned(elems),
+                    Brackets::Bracketed => Cow::owned(format!("[{}]", elems)),
+                }
+            }
+            Value::Color(c) => {
+                if is_compressed {
+                    Cow::owned(c.to_compressed_string())
+                } else {
+                    Cow::owned(c.to_string())
+                }
+            }
             Value::String(string, QuoteKind::None) => {
                 let mut after_newline = false;
                 let mut buf = String::with_capacity(string.len());
@@ -278,9 +333,9 @@ impl Value {
             Value::ArgList(args) => Cow::owned(
                 args.iter()
                     .filter(|x| !x.is_null())
-                    .map(|a| Ok(a.node.to_css_string(span)?))
+                    .map(|a| Ok(a.node.serialize(span, is_compressed)?))
                     .collect::<SassResult<Vec<Cow<'static, str>>>>()?
-                    .join(", "),
+                    .join(if is_compressed { "," } else { ", " }),
             ),
         })
     }
@@ -317,12 +372,14 @@ impl Value {
             Value::True | Value::False => "bool",
             Value::Null => "null",
             Value::Map(..) => "map",
+            Value::Calculation(..) => "calculation",
         }
     }
 
     pub fn is_special_function(&self) -> bool {
         match self {
             Value::String(s, QuoteKind::None) => is_special_function(s),
+            Value::Calculation(..) => true,
             _ => false,
         }
     }
@@ -440,10 +497,12 @@ impl Value {
                 Brackets::None => match sep {
                     ListSeparator::Space => v[0].inspect(span)?,
                     ListSeparator::Comma => Cow::owned(format!("({},)", v[0].inspect(span)?)),
+                    ListSeparator::Slash => Cow::owned(format!("({}/)", v[0].inspect(span)?)),
                 },
                 Brackets::Bracketed => match sep {
                     ListSeparator::Space => Cow::owned(format!("[{}]", v[0].inspect(span)?)),
                     ListSeparator::Comma => Cow::owned(format!("[{},]", v[0].inspect(span)?)),
+                    ListSeparator::Slash => Cow::owned(format!("[{}/]", v[0].inspect(span)?)),
                 },
             },
             Value::List(vals, sep, brackets) => Cow::owned(match brackets {
@@ -469,6 +528,11 @@ impl Value {
                     .collect::<SassResult<Vec<String>>>()?
                     .join(", ")
             )),
+            Value::Dimension(_, _, Division::Slash(operands)) => Cow::owned(format!(
+                "{}/{}",
+                operands.0.inspect(span)?,
+                operands.1.inspect(span)?
+            )),
             Value::Dimension(Some(num), unit, _) => Cow::owned(format!("{}{}", num, unit)),
             Value::Dimension(None, unit, ..) => Cow::owned(format!("NaN{}", unit)),
             Value::ArgList(args) if args.is_empty() => Cow::const_str("()"),
@@ -491,10 +555,21 @@ impl Value {
             | Value::True
             | Value::False
             | Value::Color(..)
-            | Value::String(..) => self.to_css_string(span)?,
+            | Value::String(..)
+            | Value::Calculation(..) => self.to_css_string(span)?,
         })
     }
 
+    /// This value as a map, if it is one. Empty lists are empty maps.
+    pub fn try_map(self) -> Option<SassMap> {
+        match self {
+            Value::Map(m) => Some(m),
+            Value::List(v, ..) if v.is_empty() => Some(SassMap::new()),
+            Value::ArgList(v) if v.is_empty() => Some(SassMap::new()),
+            _ => None,
+        }
+    }
+
     pub fn as_list(self) -> Vec<Value> {
         match self {
             Value::List(v, ..) => v,
@@ -540,10 +615,14 @@ impl Value {
             at_root: parser.at_root,
             at_root_has_selector: parser.at_root_has_selector,
             extender: parser.extender,
+            media_queries: parser.media_queries,
             content_scopes: parser.content_scopes,
             options: parser.options,
             modules: parser.modules,
             module_config: parser.module_config,
+            forwarded: parser.forwarded,
+            call_stack: parser.call_stack,
+            custom_function_args: parser.custom_function_args,
         }
         .parse_selector(allows_parent, true, String::new())?
         .0)
@@ -578,6 +657,7 @@ impl Value {
                             }
                         }
                     }
+                    ListSeparator::Slash => return Ok(None),
                 }
 
                 result.join(sep.as_str())
@@ -20,10 +20,13 @@ mod integer;
 
 const PRECISION: usize = 10;
 
-#[derive(Clone, Eq, PartialEq)]
+#[derive(Clone)]
 pub(crate) enum Number {
     Small(Rational64),
     Big(Box<BigRational>),
+    /// Infinity, negative infinity, or NaN, which result from dividing by
+    /// zero and can't be represented as a ratio
+    NonFinite(f64),
 }
 
 impl Number {
@@ -35,13 +38,20 @@ impl Number {
         Number::Big(Box::new(val))
     }
 
+    #[allow(clippy::cast_possible_truncation)]
     pub fn to_integer(&self) -> Integer {
         match self {
             Self::Small(val) => Integer::Small(val.to_integer()),
             Self::Big(val) => Integer::Big(val.to_integer()),
+            // saturates at the bounds of `i64`, with NaN as zero
+            Self::NonFinite(val) => Integer::Small(*val as i64),
         }
     }
 
+    pub const fn is_finite(&self) -> bool {
+        !matches!(self, Self::NonFinite(..))
+    }
+
     pub fn small_ratio<A: Into<i64>, B: Into<i64>>(a: A, b: B) -> Self {
         Number::new_small(Rational64::new(a.into(), b.into()))
     }
@@ -55,6 +65,7 @@ impl Number {
         match self {
             Self::Small(val) => Self::Small(val.round()),
             Self::Big(val) => Self::Big(Box::new(val.round())),
+            Self::NonFinite(val) => Self::NonFinite(val.round()),
         }
     }
 
@@ -62,6 +73,7 @@ impl Number {
         match self {
             Self::Small(val) => Self::Small(val.ceil()),
             Self::Big(val) => Self::Big(Box::new(val.ceil())),
+            Self::NonFinite(val) => Self::NonFinite(val.ceil()),
         }
     }
 
@@ -69,6 +81,7 @@ impl Number {
         match self {
             Self::Small(val) => Self::Small(val.floor()),
             Self::Big(val) => Self::Big(Box::new(val.floor())),
+            Self::NonFinite(val) => Self::NonFinite(val.floor()),
         }
     }
 
@@ -76,6 +89,7 @@ impl Number {
         match self {
             Self::Small(val) => Self::Small(val.abs()),
             Self::Big(val) => Self::Big(Box::new(val.abs())),
+            Self::NonFinite(val) => Self::NonFinite(val.abs()),
         }
     }
 
@@ -83,6 +97,7 @@ impl Number {
         match self {
             Self::Small(v) => !v.is_integer(),
             Self::Big(v) => !v.is_integer(),
+            Self::NonFinite(..) => true,
         }
     }
 
@@ -90,6 +105,7 @@ impl Number {
         match self {
             Self::Small(v) => Number::new_small(v.fract()),
             Self::Big(v) => Number::new_big(v.fract()),
+            Self::NonFinite(v) => Number::NonFinite(v.fract()),
         }
     }
 
@@ -112,13 +128,19 @@ impl Number {
     }
 
     #[allow(clippy::cast_precision_loss)]
-    fn as_float(self) -> Option<f64> {
+    pub fn as_float(self) -> Option<f64> {
         Some(match self {
             Number::Small(n) => ((*n.numer() as f64) / (*n.denom() as f64)),
             Number::Big(n) => ((n.numer().to_f64()?) / (n.denom().to_f64()?)),
+            Number::NonFinite(n) => n,
         })
     }
 
+    /// This number as a float, which may lose precision
+    fn as_f64(&self) -> f64 {
+        self.clone().as_float().unwrap_or(f64::NAN)
+    }
+
     pub fn sqrt(self) -> Option<Self> {
         Some(Number::Big(Box::new(BigRational::from_float(
             self.as_float()?.sqrt(),
@@ -205,6 +227,7 @@ impl Zero for Number {
         match self {
             Self::Small(v) => v.is_zero(),
             Self::Big(v) => v.is_zero(),
+            Self::NonFinite(..) => false,
         }
     }
 }
@@ -218,6 +241,7 @@ impl One for Number {
         match self {
             Self::Small(v) => v.is_one(),
             Self::Big(v) => v.is_one(),
+            Self::NonFinite(..) => false,
         }
     }
 }
@@ -255,6 +279,7 @@ impl Signed for Number {
         match self {
             Self::Small(v) => v.is_positive(),
             Self::Big(v) => v.is_positive(),
+            Self::NonFinite(v) => *v > 0.0,
         }
     }
 
@@ -262,6 +287,7 @@ impl Signed for Number {
         match self {
             Self::Small(v) => v.is_negative(),
             Self::Big(v) => v.is_negative(),
+            Self::NonFinite(v) => *v < 0.0,
         }
     }
 }
@@ -296,10 +322,9 @@ impl From<i64> for Number {
     }
 }
 
-#[allow(clippy::fallible_impl_from)]
 impl From<f64> for Number {
     fn from(b: f64) -> Self {
-        Number::Big(Box::new(BigRational::from_float(b).unwrap()))
+        BigRational::from_float(b).map_or(Number::NonFinite(b), Number::new_big)
     }
 }
 
@@ -314,6 +339,7 @@ impl fmt::Debug for Number {
         match self {
             Self::Small(..) => write!(f, "Number::Small( {} )", self),
             Self::Big(..) => write!(f, "Number::Big( {} )", self),
+            Self::NonFinite(..) => write!(f, "Number::NonFinite( {} )", self),
         }
     }
 }
@@ -333,6 +359,7 @@ impl ToPrimitive for Number {
                 }
                 n.to_u64()
             }
+            Self::NonFinite(..) => None,
         }
     }
 
@@ -350,12 +377,23 @@ impl ToPrimitive for Number {
                 }
                 n.to_i64()
             }
+            Self::NonFinite(..) => None,
         }
     }
 }
 
 impl Display for Number {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Self::NonFinite(n) = self {
+            return f.write_str(if n.is_nan() {
+                "NaN"
+            } else if n.is_sign_negative() {
+                "-Infinity"
+            } else {
+                "Infinity"
+            });
+        }
+
         let mut whole = self.to_integer().abs();
         let has_decimal = self.is_decimal();
         let mut frac = self.abs().fract();
@@ -414,10 +452,20 @@ impl Display for Number {
     }
 }
 
+// the same value may be stored as either a small or big rational
+impl PartialEq for Number {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Number {}
+
 impl PartialOrd for Number {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         match self {
             Self::Small(val1) => match other {
+                Self::NonFinite(val2) => self.as_f64().partial_cmp(val2),
                 Self::Small(val2) => val1.partial_cmp(val2),
                 Self::Big(val2) => {
                     let tuple: (i64, i64) = (*val1).into();
@@ -426,6 +474,7 @@ impl PartialOrd for Number {
                 }
             },
             Self::Big(val1) => match other {
+                Self::NonFinite(val2) => self.as_f64().partial_cmp(val2),
                 Self::Small(val2) => {
                     let tuple: (i64, i64) = (*val2).into();
                     (**val1).partial_cmp(&BigRational::new_raw(
@@ -435,6 +484,7 @@ impl PartialOrd for Number {
                 }
                 Self::Big(val2) => val1.partial_cmp(val2),
             },
+            Self::NonFinite(val1) => val1.partial_cmp(&other.as_f64()),
         }
     }
 }
@@ -443,6 +493,7 @@ impl Ord for Number {
     fn cmp(&self, other: &Self) -> Ordering {
         match self {
             Self::Small(val1) => match other {
+                Self::NonFinite(val2) => self.as_f64().total_cmp(val2),
                 Self::Small(val2) => val1.cmp(val2),
                 Self::Big(val2) => {
                     let tuple: (i64, i64) = (*val1).into();
@@ -450,6 +501,7 @@ impl Ord for Number {
                 }
             },
             Self::Big(val1) => match other {
+                Self::NonFinite(val2) => self.as_f64().total_cmp(val2),
                 Self::Small(val2) => {
                     let tuple: (i64, i64) = (*val2).into();
                     (**val1).cmp(&BigRational::new_raw(
@@ -459,6 +511,7 @@ impl Ord for Number {
                 }
                 Self::Big(val2) => val1.cmp(val2),
             },
+            Self::NonFinite(val1) => val1.total_cmp(&other.as_f64()),
         }
     }
 }
@@ -469,6 +522,7 @@ impl Add for Number {
     fn add(self, other: Self) -> Self {
         match self {
             Self::Small(val1) => match other {
+                Self::NonFinite(val2) => Number::from(Number::Small(val1).as_f64() + val2),
                 Self::Small(val2) => match val1.checked_add(&val2) {
                     Some(v) => Self::Small(v),
                     None => {
@@ -491,6 +545,7 @@ impl Add for Number {
                 }
             },
             Self::Big(val1) => match other {
+                Self::NonFinite(val2) => Number::from(Number::Big(val1).as_f64() + val2),
                 Self::Big(val2) => Self::Big(Box::new(*val1 + *val2)),
                 Self::Small(val2) => {
                     let tuple: (i64, i64) = val2.into();
@@ -500,6 +555,7 @@ impl Add for Number {
                     ))
                 }
             },
+            Self::NonFinite(val1) => Number::from(val1 + other.as_f64()),
         }
     }
 }
@@ -510,6 +566,7 @@ impl Add<&Self> for Number {
     fn add(self, other: &Self) -> Self {
         match self {
             Self::Small(val1) => match other {
+                Self::NonFinite(val2) => Number::from(Number::Small(val1).as_f64() + *val2),
                 Self::Small(val2) => match val1.checked_add(val2) {
                     Some(v) => Self::Small(v),
                     None => {
@@ -533,6 +590,7 @@ impl Add<&Self> for Number {
                 }
             },
             Self::Big(val1) => match other {
+                Self::NonFinite(val2) => Number::from(Number::Big(val1).as_f64() + *val2),
                 Self::Big(val2) => Self::Big(Box::new(*val1 + *val2.clone())),
                 Self::Small(val2) => {
                     let tuple: (i64, i64) = (*val2).into();
@@ -541,6 +599,7 @@ impl Add<&Self> for Number {
                     ))
                 }
             },
+            Self::NonFinite(val1) => Number::from(val1 + other.as_f64()),
         }
     }
 }
@@ -558,6 +617,7 @@ impl Sub for Number {
     fn sub(self, other: Self) -> Self {
         match self {
             Self::Small(val1) => match other {
+                Self::NonFinite(val2) => Number::from(Number::Small(val1).as_f64() - val2),
                 Self::Small(val2) => match val1.checked_sub(&val2) {
                     Some(v) => Self::Small(v),
                     None => {
@@ -580,6 +640,7 @@ impl Sub for Number {
                 }
             },
             Self::Big(val1) => match other {
+                Self::NonFinite(val2) => Number::from(Number::Big(val1).as_f64() - val2),
                 Self::Big(val2) => Self::Big(Box::new(*val1 - *val2)),
                 Self::Small(val2) => {
                     let tuple: (i64, i64) = val2.into();
@@ -588,6 +649,7 @@ impl Sub for Number {
                     ))
                 }
             },
+            Self::NonFinite(val1) => Number::from(val1 - other.as_f64()),
         }
     }
 }
@@ -605,6 +667,7 @@ impl Mul for Number {
     fn mul(self, other: Self) -> Self {
         match self {
             Self::Small(val1) => match other {
+                Self::NonFinite(val2) => Number::from(Number::Small(val1).as_f64() * val2),
                 Self::Small(val2) => match val1.checked_mul(&val2) {
                     Some(v) => Self::Small(v),
                     None => {
@@ -627,6 +690,7 @@ impl Mul for Number {
                 }
             },
             Self::Big(val1) => match other {
+                Self::NonFinite(val2) => Number::from(Number::Big(val1).as_f64() * val2),
                 Self::Big(val2) => Self::Big(Box::new(*val1 * *val2)),
                 Self::Small(val2) => {
                     let tuple: (i64, i64) = val2.into();
@@ -635,6 +699,7 @@ impl Mul for Number {
                     ))
                 }
             },
+            Self::NonFinite(val1) => Number::from(val1 * other.as_f64()),
         }
     }
 }
@@ -642,10 +707,12 @@ impl Mul for Number {
 impl Mul<i64> for Number {
     type Output = Self;
 
+    #[allow(clippy::cast_precision_loss)]
     fn mul(self, other: i64) -> Self {
         match self {
             Self::Small(val1) => Self::Small(val1 * other),
             Self::Big(val1) => Self::Big(Box::new(*val1 * BigInt::from(other))),
+            Self::NonFinite(val1) => Number::from(val1 * other as f64),
         }
     }
 }
@@ -668,8 +735,13 @@ impl Div for Number {
     type Output = Self;
 
     fn div(self, other: Self) -> Self {
+        // dividing by zero results in infinity, or NaN if this is also zero
+        if other.is_zero() {
+            return Number::from(self.as_f64() / 0.0);
+        }
         match self {
             Self::Small(val1) => match other {
+                Self::NonFinite(val2) => Number::from(Number::Small(val1).as_f64() / val2),
                 Self::Small(val2) => match val1.checked_div(&val2) {
                     Some(v) => Self::Small(v),
                     None => {
@@ -692,6 +764,7 @@ impl Div for Number {
                 }
             },
             Self::Big(val1) => match other {
+                Self::NonFinite(val2) => Number::from(Number::Big(val1).as_f64() / val2),
                 Self::Big(val2) => Self::Big(Box::new(*val1 / *val2)),
                 Self::Small(val2) => {
                     let tuple: (i64, i64) = val2.into();
@@ -700,6 +773,7 @@ impl Div for Number {
                     ))
                 }
             },
+            Self::NonFinite(val1) => Number::from(val1 / other.as_f64()),
         }
     }
 }
@@ -715,8 +789,13 @@ impl Rem for Number {
     type Output = Self;
 
     fn rem(self, other: Self) -> Self {
+        // the remainder of dividing by zero is NaN
+        if other.is_zero() {
+            return Number::NonFinite(f64::NAN);
+        }
         match self {
             Self::Small(val1) => match other {
+                Self::NonFinite(val2) => Number::from(Number::Small(val1).as_f64() % val2),
                 // todo: checked_rem for ratio?
                 Self::Small(val2) => {
                     let tuple1: (i64, i64) = val1.into();
@@ -734,6 +813,7 @@ impl Rem for Number {
                 }
             },
             Self::Big(val1) => match other {
+                Self::NonFinite(val2) => Number::from(Number::Big(val1).as_f64() % val2),
                 Self::Big(val2) => Self::Big(Box::new(*val1 % *val2)),
                 Self::Small(val2) => {
                     let tuple: (i64, i64) = val2.into();
@@ -742,6 +822,7 @@ impl Rem for Number {
                     ))
                 }
             },
+            Self::NonFinite(val1) => Number::from(val1 % other.as_f64()),
         }
     }
 }
@@ -760,6 +841,7 @@ impl Neg for Number {
         match self {
             Self::Small(v) => Self::Small(-v),
             Self::Big(v) => Self::Big(Box::new(-*v)),
+            Self::NonFinite(v) => Self::NonFinite(-v),
         }
     }
 }
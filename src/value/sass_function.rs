@@ -8,6 +8,9 @@
 //!
 //! Builtin functions are those that have been implemented in rust and are
 //! in the global scope.
+//!
+//! Custom functions are those that have been implemented in rust by the
+//! host application and registered through `Options::add_function`.
 
 use std::fmt;
 
@@ -26,6 +29,7 @@ use crate::{
 pub(crate) enum SassFunction {
     Builtin(Builtin, Identifier),
     UserDefined(Box<Function>, Identifier),
+    Custom(Identifier),
 }
 
 impl SassFunction {
@@ -34,24 +38,30 @@ impl SassFunction {
     /// Used mainly in debugging and `inspect()`
     pub fn name(&self) -> &Identifier {
         match self {
-            Self::Builtin(_, name) | Self::UserDefined(_, name) => name,
+            Self::Builtin(_, name) | Self::UserDefined(_, name) | Self::Custom(name) => name,
         }
     }
 
-    /// Whether the function is builtin or user-defined
+    /// Whether the function is builtin, user-defined, or custom
     ///
     /// Used only in `std::fmt::Debug` for `SassFunction`
     fn kind(&self) -> &'static str {
         match &self {
             Self::Builtin(..) => "Builtin",
             Self::UserDefined(..) => "UserDefined",
+            Self::Custom(..) => "Custom",
         }
     }
 
     pub fn call(self, args: CallArgs, parser: &mut Parser<'_>) -> SassResult<Value> {
         match self {
             Self::Builtin(f, ..) => f.0(args, parser),
-            Self::UserDefined(f, ..) => parser.eval_function(*f, args),
+            Self::UserDefined(f, name) => {
+                parser.with_call(format!("{}()", name), args.span(), |parser| {
+                    parser.eval_function(*f, args)
+                })
+            }
+            Self::Custom(name) => parser.eval_custom_function(name, args),
         }
     }
 }
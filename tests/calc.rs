@@ -0,0 +1,259 @@
+#[macro_use]
+mod macros;
+
+test!(
+    simplifies_same_units,
+    "a {\n  color: calc(1px + 2px);\n}\n",
+    "a {\n  color: 3px;\n}\n"
+);
+test!(
+    simplifies_compatible_units,
+    "a {\n  color: calc(1in - 48px);\n}\n",
+    "a {\n  color: 0.5in;\n}\n"
+);
+test!(
+    simplifies_multiplication_and_division,
+    "a {\n  color: calc(2px * 3 / 4);\n}\n",
+    "a {\n  color: 1.5px;\n}\n"
+);
+test!(
+    keeps_incompatible_units,
+    "a {\n  color: calc(1px + 1em);\n}\n",
+    "a {\n  color: calc(1px + 1em);\n}\n"
+);
+test!(
+    keeps_percent,
+    "a {\n  color: calc(100% - 20px);\n}\n",
+    "a {\n  color: calc(100% - 20px);\n}\n"
+);
+test!(
+    simplifies_within_operation,
+    "a {\n  color: calc(1em + 2px * 3);\n}\n",
+    "a {\n  color: calc(1em + 6px);\n}\n"
+);
+test!(
+    parenthesizes_lower_precedence_left,
+    "a {\n  color: calc((1px + 1em) * 2);\n}\n",
+    "a {\n  color: calc((1px + 1em) * 2);\n}\n"
+);
+test!(
+    parenthesizes_lower_precedence_right,
+    "a {\n  color: calc(2 * (1px + 1em));\n}\n",
+    "a {\n  color: calc(2 * (1px + 1em));\n}\n"
+);
+test!(
+    parenthesizes_right_of_minus,
+    "a {\n  color: calc(1px - (1em - 1%));\n}\n",
+    "a {\n  color: calc(1px - (1em - 1%));\n}\n"
+);
+test!(
+    removes_redundant_parens,
+    "a {\n  color: calc((1px) + (1em * var(--a)));\n}\n",
+    "a {\n  color: calc(1px + 1em * var(--a));\n}\n"
+);
+test!(
+    variable,
+    "$a: 10px;\na {\n  color: calc($a + 1em);\n}\n",
+    "a {\n  color: calc(10px + 1em);\n}\n"
+);
+test!(
+    variables_simplified,
+    "$a: 10px;\n$b: 2;\na {\n  color: calc($a * $b);\n}\n",
+    "a {\n  color: 20px;\n}\n"
+);
+test!(
+    module_variable,
+    "@use \"sass:math\";\na {\n  color: calc(math.$pi * 1em + 1px);\n}\n",
+    "a {\n  color: calc(3.1415926536em + 1px);\n}\n"
+);
+test!(
+    function_call,
+    "@function double($n) {\n  @return $n * 2;\n}\na {\n  color: calc(double(1em) + 1px);\n}\n",
+    "a {\n  color: calc(2em + 1px);\n}\n"
+);
+test!(
+    var_function,
+    "a {\n  color: calc(var(--a) * 2);\n}\n",
+    "a {\n  color: calc(var(--a) * 2);\n}\n"
+);
+test!(
+    interpolation,
+    "$a: \"1px + 1em\";\na {\n  color: calc(#{$a});\n}\n",
+    "a {\n  color: calc(1px + 1em);\n}\n"
+);
+test!(
+    interpolation_operand_is_parenthesized,
+    "$a: \"1px + 1em\";\na {\n  color: calc(#{$a} * 2);\n}\n",
+    "a {\n  color: calc((1px + 1em) * 2);\n}\n"
+);
+test!(
+    nested_calc_is_removed,
+    "a {\n  color: calc(calc(1px + 1em));\n}\n",
+    "a {\n  color: calc(1px + 1em);\n}\n"
+);
+test!(
+    nested_calc_operand_is_parenthesized,
+    "a {\n  color: calc(2 * calc(1px + 1em));\n}\n",
+    "a {\n  color: calc(2 * (1px + 1em));\n}\n"
+);
+test!(
+    calculation_in_variable,
+    "$a: calc(1px + 1em);\na {\n  color: calc($a / 2);\n}\n",
+    "a {\n  color: calc((1px + 1em) / 2);\n}\n"
+);
+test!(
+    nested_min,
+    "a {\n  color: calc(min(1px, 2px) + 1em);\n}\n",
+    "a {\n  color: calc(1px + 1em);\n}\n"
+);
+test!(
+    clamp_simplified,
+    "a {\n  color: clamp(1px, 5px, 3px);\n}\n",
+    "a {\n  color: 3px;\n}\n"
+);
+test!(
+    clamp_compatible_units,
+    "a {\n  color: clamp(1in, 100px, 2in);\n}\n",
+    "a {\n  color: 100px;\n}\n"
+);
+test!(
+    clamp_not_simplified,
+    "a {\n  color: clamp(1px, 50%, 3em);\n}\n",
+    "a {\n  color: clamp(1px, 50%, 3em);\n}\n"
+);
+test!(
+    clamp_with_operation,
+    "a {\n  color: clamp(1rem, 1rem + 2vw, 3rem);\n}\n",
+    "a {\n  color: clamp(1rem, 1rem + 2vw, 3rem);\n}\n"
+);
+test!(
+    clamp_var_may_be_several_args,
+    "a {\n  color: clamp(var(--a), 2px);\n}\n",
+    "a {\n  color: clamp(var(--a), 2px);\n}\n"
+);
+test!(
+    interpolation_is_plain_css,
+    "a {\n  color: calc(#{1px}px + 1);\n}\n",
+    "a {\n  color: calc(1pxpx + 1);\n}\n"
+);
+test!(
+    invalid_min_is_function_call,
+    "a {\n  color: min(1px +2px, 4px);\n}\n",
+    "a {\n  color: 3px;\n}\n"
+);
+test!(
+    type_of,
+    "a {\n  color: type-of(calc(1px + 1em));\n}\n",
+    "a {\n  color: calculation;\n}\n"
+);
+test!(
+    type_of_simplified,
+    "a {\n  color: type-of(calc(1px + 1px));\n}\n",
+    "a {\n  color: number;\n}\n"
+);
+test!(
+    equality,
+    "a {\n  color: calc(1px + 1em) == calc(1px + 1em);\n}\n",
+    "a {\n  color: true;\n}\n"
+);
+test!(
+    inequality,
+    "a {\n  color: calc(1px + 1em) == calc(1px + 2em);\n}\n",
+    "a {\n  color: false;\n}\n"
+);
+test!(
+    inspect,
+    "a {\n  color: inspect(calc(1px + 1em));\n}\n",
+    "a {\n  color: calc(1px + 1em);\n}\n"
+);
+test!(
+    concatenated_with_string,
+    "a {\n  color: calc(1px + 1em) + \"a\";\n}\n",
+    "a {\n  color: \"calc(1px + 1em)a\";\n}\n"
+);
+test!(
+    within_rgb_is_special_function,
+    "a {\n  color: rgb(calc(1px + 1em), 2, 3);\n}\n",
+    "a {\n  color: rgb(calc(1px + 1em), 2, 3);\n}\n"
+);
+test!(
+    divide_by_zero_is_infinity,
+    "a {\n  color: calc(1px / 0);\n}\n",
+    "a {\n  color: calc(infinity * 1px);\n}\n"
+);
+test!(
+    divide_negative_by_zero_is_negative_infinity,
+    "a {\n  color: calc(-1 / 0);\n}\n",
+    "a {\n  color: calc(-infinity);\n}\n"
+);
+test!(
+    divide_zero_by_zero_is_nan,
+    "a {\n  color: calc(0px / 0);\n}\n",
+    "a {\n  color: calc(NaN * 1px);\n}\n"
+);
+test!(
+    max_of_infinity,
+    "a {\n  color: max(1px / 0, 2px);\n}\n",
+    "a {\n  color: calc(infinity * 1px);\n}\n"
+);
+test!(
+    compressed_infinity,
+    "a {\n  color: calc(1px / 0);\n}\n",
+    "a{color:calc(infinity*1px)}",
+    grass::Options::default().style(grass::OutputStyle::Compressed)
+);
+test!(
+    compressed,
+    "a {\n  color: calc((1px + 1em) * 2 - 3%);\n}\n",
+    "a{color:calc((1px + 1em)*2 - 3%)}",
+    grass::Options::default().style(grass::OutputStyle::Compressed)
+);
+error!(
+    incompatible_units,
+    "a {\n  color: calc(1px + 1s);\n}\n", "Error: 1px and 1s are incompatible."
+);
+error!(
+    unitless_and_unit,
+    "a {\n  color: calc(1 + 1px);\n}\n", "Error: 1 and 1px are incompatible."
+);
+error!(
+    complex_units,
+    "a {\n  color: calc(1px * 1px + 1em);\n}\n",
+    "Error: Number 1px*px isn't compatible with CSS calculations."
+);
+error!(
+    quoted_string,
+    "$a: \"a\";\na {\n  color: calc($a + 1px);\n}\n",
+    "Error: Value \"a\" can't be used in a calculation."
+);
+error!(
+    operator_without_whitespace,
+    "a {\n  color: calc(1px +2px);\n}\n",
+    "Error: \"+\" and \"-\" must be surrounded by whitespace in calculations."
+);
+error!(
+    calc_no_args,
+    "a {\n  color: calc();\n}\n", "Error: Expected number, variable, function, or calculation."
+);
+error!(
+    calc_two_args,
+    "a {\n  color: calc(1px, 2px);\n}\n", "Error: expected \"+\", \"-\", \"*\", \"/\", or \")\"."
+);
+error!(
+    clamp_two_args,
+    "a {\n  color: clamp(1px, 2px);\n}\n", "Error: 3 arguments required, but only 2 were passed."
+);
+error!(
+    unknown_identifier,
+    "a {\n  color: calc(a);\n}\n", "Error: Expected \"(\" or \".\"."
+);
+error!(
+    addition,
+    "a {\n  color: calc(1px + 1em) + 1px;\n}\n",
+    "Error: Undefined operation \"calc(1px + 1em) + 1px\"."
+);
+error!(
+    subtraction,
+    "a {\n  color: calc(1px + 1em) - 1px;\n}\n",
+    "Error: Undefined operation \"calc(1px + 1em) - 1px\"."
+);
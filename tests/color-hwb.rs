@@ -0,0 +1,173 @@
+#[macro_use]
+mod macros;
+
+test!(
+    hwb_basic,
+    "@use \"sass:color\";\na {\n  color: color.hwb(120, 30%, 40%);\n}\n",
+    "a {\n  color: #4d994d;\n}\n"
+);
+test!(
+    hwb_space_separated_channels,
+    "@use \"sass:color\";\na {\n  color: color.hwb(120 30% 40%);\n}\n",
+    "a {\n  color: #4d994d;\n}\n"
+);
+test!(
+    hwb_with_alpha,
+    "@use \"sass:color\";\na {\n  color: color.hwb(0, 0%, 0%, 0.5);\n}\n",
+    "a {\n  color: rgba(255, 0, 0, 0.5);\n}\n"
+);
+test!(
+    hwb_with_percent_alpha,
+    "@use \"sass:color\";\na {\n  color: color.hwb($hue: 30, $whiteness: 10%, $blackness: 50%, $alpha: 50%);\n}\n",
+    "a {\n  color: rgba(128, 77, 26, 0.5);\n}\n"
+);
+test!(
+    hwb_whiteness_and_blackness_above_100_percent_is_gray,
+    "@use \"sass:color\";\na {\n  color: color.hwb(0, 60%, 60%);\n}\n",
+    "a {\n  color: gray;\n}\n"
+);
+test!(
+    hwb_full_blackness,
+    "@use \"sass:color\";\na {\n  color: color.hwb(210, 0%, 100%);\n}\n",
+    "a {\n  color: black;\n}\n"
+);
+test!(
+    hwb_negative_hue,
+    "@use \"sass:color\";\na {\n  color: color.hwb(-90, 10%, 20%);\n}\n",
+    "a {\n  color: #731acc;\n}\n"
+);
+test!(
+    whiteness,
+    "@use \"sass:color\";\na {\n  color: color.whiteness(#4d994d);\n}\n",
+    "a {\n  color: 30.1960784314%;\n}\n"
+);
+test!(
+    whiteness_of_white,
+    "@use \"sass:color\";\na {\n  color: color.whiteness(white);\n}\n",
+    "a {\n  color: 100%;\n}\n"
+);
+test!(
+    blackness,
+    "@use \"sass:color\";\na {\n  color: color.blackness(#4d994d);\n}\n",
+    "a {\n  color: 40%;\n}\n"
+);
+test!(
+    blackness_of_black,
+    "@use \"sass:color\";\na {\n  color: color.blackness(black);\n}\n",
+    "a {\n  color: 100%;\n}\n"
+);
+test!(
+    change_whiteness,
+    "@use \"sass:color\";\na {\n  color: color.change(#ff0000, $whiteness: 20%);\n}\n",
+    "a {\n  color: #ff3333;\n}\n"
+);
+test!(
+    change_hue_keeps_whiteness_and_blackness,
+    "@use \"sass:color\";\na {\n  color: color.change(#4d994d, $hue: 240, $whiteness: 30%);\n}\n",
+    "a {\n  color: #4d4d99;\n}\n"
+);
+test!(
+    adjust_blackness,
+    "@use \"sass:color\";\na {\n  color: color.adjust(#4d994d, $blackness: 10%);\n}\n",
+    "a {\n  color: #4d804d;\n}\n"
+);
+test!(
+    scale_blackness,
+    "@use \"sass:color\";\na {\n  color: color.scale(#ff0000, $blackness: 50%);\n}\n",
+    "a {\n  color: maroon;\n}\n"
+);
+test!(
+    global_scale_color_whiteness,
+    "a {\n  color: scale-color(#ff0000, $whiteness: 20%);\n}\n",
+    "a {\n  color: #ff3333;\n}\n"
+);
+error!(
+    hwb_whiteness_without_percent,
+    "@use \"sass:color\";\na {\n  color: color.hwb(0, 10, 10%);\n}\n",
+    "Error: $whiteness: Expected 10 to have unit \"%\"."
+);
+error!(
+    hwb_whiteness_above_100_percent,
+    "@use \"sass:color\";\na {\n  color: color.hwb(0, 120%, 10%);\n}\n",
+    "Error: $whiteness: Expected 120% to be within 0% and 100%."
+);
+error!(
+    hwb_missing_blackness,
+    "@use \"sass:color\";\na {\n  color: color.hwb(0, 10%);\n}\n",
+    "Error: Missing argument $blackness."
+);
+error!(
+    hwb_missing_element_blackness,
+    "@use \"sass:color\";\na {\n  color: color.hwb(0 10%);\n}\n",
+    "Error: Missing element $blackness."
+);
+error!(
+    hwb_comma_separated_channels,
+    "@use \"sass:color\";\na {\n  color: color.hwb((0, 10%, 10%));\n}\n",
+    "Error: $channels must be a space-separated list."
+);
+error!(
+    hwb_too_many_channels,
+    "@use \"sass:color\";\na {\n  color: color.hwb(0 10% 10% 10%);\n}\n",
+    "Error: Only 3 elements allowed, but 4 were passed."
+);
+error!(
+    hwb_alpha_with_unit,
+    "@use \"sass:color\";\na {\n  color: color.hwb(0, 10%, 10%, 1px);\n}\n",
+    "Error: $alpha: Expected 1px to have no units or \"%\"."
+);
+error!(
+    change_rgb_and_hwb,
+    "@use \"sass:color\";\na {\n  color: color.change(red, $red: 10, $whiteness: 10%);\n}\n",
+    "Error: RGB parameters may not be passed along with HWB parameters."
+);
+error!(
+    adjust_hsl_and_hwb,
+    "@use \"sass:color\";\na {\n  color: color.adjust(red, $saturation: 10%, $blackness: 10%);\n}\n",
+    "Error: HSL parameters may not be passed along with HWB parameters."
+);
+error!(
+    scale_rgb_and_hsl,
+    "@use \"sass:color\";\na {\n  color: color.scale(red, $red: 10%, $lightness: 10%);\n}\n",
+    "Error: RGB parameters may not be passed along with HSL parameters."
+);
+test!(
+    hwb_slash_list_channels,
+    "@use \"sass:color\";\n@use \"sass:list\";\na {\n  color: color.hwb(list.slash(0 0% 0%, 0.5));\n}\n",
+    "a {\n  color: rgba(255, 0, 0, 0.5);\n}\n"
+);
+test!(
+    hwb_slash_separated_alpha,
+    "@use \"sass:color\";\na {\n  color: color.hwb(120 30% 40% / 0.5);\n}\n",
+    "a {\n  color: rgba(77, 153, 77, 0.5);\n}\n"
+);
+test!(
+    hwb_slash_separated_alpha_without_spaces,
+    "@use \"sass:color\";\na {\n  color: color.hwb(120 30% 40%/50%);\n}\n",
+    "a {\n  color: rgba(77, 153, 77, 0.5);\n}\n"
+);
+error!(
+    hwb_slash_list_too_many_elements,
+    "@use \"sass:color\";\n@use \"sass:list\";\na {\n  color: color.hwb(list.slash(0, 0%, 0%));\n}\n",
+    "Error: Only 2 slash-separated elements allowed, but 3 were passed."
+);
+error!(
+    hwb_nan_hue,
+    "@use \"sass:color\";\n@use \"sass:math\";\na {\n  color: color.hwb(math.div(0, 0), 0%, 0%);\n}\n",
+    "Error: $hue: Expected NaN to be a finite number."
+);
+error!(
+    hwb_nan_whiteness,
+    "@use \"sass:color\";\n@use \"sass:math\";\na {\n  color: color.hwb(0, math.div(0%, 0), 0%);\n}\n",
+    "Error: $whiteness: Expected NaN% to be a finite number."
+);
+error!(
+    hwb_nan_alpha,
+    "@use \"sass:color\";\n@use \"sass:math\";\na {\n  color: color.hwb(0, 0%, 0%, math.div(0, 0));\n}\n",
+    "Error: $alpha: Expected NaN to be a finite number."
+);
+error!(
+    adjust_nan_whiteness,
+    "@use \"sass:color\";\n@use \"sass:math\";\na {\n  color: color.adjust(red, $whiteness: math.div(0%, 0));\n}\n",
+    "Error: $whiteness: Expected NaN% to be a finite number."
+);
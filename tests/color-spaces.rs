@@ -0,0 +1,259 @@
+#[macro_use]
+mod macros;
+
+test!(
+    lab,
+    "a {\n  color: lab(50% 40 59.5);\n}\n",
+    "a {\n  color: lab(50% 40 59.5);\n}\n"
+);
+test!(
+    lab_with_alpha,
+    "a {\n  color: lab(50% 40 59.5 / 0.5);\n}\n",
+    "a {\n  color: lab(50% 40 59.5 / 0.5);\n}\n"
+);
+test!(
+    lab_percent_channels,
+    "a {\n  color: lab(50% 40% -20%);\n}\n",
+    "a {\n  color: lab(50% 50 -25);\n}\n"
+);
+test!(
+    lch_turn_hue,
+    "a {\n  color: lch(50% 40 0.25turn);\n}\n",
+    "a {\n  color: lch(50% 40 90deg);\n}\n"
+);
+test!(
+    oklab,
+    "a {\n  color: oklab(50% 0.1 -0.1);\n}\n",
+    "a {\n  color: oklab(50% 0.1 -0.1);\n}\n"
+);
+test!(
+    oklch_unitless_lightness,
+    "a {\n  color: oklch(0.5 0.1 120);\n}\n",
+    "a {\n  color: oklch(50% 0.1 120deg);\n}\n"
+);
+test!(
+    oklch_percent_alpha,
+    "a {\n  color: oklch(50% 0.1 120 / 50%);\n}\n",
+    "a {\n  color: oklch(50% 0.1 120deg / 0.5);\n}\n"
+);
+test!(
+    display_p3,
+    "a {\n  color: color(display-p3 0.5 0.2 0.1);\n}\n",
+    "a {\n  color: color(display-p3 0.5 0.2 0.1);\n}\n"
+);
+test!(
+    unsupported_predefined_space_is_plain_css,
+    "a {\n  color: color(rec2020 0.1 0.2 0.3);\n}\n"
+);
+test!(
+    var_channel_is_plain_css,
+    "a {\n  color: lab(var(--l) 1 2);\n}\n"
+);
+test!(
+    var_alpha_is_plain_css,
+    "a {\n  color: oklch(50% 0.1 120 / var(--alpha));\n}\n"
+);
+test!(none_channel, "a {\n  color: lab(none 10 20);\n}\n");
+test!(
+    none_hue_round_trips,
+    "a {\n  color: lch(none 10 20);\n}\n",
+    "a {\n  color: lch(none 10 20deg);\n}\n"
+);
+test!(
+    none_channel_is_zero,
+    "@use \"sass:color\";\na {\n  color: color.channel(lab(none 10 20), \"lightness\");\n}\n",
+    "a {\n  color: 0%;\n}\n"
+);
+test!(
+    named_channels,
+    "a {\n  color: lab($channels: 50% 40 59.5);\n}\n",
+    "a {\n  color: lab(50% 40 59.5);\n}\n"
+);
+test!(
+    named_channels_with_alpha,
+    "a {\n  color: lab($channels: 50% 40 59.5 / 0.5);\n}\n",
+    "a {\n  color: lab(50% 40 59.5 / 0.5);\n}\n"
+);
+test!(
+    spread_channels,
+    "$args: (50% 0.1 120,);\na {\n  color: oklch($args...);\n}\n",
+    "a {\n  color: oklch(50% 0.1 120deg);\n}\n"
+);
+test!(
+    spread_map_channels,
+    "$args: (channels: 50% 0.1 120);\na {\n  color: oklch($args...);\n}\n",
+    "a {\n  color: oklch(50% 0.1 120deg);\n}\n"
+);
+test!(
+    slash_list_channels,
+    "@use \"sass:list\";\na {\n  color: oklch(list.slash(50% 0.1 120, 0.5));\n}\n",
+    "a {\n  color: oklch(50% 0.1 120deg / 0.5);\n}\n"
+);
+test!(
+    division_with_variable_is_not_alpha,
+    "$a: 2;\na {\n  color: lab(50% 40 (120 / $a));\n}\n",
+    "a {\n  color: lab(50% 40 60);\n}\n"
+);
+test!(
+    type_of_oklch,
+    "a {\n  color: type-of(oklch(50% 0.1 120));\n}\n",
+    "a {\n  color: color;\n}\n"
+);
+test!(
+    oklch_equality,
+    "a {\n  color: oklch(50% 0.1 120) == oklch(0.5 0.1 120deg);\n}\n",
+    "a {\n  color: true;\n}\n"
+);
+test!(
+    different_spaces_are_not_equal,
+    "a {\n  color: color(display-p3 1 1 1) == white;\n}\n",
+    "a {\n  color: false;\n}\n"
+);
+test!(
+    compressed_keeps_space,
+    "a {\n  color: oklch(50% 0.1 120 / 0.5);\n}\n",
+    "a{color:oklch(50% 0.1 120deg / 0.5)}",
+    grass::Options::default().style(grass::OutputStyle::Compressed)
+);
+test!(
+    to_space_oklch,
+    "@use \"sass:color\";\na {\n  color: color.to-space(#ff0000, oklch);\n}\n",
+    "a {\n  color: oklch(62.7955363921% 0.2576833038 29.2338802796deg);\n}\n"
+);
+test!(
+    to_space_lab,
+    "@use \"sass:color\";\na {\n  color: color.to-space(#ff0000, lab);\n}\n",
+    "a {\n  color: lab(54.290542947% 80.8049203346 69.890988259);\n}\n"
+);
+test!(
+    to_space_display_p3,
+    "@use \"sass:color\";\na {\n  color: color.to-space(#ff0000, display-p3);\n}\n",
+    "a {\n  color: color(display-p3 0.9174875573 0.2002868077 0.1385605912);\n}\n"
+);
+test!(
+    to_space_rgb_round_trips,
+    "@use \"sass:color\";\na {\n  color: color.to-space(color.to-space(#abcdef, oklch), rgb);\n}\n",
+    "a {\n  color: #abcdef;\n}\n"
+);
+test!(
+    to_space_achromatic_has_no_hue,
+    "@use \"sass:color\";\na {\n  color: color.to-space(white, oklch);\n}\n",
+    "a {\n  color: oklch(100% 0 0deg);\n}\n"
+);
+test!(
+    to_space_rgb_is_gamut_mapped,
+    "@use \"sass:color\";\na {\n  color: color.to-space(color(display-p3 0 1 0), rgb);\n}\n",
+    "a {\n  color: #00fb29;\n}\n"
+);
+test!(
+    space,
+    "@use \"sass:color\";\na {\n  color: color.space(oklch(50% 0.1 120)) color.space(hsl(0, 0%, 0%)) color.space(red);\n}\n",
+    "a {\n  color: oklch hsl rgb;\n}\n"
+);
+test!(
+    channel_lightness,
+    "@use \"sass:color\";\na {\n  color: color.channel(oklch(50% 0.1 120), \"lightness\");\n}\n",
+    "a {\n  color: 50%;\n}\n"
+);
+test!(
+    channel_hue,
+    "@use \"sass:color\";\na {\n  color: color.channel(oklch(50% 0.1 120), \"hue\");\n}\n",
+    "a {\n  color: 120deg;\n}\n"
+);
+test!(
+    channel_in_other_space,
+    "@use \"sass:color\";\na {\n  color: color.channel(#ff0000, \"chroma\", $space: oklch);\n}\n",
+    "a {\n  color: 0.2576833038;\n}\n"
+);
+test!(
+    channel_legacy,
+    "@use \"sass:color\";\na {\n  color: color.channel(hsl(120, 50%, 50%), \"saturation\");\n}\n",
+    "a {\n  color: 50%;\n}\n"
+);
+test!(
+    channel_alpha,
+    "@use \"sass:color\";\na {\n  color: color.channel(lab(50% 40 59.5 / 0.5), \"alpha\");\n}\n",
+    "a {\n  color: 0.5;\n}\n"
+);
+test!(
+    is_in_gamut,
+    "@use \"sass:color\";\na {\n  color: color.is-in-gamut(color(display-p3 1 0 0), rgb) color.is-in-gamut(color(display-p3 1 0 0)) color.is-in-gamut(oklch(70% 0.4 150));\n}\n",
+    "a {\n  color: false true true;\n}\n"
+);
+test!(
+    to_gamut_clip,
+    "@use \"sass:color\";\na {\n  color: color.to-gamut(oklch(70% 0.4 150), $space: rgb, $method: clip);\n}\n",
+    "a {\n  color: oklch(75.8703156392% 0.2581672644 142.4953450414deg);\n}\n"
+);
+test!(
+    to_gamut_local_minde,
+    "@use \"sass:color\";\na {\n  color: color.to-gamut(oklch(70% 0.4 150), $space: rgb, $method: local-minde);\n}\n",
+    "a {\n  color: oklch(70.9142991744% 0.2103908787 147.0649459543deg);\n}\n"
+);
+test!(
+    to_gamut_unbounded_space_is_unchanged,
+    "@use \"sass:color\";\na {\n  color: color.to-gamut(oklch(70% 0.4 150), $method: local-minde);\n}\n",
+    "a {\n  color: oklch(70% 0.4 150deg);\n}\n"
+);
+test!(
+    legacy_function_uses_srgb,
+    "a {\n  color: red(color(display-p3 1 0 0));\n}\n",
+    "a {\n  color: 255;\n}\n"
+);
+error!(
+    lab_comma_separated,
+    "a {\n  color: lab(1, 2, 3);\n}\n", "Error: Only 1 argument allowed, but 3 were passed."
+);
+error!(
+    lab_missing_channel,
+    "a {\n  color: lab(50% 40);\n}\n", "Error: Missing element $b."
+);
+error!(
+    lab_too_many_channels,
+    "a {\n  color: lab(50% 40 1 2);\n}\n", "Error: Only 3 elements allowed, but 4 were passed."
+);
+error!(
+    lch_hue_with_length,
+    "a {\n  color: lch(50% 40 10px);\n}\n",
+    "Error: $hue: Expected 10px to have an angle unit (deg, grad, rad, turn)."
+);
+error!(
+    lab_lightness_with_length,
+    "a {\n  color: lab(50px 40 1);\n}\n",
+    "Error: $lightness: Expected 50px to have no units or \"%\"."
+);
+error!(
+    to_space_unknown_space,
+    "@use \"sass:color\";\na {\n  color: color.to-space(red, foo);\n}\n",
+    "Error: $space: Unknown color space \"foo\"."
+);
+error!(
+    channel_unknown,
+    "@use \"sass:color\";\na {\n  color: color.channel(red, \"hue\");\n}\n",
+    "Error: $channel: Color red has no channel named hue."
+);
+error!(
+    to_gamut_without_method,
+    "@use \"sass:color\";\na {\n  color: color.to-gamut(red);\n}\n",
+    "Error: $method: color.to-gamut() requires a $method argument for forwards-compatibility with changes in the CSS spec. Suggestion:"
+);
+error!(
+    to_gamut_unknown_method,
+    "@use \"sass:color\";\na {\n  color: color.to-gamut(red, $method: foo);\n}\n",
+    "Error: $method: Unknown gamut map method \"foo\"."
+);
+error!(
+    nan_channel,
+    "@use \"sass:math\";\na {\n  color: oklch(math.div(0, 0) 0.1 200);\n}\n",
+    "Error: $lightness: Expected NaN to be a finite number."
+);
+error!(
+    nan_alpha,
+    "@use \"sass:list\";\n@use \"sass:math\";\na {\n  color: oklch(list.slash(50% 0.1 200, math.div(0, 0)));\n}\n",
+    "Error: $alpha: Expected NaN to be a finite number."
+);
+error!(
+    too_many_slash_elements,
+    "@use \"sass:list\";\na {\n  color: lab(list.slash(50% 40 59.5, 0.5, 1));\n}\n",
+    "Error: Only 2 slash-separated elements allowed, but 3 were passed."
+);
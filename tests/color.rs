@@ -640,6 +640,6 @@ error!(
 // todo: we need many more of these tests
 test!(
     rgba_special_fn_4th_arg_max,
-    "a {\n  color: rgba(1 2 max(3, 3));\n}\n",
-    "a {\n  color: rgba(1, 2, max(3, 3));\n}\n"
+    "a {\n  color: rgba(1 2 max(3%, 3vh));\n}\n",
+    "a {\n  color: rgba(1, 2, max(3%, 3vh));\n}\n"
 );
@@ -0,0 +1,189 @@
+#[macro_use]
+mod macros;
+
+test!(
+    compresses_simple_rule,
+    "a {\n  color: red;\n}\n",
+    "a{color:red}",
+    grass::Options::default().style(grass::OutputStyle::Compressed)
+);
+test!(
+    compresses_rule_with_many_styles,
+    "a {\n  color: red;\n  color: blue;\n}\n",
+    "a{color:red;color:blue}",
+    grass::Options::default().style(grass::OutputStyle::Compressed)
+);
+test!(
+    compresses_multiple_rules,
+    "a {\n  color: red;\n}\nb {\n  color: blue;\n}\n",
+    "a{color:red}b{color:blue}",
+    grass::Options::default().style(grass::OutputStyle::Compressed)
+);
+test!(
+    compresses_selector_list,
+    "a, b {\n  color: red;\n}\n",
+    "a,b{color:red}",
+    grass::Options::default().style(grass::OutputStyle::Compressed)
+);
+test!(
+    compresses_combinators,
+    "a > b ~ c + d e {\n  color: red;\n}\n",
+    "a>b~c+d e{color:red}",
+    grass::Options::default().style(grass::OutputStyle::Compressed)
+);
+test!(
+    compresses_nested_rules,
+    "a {\n  color: red;\n  b {\n    color: blue;\n  }\n}\n",
+    "a{color:red}a b{color:blue}",
+    grass::Options::default().style(grass::OutputStyle::Compressed)
+);
+test!(
+    removes_empty_rule,
+    "a {}\nb {\n  color: red;\n}\n",
+    "b{color:red}",
+    grass::Options::default().style(grass::OutputStyle::Compressed)
+);
+test!(
+    removes_loud_comment,
+    "/* foo */\na {\n  /* bar */\n  color: red;\n}\n",
+    "a{color:red}",
+    grass::Options::default().style(grass::OutputStyle::Compressed)
+);
+test!(
+    keeps_preserved_comment,
+    "/*! foo */\na {\n  color: red;\n  /*! bar */\n}\n",
+    "/*! foo */a{color:red;/*! bar */}",
+    grass::Options::default().style(grass::OutputStyle::Compressed)
+);
+test!(
+    comma_separated_list,
+    "a {\n  color: a, b, c;\n}\n",
+    "a{color:a,b,c}",
+    grass::Options::default().style(grass::OutputStyle::Compressed)
+);
+test!(
+    space_separated_list,
+    "a {\n  color: a b c;\n}\n",
+    "a{color:a b c}",
+    grass::Options::default().style(grass::OutputStyle::Compressed)
+);
+test!(
+    strips_leading_zero,
+    "a {\n  color: 0.5;\n}\n",
+    "a{color:.5}",
+    grass::Options::default().style(grass::OutputStyle::Compressed)
+);
+test!(
+    strips_leading_zero_negative_with_unit,
+    "a {\n  color: -0.5px;\n}\n",
+    "a{color:-.5px}",
+    grass::Options::default().style(grass::OutputStyle::Compressed)
+);
+test!(
+    keeps_integer_zero,
+    "a {\n  color: 0;\n}\n",
+    "a{color:0}",
+    grass::Options::default().style(grass::OutputStyle::Compressed)
+);
+test!(
+    named_color_shorter_than_hex,
+    "a {\n  color: #ff0000;\n}\n",
+    "a{color:red}",
+    grass::Options::default().style(grass::OutputStyle::Compressed)
+);
+test!(
+    short_hex_shorter_than_name,
+    "a {\n  color: white;\n}\n",
+    "a{color:#fff}",
+    grass::Options::default().style(grass::OutputStyle::Compressed)
+);
+test!(
+    long_hex_when_no_short_hex,
+    "a {\n  color: #AbCdEf;\n}\n",
+    "a{color:#abcdef}",
+    grass::Options::default().style(grass::OutputStyle::Compressed)
+);
+test!(
+    rgba_without_spaces,
+    "a {\n  color: rgba(1, 2, 3, 0.5);\n}\n",
+    "a{color:rgba(1,2,3,.5)}",
+    grass::Options::default().style(grass::OutputStyle::Compressed)
+);
+test!(
+    transparent_keeps_name,
+    "a {\n  color: transparent;\n}\n",
+    "a{color:transparent}",
+    grass::Options::default().style(grass::OutputStyle::Compressed)
+);
+test!(
+    compresses_media,
+    "@media screen {\n  a {\n    color: red;\n  }\n}\n",
+    "@media screen{a{color:red}}",
+    grass::Options::default().style(grass::OutputStyle::Compressed)
+);
+test!(
+    compresses_media_features,
+    "@media screen and (min-width: 10px) and (color), print {\n  a {\n    color: red;\n  }\n}\n",
+    "@media screen and (min-width:10px)and (color),print{a{color:red}}",
+    grass::Options::default().style(grass::OutputStyle::Compressed)
+);
+test!(
+    compresses_media_condition,
+    "@media (min-width: 10px) {\n  a {\n    color: red;\n  }\n}\n",
+    "@media(min-width:10px){a{color:red}}",
+    grass::Options::default().style(grass::OutputStyle::Compressed)
+);
+test!(
+    compresses_supports,
+    "@supports (a: b) {\n  a {\n    color: red;\n  }\n}\n",
+    "@supports(a: b){a{color:red}}",
+    grass::Options::default().style(grass::OutputStyle::Compressed)
+);
+test!(
+    compresses_supports_negation,
+    "@supports not (a: b) {\n  a {\n    color: red;\n  }\n}\n",
+    "@supports not (a: b){a{color:red}}",
+    grass::Options::default().style(grass::OutputStyle::Compressed)
+);
+test!(
+    compresses_keyframes,
+    "@keyframes foo {\n  from {\n    color: red;\n  }\n  to {\n    color: blue;\n  }\n}\n",
+    "@keyframes foo{from{color:red}to{color:blue}}",
+    grass::Options::default().style(grass::OutputStyle::Compressed)
+);
+test!(
+    compresses_unknown_at_rule_without_body,
+    "@foo;\na {\n  color: red;\n}\n",
+    "@foo;a{color:red}",
+    grass::Options::default().style(grass::OutputStyle::Compressed)
+);
+test!(
+    byte_order_mark_for_non_ascii,
+    "a {\n  color: \"\u{1F600}\";\n}\n",
+    "\u{FEFF}a{color:\"\u{1F600}\"}",
+    grass::Options::default().style(grass::OutputStyle::Compressed)
+);
+test!(
+    compresses_slash_list,
+    "@use \"sass:list\";\na {\n  grid-area: list.slash(1, 2, 3);\n}\n",
+    "a{grid-area:1/2/3}",
+    grass::Options::default().style(grass::OutputStyle::Compressed)
+);
+test!(
+    no_semicolon_after_last_declaration_in_at_rule,
+    "@font-face {\n  font-family: x;\n}\n",
+    "@font-face{font-family:x}",
+    grass::Options::default().style(grass::OutputStyle::Compressed)
+);
+test!(
+    semicolon_between_declarations_in_at_rule,
+    "@font-face {\n  font-family: x;\n  src: y;\n}\n",
+    "@font-face{font-family:x;src:y}",
+    grass::Options::default().style(grass::OutputStyle::Compressed)
+);
+test!(
+    semicolon_between_declaration_and_rule_in_at_rule,
+    "@foo {\n  a: b;\n  c {\n    d: e;\n  }\n}\n",
+    "@foo{a:b;c{d:e}}",
+    grass::Options::default().style(grass::OutputStyle::Compressed)
+);
@@ -0,0 +1,244 @@
+use grass::{Options, SassValue};
+
+fn asset_url(args: &[SassValue]) -> Result<SassValue, String> {
+    let path = args[0].as_str().ok_or("$path: Expected a string.")?;
+    let url = if args[1].is_truthy() {
+        format!("url(\"/assets/{}?v=1a2b\")", path)
+    } else {
+        format!("url(\"/assets/{}\")", path)
+    };
+    Ok(SassValue::string(&url, false))
+}
+
+fn compile(input: &str, options: &Options) -> Result<String, String> {
+    grass::from_string(input.to_string(), options)
+        .map_err(|e| e.to_string().chars().take_while(|c| *c != '\n').collect())
+}
+
+#[test]
+fn default_argument() {
+    let options = Options::default().add_function("asset-url($path, $hash: false)", asset_url);
+    assert_eq!(
+        Ok("a {\n  b: url(\"/assets/logo.png\");\n}\n".to_string()),
+        compile("a { b: asset-url(\"logo.png\"); }", &options)
+    );
+}
+
+#[test]
+fn positional_arguments() {
+    let options = Options::default().add_function("asset-url($path, $hash: false)", asset_url);
+    assert_eq!(
+        Ok("a {\n  b: url(\"/assets/logo.png?v=1a2b\");\n}\n".to_string()),
+        compile("a { b: asset-url(\"logo.png\", true); }", &options)
+    );
+}
+
+#[test]
+fn named_arguments() {
+    let options = Options::default().add_function("asset-url($path, $hash: false)", asset_url);
+    assert_eq!(
+        Ok("a {\n  b: url(\"/assets/logo.png?v=1a2b\");\n}\n".to_string()),
+        compile(
+            "a { b: asset-url($hash: true, $path: \"logo.png\"); }",
+            &options
+        )
+    );
+}
+
+#[test]
+fn default_evaluated_in_caller_scope() {
+    let options = Options::default().add_function("theme($name, $fallback: $default)", |args| {
+        Ok(args[1].clone())
+    });
+    assert_eq!(
+        Ok("a {\n  b: red;\n}\n".to_string()),
+        compile("$default: red;\na { b: theme(primary); }", &options)
+    );
+}
+
+#[test]
+fn numbers_keep_units() {
+    let options = Options::default().add_function("double($n)", |args| {
+        let (n, unit) = args[0].as_number().ok_or("$n: Expected a number.")?;
+        Ok(SassValue::number(n * 2.0, &unit))
+    });
+    assert_eq!(
+        Ok("a {\n  b: 3px;\n  c: 1;\n}\n".to_string()),
+        compile("a { b: double(1.5px); c: double(0.5); }", &options)
+    );
+}
+
+#[test]
+fn returns_quoted_string() {
+    let options =
+        Options::default().add_function("greet()", |_| Ok(SassValue::string("hello", true)));
+    assert_eq!(
+        Ok("a {\n  b: \"hello\";\n}\n".to_string()),
+        compile("a { b: greet(); }", &options)
+    );
+}
+
+#[test]
+fn signature_without_parentheses() {
+    let options = Options::default().add_function("nothing", |_| Ok(SassValue::null()));
+    assert_eq!(
+        Ok("a {\n  c: d;\n}\n".to_string()),
+        compile("a { b: nothing(); c: d; }", &options)
+    );
+}
+
+#[test]
+fn variadic_arguments_are_a_list() {
+    let options = Options::default().add_function("first($args...)", |args| {
+        Ok(SassValue::bool(args[0] == SassValue::string("a", false)))
+    });
+    assert_eq!(
+        Ok("a {\n  b: false;\n}\n".to_string()),
+        compile("a { b: first(a, b); }", &options)
+    );
+}
+
+#[test]
+fn underscores_and_hyphens_are_equivalent() {
+    let options = Options::default().add_function("foo_bar()", |_| Ok(SassValue::bool(true)));
+    assert_eq!(
+        Ok("a {\n  b: true;\n}\n".to_string()),
+        compile("a { b: foo-bar(); }", &options)
+    );
+}
+
+#[test]
+fn overrides_builtin_function() {
+    let options =
+        Options::default().add_function("unquote($string)", |_| Ok(SassValue::number(1.0, "")));
+    assert_eq!(
+        Ok("a {\n  b: 1;\n}\n".to_string()),
+        compile("a { b: unquote(\"c\"); }", &options)
+    );
+}
+
+#[test]
+fn stylesheet_function_takes_precedence() {
+    let options = Options::default().add_function("foo()", |_| Ok(SassValue::bool(true)));
+    assert_eq!(
+        Ok("a {\n  b: false;\n}\n".to_string()),
+        compile(
+            "@function foo() { @return false; }\na { b: foo(); }",
+            &options
+        )
+    );
+}
+
+#[test]
+fn later_registration_takes_precedence() {
+    let options = Options::default()
+        .add_function("foo()", |_| Ok(SassValue::bool(true)))
+        .add_function("foo()", |_| Ok(SassValue::bool(false)));
+    assert_eq!(
+        Ok("a {\n  b: false;\n}\n".to_string()),
+        compile("a { b: foo(); }", &options)
+    );
+}
+
+#[test]
+fn function_exists() {
+    let options = Options::default().add_function("foo()", |_| Ok(SassValue::null()));
+    assert_eq!(
+        Ok("a {\n  b: true;\n}\n".to_string()),
+        compile("a { b: function-exists(foo); }", &options)
+    );
+}
+
+#[test]
+fn get_function_and_call() {
+    let options = Options::default().add_function("asset-url($path, $hash: false)", asset_url);
+    assert_eq!(
+        Ok("a {\n  b: url(\"/assets/c\");\n  c: get-function(\"asset-url\");\n}\n".to_string()),
+        compile(
+            "$f: get-function(asset-url);\na { b: call($f, c); c: inspect($f); }",
+            &options
+        )
+    );
+}
+
+#[test]
+fn closure_captures_environment() {
+    let themes = vec![("primary", "#639")];
+    let options = Options::default().add_function("theme($name)", move |args| {
+        let name = args[0].as_str().ok_or("$name: Expected a string.")?;
+        themes
+            .iter()
+            .find(|(theme, _)| *theme == name)
+            .map(|(_, color)| SassValue::string(color, false))
+            .ok_or_else(|| format!("Unknown theme color {}.", name))
+    });
+    assert_eq!(
+        Ok("a {\n  color: #639;\n}\n".to_string()),
+        compile("a { color: theme(primary); }", &options)
+    );
+    assert_eq!(
+        Err("Error: Unknown theme color secondary.".to_string()),
+        compile("a { color: theme(secondary); }", &options)
+    );
+}
+
+#[test]
+fn missing_argument() {
+    let options = Options::default().add_function("asset-url($path, $hash: false)", asset_url);
+    assert_eq!(
+        Err("Error: Missing argument $path.".to_string()),
+        compile("a { b: asset-url(); }", &options)
+    );
+}
+
+#[test]
+fn invalid_signature() {
+    let options = Options::default().add_function("foo(bar)", |_| Ok(SassValue::null()));
+    assert_eq!(
+        Err("Error: expected \")\".".to_string()),
+        compile("a { b: c; }", &options)
+    );
+}
+
+#[test]
+fn unclosed_signature() {
+    let options = Options::default().add_function("foo($a", |_| Ok(SassValue::null()));
+    assert_eq!(
+        Err("Error: expected \")\".".to_string()),
+        compile("a { b: foo(1); }", &options)
+    );
+}
+
+#[test]
+fn called_repeatedly() {
+    let options = Options::default().add_function("asset-url($path, $hash: false)", asset_url);
+    assert_eq!(
+        Ok("a {\n  b: url(\"/assets/a.png\");\n  c: url(\"/assets/b.png?v=1a2b\");\n  d: url(\"/assets/c.png\");\n}\n".to_string()),
+        compile(
+            "a { b: asset-url(\"a.png\"); c: asset-url(\"b.png\", true); d: asset-url($path: \"c.png\"); }",
+            &options
+        )
+    );
+}
+
+#[test]
+fn options_reused_across_compilations() {
+    let options = Options::default().add_function("asset-url($path, $hash: false)", asset_url);
+    for _ in 0..2 {
+        assert_eq!(
+            Ok("a {\n  b: url(\"/assets/logo.png\");\n}\n".to_string()),
+            compile("a { b: asset-url(\"logo.png\"); }", &options)
+        );
+    }
+}
+
+#[test]
+fn error_in_default_argument() {
+    let options = Options::default().add_function("foo($a: $undefined)", |_| Ok(SassValue::null()));
+    for _ in 0..2 {
+        assert_eq!(
+            Err("Error: Undefined variable.".to_string()),
+            compile("a { b: foo(); }", &options)
+        );
+    }
+}
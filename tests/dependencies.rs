@@ -0,0 +1,113 @@
+use std::{io::Write, path::PathBuf};
+
+use grass::{DependencyGraph, Options};
+
+#[macro_use]
+mod macros;
+
+fn paths(paths: &[&str]) -> Vec<PathBuf> {
+    paths.iter().map(PathBuf::from).collect()
+}
+
+#[test]
+fn records_import() {
+    let input = "@import \"records_import__a\";";
+    tempfile!("records_import__a.scss", "a { color: red; }");
+    let graph = DependencyGraph::new();
+    grass::from_string(
+        input.to_string(),
+        &Options::default().track_dependencies(&graph),
+    )
+    .expect(input);
+    assert_eq!(paths(&["records_import__a.scss"]), graph.files());
+}
+
+#[test]
+fn records_nested_use_and_forward() {
+    let input = "@use \"records_nested_use_and_forward__a\" as a;\nb { color: a.$c; }";
+    tempfile!(
+        "records_nested_use_and_forward__a.scss",
+        "@forward \"records_nested_use_and_forward__b\";"
+    );
+    tempfile!("_records_nested_use_and_forward__b.scss", "$c: red;");
+    let graph = DependencyGraph::new();
+    grass::from_string(
+        input.to_string(),
+        &Options::default().track_dependencies(&graph),
+    )
+    .expect(input);
+    assert_eq!(
+        paths(&[
+            "records_nested_use_and_forward__a.scss",
+            "_records_nested_use_and_forward__b.scss"
+        ]),
+        graph.files()
+    );
+    assert_eq!(
+        paths(&["_records_nested_use_and_forward__b.scss"]),
+        graph.dependencies_of("records_nested_use_and_forward__a.scss".as_ref())
+    );
+}
+
+#[test]
+fn stylesheet_loaded_twice_is_recorded_once() {
+    let input = "@import \"stylesheet_loaded_twice_is_recorded_once__a\";\n@import \"stylesheet_loaded_twice_is_recorded_once__a\";";
+    tempfile!(
+        "stylesheet_loaded_twice_is_recorded_once__a.scss",
+        "a { color: red; }"
+    );
+    let graph = DependencyGraph::new();
+    grass::from_string(
+        input.to_string(),
+        &Options::default().track_dependencies(&graph),
+    )
+    .expect(input);
+    assert_eq!(
+        paths(&["stylesheet_loaded_twice_is_recorded_once__a.scss"]),
+        graph.files()
+    );
+}
+
+#[test]
+fn records_stylesheet_containing_error() {
+    let input = "@import \"records_stylesheet_containing_error__a\";";
+    tempfile!(
+        "records_stylesheet_containing_error__a.scss",
+        "a { color: ; }"
+    );
+    let graph = DependencyGraph::new();
+    assert!(grass::from_string(
+        input.to_string(),
+        &Options::default().track_dependencies(&graph),
+    )
+    .is_err());
+    assert_eq!(
+        paths(&["records_stylesheet_containing_error__a.scss"]),
+        graph.files()
+    );
+}
+
+#[test]
+fn builtin_modules_are_not_recorded() {
+    let graph = DependencyGraph::new();
+    grass::from_string(
+        "@use \"sass:math\";\na { b: math.$pi; }".to_string(),
+        &Options::default().track_dependencies(&graph),
+    )
+    .unwrap();
+    assert!(graph.files().is_empty());
+}
+
+#[test]
+fn clear() {
+    let input = "@import \"clear__a\";";
+    tempfile!("clear__a.scss", "a { color: red; }");
+    let graph = DependencyGraph::new();
+    grass::from_string(
+        input.to_string(),
+        &Options::default().track_dependencies(&graph),
+    )
+    .expect(input);
+    graph.clear();
+    assert!(graph.files().is_empty());
+}
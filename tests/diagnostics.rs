@@ -0,0 +1,156 @@
+use std::{io::Write, path::Path};
+
+use grass::{Options, Position, Severity};
+
+#[macro_use]
+mod macros;
+
+fn error(input: &str) -> Box<grass::Error> {
+    match grass::from_string(input.to_string(), &Options::default()) {
+        Ok(..) => panic!("did not fail"),
+        Err(e) => e,
+    }
+}
+
+fn trace(error: &grass::Error) -> Vec<(String, usize)> {
+    error
+        .trace()
+        .iter()
+        .map(|frame| (frame.name().to_owned(), frame.start().line))
+        .collect()
+}
+
+#[test]
+fn message_and_location() {
+    let e = error("a {\n  color: 1px + 1em;\n}\n");
+    assert_eq!("Incompatible units em and px.", e.message());
+    assert_eq!(Severity::Error, e.severity());
+    assert_eq!(Some(Path::new("stdin")), e.path());
+    assert_eq!(
+        Some(Position {
+            line: 2,
+            column: 10
+        }),
+        e.start()
+    );
+    assert_eq!(Some("  color: 1px + 1em;"), e.snippet());
+}
+
+#[test]
+fn columns_count_chars() {
+    let e = error("a { b: \"\u{1f600}\u{e9}\"; c: 1px + 1em; }");
+    assert_eq!(
+        Some(Position {
+            line: 1,
+            column: 17
+        }),
+        e.start()
+    );
+}
+
+#[test]
+fn io_error_has_no_location() {
+    let e = grass::from_path("io_error_has_no_location.scss", &Options::default()).unwrap_err();
+    assert_eq!(None, e.path());
+    assert_eq!(None, e.start());
+    assert_eq!(None, e.snippet());
+    assert!(e.trace().is_empty());
+}
+
+#[test]
+fn trace_at_root() {
+    let e = error("a {\n  color: 1px + 1em;\n}\n");
+    assert_eq!(vec![("root stylesheet".to_owned(), 2)], trace(&e));
+}
+
+#[test]
+fn trace_through_mixin_and_function() {
+    let e = error(
+        "@function foo() {\n  @return 1px + 1em;\n}\n@mixin bar {\n  a { color: foo(); }\n}\n@include bar;\n",
+    );
+    assert_eq!(
+        vec![
+            ("foo()".to_owned(), 2),
+            ("bar()".to_owned(), 5),
+            ("root stylesheet".to_owned(), 7),
+        ],
+        trace(&e)
+    );
+}
+
+#[test]
+fn trace_does_not_include_finished_calls() {
+    let e = error("@mixin foo {}\n@include foo;\na {\n  color: 1px + 1em;\n}\n");
+    assert_eq!(vec![("root stylesheet".to_owned(), 4)], trace(&e));
+}
+
+#[test]
+fn trace_through_import() {
+    let input = "@import \"trace_through_import__a\";";
+    tempfile!(
+        "trace_through_import__a.scss",
+        "a {\n  color: 1px + 1em;\n}\n"
+    );
+    let e = error(input);
+    assert_eq!(Some(Path::new("trace_through_import__a.scss")), e.path());
+    assert_eq!(
+        vec![("@import".to_owned(), 2), ("root stylesheet".to_owned(), 1)],
+        trace(&e)
+    );
+    assert_eq!(Path::new("stdin"), e.trace().last().unwrap().path());
+}
+
+#[test]
+fn trace_through_use() {
+    let input = "@use \"trace_through_use__a\";";
+    tempfile!("trace_through_use__a.scss", "a {\n  color: 1px + 1em;\n}\n");
+    let e = error(input);
+    assert_eq!(
+        vec![("@use".to_owned(), 2), ("root stylesheet".to_owned(), 1)],
+        trace(&e)
+    );
+}
+
+#[test]
+fn display_includes_trace() {
+    let e = error(
+        "@function foo() {\n  @return 1px + 1em;\n}\n@mixin bar {\n  a { color: foo(); }\n}\n@include bar;\n",
+    );
+    assert!(e
+        .to_string()
+        .ends_with("  stdin 2:11  foo()\n  stdin 5:14  bar()\n  stdin 7:1   root stylesheet\n"));
+}
+
+#[test]
+fn trace_through_content_block() {
+    let e = error("@mixin foo {\n  a { @content; }\n}\n@include foo {\n  color: 1px + 1em;\n}\n");
+    assert_eq!(
+        vec![
+            ("@content".to_owned(), 5),
+            ("foo()".to_owned(), 2),
+            ("root stylesheet".to_owned(), 4),
+        ],
+        trace(&e)
+    );
+}
+
+#[test]
+fn trace_through_module_function() {
+    let input = "@use \"trace_through_module_function__a\" as a;\nb {\n  color: a.foo();\n}\n";
+    tempfile!(
+        "trace_through_module_function__a.scss",
+        "@function foo() {\n  @return 1px + 1em;\n}\n"
+    );
+    let e = error(input);
+    assert_eq!(
+        vec![("foo()".to_owned(), 2), ("root stylesheet".to_owned(), 3)],
+        trace(&e)
+    );
+    assert_eq!(
+        Position {
+            line: 3,
+            column: 10
+        },
+        e.trace()[1].start()
+    );
+}
@@ -167,8 +167,58 @@ test!(
     "a {\n  color: 1 + 3 / 4;\n}\n",
     "a {\n  color: 1.75;\n}\n"
 );
+test!(
+    one_div_zero_is_infinity,
+    "a {\n  color: (1 / 0);\n}\n",
+    "a {\n  color: Infinity;\n}\n"
+);
+test!(
+    modulo_zero_is_nan,
+    "a {\n  color: (1 % 0);\n}\n",
+    "a {\n  color: NaN;\n}\n"
+);
 test!(
     zero_div_zero_is_nan,
     "a {\n  color: (0 / 0);\n}\n",
     "a {\n  color: NaN;\n}\n"
 );
+test!(
+    slash_in_plain_css_function_call,
+    "a {\n  color: foo(4px/2);\n}\n",
+    "a {\n  color: foo(4px/2);\n}\n"
+);
+test!(
+    slash_in_list_passed_to_builtin,
+    "a {\n  color: nth(4px/2 3px, 1);\n}\n",
+    "a {\n  color: 4px/2;\n}\n"
+);
+test!(
+    inspect_slash,
+    "a {\n  color: inspect(4px/2);\n}\n",
+    "a {\n  color: 4px/2;\n}\n"
+);
+test!(
+    type_of_slash,
+    "a {\n  color: type-of(4px/2);\n}\n",
+    "a {\n  color: number;\n}\n"
+);
+test!(
+    slash_is_divided_in_arithmetic,
+    "a {\n  color: nth(4px/2 3px, 1) + 1;\n}\n",
+    "a {\n  color: 3px;\n}\n"
+);
+test!(
+    slash_returned_from_function_is_divided,
+    "a {\n  color: nth(4px/2 3px, 1) / 2;\n}\n",
+    "a {\n  color: 1px;\n}\n"
+);
+test!(
+    slash_passed_to_user_defined_function_is_divided,
+    "@function foo($a) {\n  @return $a;\n}\n\na {\n  color: foo(4px/2);\n}\n",
+    "a {\n  color: 2px;\n}\n"
+);
+test!(
+    negated_slash_is_divided,
+    "a {\n  color: - nth(4px/2 3px, 1);\n}\n",
+    "a {\n  color: -2px;\n}\n"
+);
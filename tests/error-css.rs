@@ -0,0 +1,45 @@
+fn error_css(input: &str, options: &grass::Options) -> String {
+    match grass::from_string(input.to_string(), options) {
+        Ok(..) => panic!("did not fail"),
+        Err(e) => e.to_css(),
+    }
+}
+
+#[test]
+fn expression_error() {
+    assert_eq!(
+        "/* Error: Expected expression.\n *   |\n * 1 | a { color: ; }\n *   |     ^^^^^\n *   |\n *   stdin 1:5  root stylesheet */\n\nbody::before {\n  font-family: \"Source Code Pro\", \"SF Mono\", Monaco, Inconsolata, \"Fira Mono\",\n      \"Droid Sans Mono\", monospace, monospace;\n  white-space: pre;\n  display: block;\n  padding: 1em;\n  margin-bottom: 1em;\n  border-bottom: 2px solid black;\n  content: \"Error: Expected expression.\\a   |\\a 1 | a { color: ; }\\a   |     ^^^^^\\a   |\\a   stdin 1:5  root stylesheet\";\n}\n",
+        error_css(
+            "a { color: ; }",
+            &grass::Options::default().unicode_error_messages(false)
+        )
+    );
+}
+
+#[test]
+fn escapes_quotes_and_backslashes() {
+    let css = error_css(
+        "a { color: \"\\\\\" + ; }",
+        &grass::Options::default().unicode_error_messages(false),
+    );
+    assert!(css.contains(
+        "content: \"Error: Expected expression.\\a   |\\a 1 | a { color: \\\"\\\\\\\\\\\" + ; }"
+    ));
+}
+
+#[test]
+fn escapes_non_ascii() {
+    let css = error_css("a { color: ; }", &grass::Options::default());
+    assert!(css.contains("content: \"Error: Expected expression.\\a   \\2577 \\a 1 \\2502  a"));
+}
+
+#[test]
+fn comment_cannot_be_closed_by_error() {
+    let css = error_css(
+        "a { color: ; } /**/",
+        &grass::Options::default().unicode_error_messages(false),
+    );
+    assert!(css.contains("1 | a { color: ; } /**\u{2215}"));
+    let comment = &css[..css.find("body::before").unwrap()];
+    assert_eq!(1, comment.matches("*/").count());
+}
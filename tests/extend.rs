@@ -1394,13 +1394,70 @@ test!(
     "@media screen {\n  @unknown {\n    .foo, .bar {\n      a: b;\n    }\n  }\n}\n"
 );
 test!(
-    #[ignore = "media queries are not yet parsed correctly"]
     extend_within_separate_media_queries,
     "@media screen {.foo {a: b}}
     @media screen {.bar {@extend .foo}}
     ",
     "@media screen {\n  .foo, .bar {\n    a: b;\n  }\n}\n"
 );
+test!(
+    extend_within_separate_interpolated_media_queries,
+    "$query: \"screen, print\";
+    @media #{$query} {.foo {a: b}}
+    @media screen, print {.bar {@extend .foo}}
+    ",
+    "@media screen, print {\n  .foo, .bar {\n    a: b;\n  }\n}\n"
+);
+test!(
+    extend_within_merged_media_queries,
+    "@media screen {@media (color) {.foo {a: b}}}
+    @media screen and (color) {.bar {@extend .foo}}
+    ",
+    "@media screen and (color) {\n  .foo, .bar {\n    a: b;\n  }\n}\n"
+);
+test!(
+    extend_into_media_from_top_level,
+    "@media screen {.foo {a: b}}
+    .bar {@extend .foo}
+    ",
+    "@media screen {\n  .foo, .bar {\n    a: b;\n  }\n}\n"
+);
+error!(
+    extend_top_level_selector_from_media,
+    ".foo {a: b}
+    @media screen {.bar {@extend .foo}}
+    ",
+    "Error: You may not @extend selectors across media queries."
+);
+error!(
+    extend_later_top_level_selector_from_media,
+    "@media screen {.bar {@extend .foo}}
+    .foo {a: b}
+    ",
+    "Error: You may not @extend selectors across media queries."
+);
+error!(
+    extend_across_different_media_queries,
+    "@media screen {.foo {a: b}}
+    @media print {.bar {@extend .foo}}
+    ",
+    "Error: You may not @extend selectors across media queries."
+);
+error!(
+    extend_optional_across_media_queries,
+    ".foo {a: b}
+    @media screen {.bar {@extend .foo !optional}}
+    ",
+    "Error: You may not @extend selectors across media queries."
+);
+error!(
+    extend_same_selector_from_different_media_queries,
+    "@media screen {.bar {@extend .foo}}
+    @media print {.bar {@extend .foo}}
+    .foo {a: b}
+    ",
+    "Error: You may not @extend the same selector from within different media queries."
+);
 test!(
     #[ignore = "media queries are not yet parsed correctly"]
     extend_within_separate_unknown_at_rules,
@@ -1563,7 +1620,6 @@ test!(
     ".parent1 .child {\n  a: b;\n}\n"
 );
 test!(
-    #[ignore = "media queries are not yet parsed correctly"]
     extend_inside_double_nested_media,
     "@media all {
         @media (orientation: landscape) {
@@ -168,3 +168,16 @@ error!(
     to_and_from_i32_min,
     "@for $i from -2147483648 through -2147483648 {}", "Error: -2147483648 is not an int."
 );
+test!(
+    nested_style_rules_evaluated_each_iteration,
+    "a {
+        @for $i from 1 through 2 {
+            $j: $i * 2;
+            .b-#{$i} {
+                color: $j;
+            }
+            c { d: $i; }
+        }
+    }",
+    "a .b-1 {\n  color: 2;\n}\na c {\n  d: 1;\n}\na .b-2 {\n  color: 4;\n}\na c {\n  d: 2;\n}\n"
+);
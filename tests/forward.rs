@@ -0,0 +1,320 @@
+use std::io::Write;
+
+#[macro_use]
+mod macros;
+
+error!(
+    after_style,
+    "a {}
+    @forward \"foo\";
+    ",
+    "Error: @forward rules must be written before any other rules."
+);
+error!(
+    forward_missing_semicolon,
+    "@forward \"sass:math\" as m-* foo;", "Error: expected \";\"."
+);
+error!(
+    forward_prefix_missing_star,
+    "@forward \"sass:math\" as m-;", "Error: expected \"*\"."
+);
+
+#[test]
+fn forward_simple() {
+    let input = "@use \"forward_simple__a\";\na {\n color: forward_simple__a.$a;\n}";
+    tempfile!("forward_simple__a.scss", "@forward \"forward_simple__b\";");
+    tempfile!("forward_simple__b.scss", "$a: red;");
+    assert_eq!(
+        "a {\n  color: red;\n}\n",
+        &grass::from_string(input.to_string(), &grass::Options::default()).expect(input)
+    );
+}
+
+#[test]
+fn forward_emits_css() {
+    let input = "@use \"forward_emits_css__a\";";
+    tempfile!(
+        "forward_emits_css__a.scss",
+        "@forward \"forward_emits_css__b\";"
+    );
+    tempfile!("forward_emits_css__b.scss", "a { color: red; }");
+    assert_eq!(
+        "a {\n  color: red;\n}\n",
+        &grass::from_string(input.to_string(), &grass::Options::default()).expect(input)
+    );
+}
+
+#[test]
+fn forward_function_and_mixin() {
+    let input =
+        "@use \"forward_function_and_mixin__a\" as a;\nb {\n @include a.foo;\n color: a.bar(); \n}";
+    tempfile!(
+        "forward_function_and_mixin__a.scss",
+        "@forward \"forward_function_and_mixin__b\";"
+    );
+    tempfile!(
+        "forward_function_and_mixin__b.scss",
+        "@mixin foo { color: red; } @function bar() { @return green; }"
+    );
+    assert_eq!(
+        "b {\n  color: red;\n  color: green;\n}\n",
+        &grass::from_string(input.to_string(), &grass::Options::default()).expect(input)
+    );
+}
+
+#[test]
+fn forward_builtin_module() {
+    let input = "@use \"forward_builtin_module\" as a;\nb {\n color: a.clamp(0, 1, 2);\n}";
+    tempfile!("forward_builtin_module.scss", "@forward \"sass:math\";");
+    assert_eq!(
+        "b {\n  color: 1;\n}\n",
+        &grass::from_string(input.to_string(), &grass::Options::default()).expect(input)
+    );
+}
+
+#[test]
+fn forward_not_visible_in_forwarding_module() {
+    let input = "@use \"forward_not_visible_in_forwarding_module__a\";";
+    tempfile!(
+        "forward_not_visible_in_forwarding_module__a.scss",
+        "@forward \"forward_not_visible_in_forwarding_module__b\"; a { color: $a; }"
+    );
+    tempfile!(
+        "forward_not_visible_in_forwarding_module__b.scss",
+        "$a: red;"
+    );
+    assert_err!("Error: Undefined variable.", input);
+}
+
+#[test]
+fn forward_with_prefix() {
+    let input =
+        "@use \"forward_with_prefix__a\" as a;\nb {\n color: a.$foo-a;\n color: a.foo-b();\n}";
+    tempfile!(
+        "forward_with_prefix__a.scss",
+        "@forward \"forward_with_prefix__b\" as foo-*;"
+    );
+    tempfile!(
+        "forward_with_prefix__b.scss",
+        "$a: red; @function b() { @return green; }"
+    );
+    assert_eq!(
+        "b {\n  color: red;\n  color: green;\n}\n",
+        &grass::from_string(input.to_string(), &grass::Options::default()).expect(input)
+    );
+}
+
+#[test]
+fn forward_with_prefix_unprefixed_is_undefined() {
+    let input =
+        "@use \"forward_with_prefix_unprefixed_is_undefined__a\" as a;\nb {\n color: a.$a;\n}";
+    tempfile!(
+        "forward_with_prefix_unprefixed_is_undefined__a.scss",
+        "@forward \"forward_with_prefix_unprefixed_is_undefined__b\" as foo-*;"
+    );
+    tempfile!(
+        "forward_with_prefix_unprefixed_is_undefined__b.scss",
+        "$a: red;"
+    );
+    assert_err!("Error: Undefined variable.", input);
+}
+
+#[test]
+fn forward_with_prefix_private_variable_is_undefined() {
+    let input = "@use \"forward_with_prefix_private_variable_is_undefined__a\" as a;\nb {\n color: a.$foo--b;\n}";
+    tempfile!(
+        "forward_with_prefix_private_variable_is_undefined__a.scss",
+        "@forward \"forward_with_prefix_private_variable_is_undefined__b\" as foo-*;"
+    );
+    tempfile!(
+        "forward_with_prefix_private_variable_is_undefined__b.scss",
+        "$-b: red;"
+    );
+    assert_err!("Error: Undefined variable.", input);
+}
+
+#[test]
+fn forward_with_prefix_private_function_is_undefined() {
+    let input = "@use \"forward_with_prefix_private_function_is_undefined__a\" as a;\nb {\n color: a.foo--b();\n}";
+    tempfile!(
+        "forward_with_prefix_private_function_is_undefined__a.scss",
+        "@forward \"forward_with_prefix_private_function_is_undefined__b\" as foo-*;"
+    );
+    tempfile!(
+        "forward_with_prefix_private_function_is_undefined__b.scss",
+        "@function _b() { @return red; }"
+    );
+    assert_err!("Error: Undefined function.", input);
+}
+
+#[test]
+fn forward_with_prefix_private_mixin_is_undefined() {
+    let input = "@use \"forward_with_prefix_private_mixin_is_undefined__a\" as a;\nb {\n @include a.foo--b;\n}";
+    tempfile!(
+        "forward_with_prefix_private_mixin_is_undefined__a.scss",
+        "@forward \"forward_with_prefix_private_mixin_is_undefined__b\" as foo-*;"
+    );
+    tempfile!(
+        "forward_with_prefix_private_mixin_is_undefined__b.scss",
+        "@mixin -b { color: red; }"
+    );
+    assert_err!("Error: Undefined mixin.", input);
+}
+
+#[test]
+fn forward_show() {
+    let input = "@use \"forward_show__a\" as a;\nb {\n color: a.$a;\n color: a.foo();\n}";
+    tempfile!(
+        "forward_show__a.scss",
+        "@forward \"forward_show__b\" show foo, $a;"
+    );
+    tempfile!(
+        "forward_show__b.scss",
+        "$a: red; $b: blue; @function foo() { @return green; }"
+    );
+    assert_eq!(
+        "b {\n  color: red;\n  color: green;\n}\n",
+        &grass::from_string(input.to_string(), &grass::Options::default()).expect(input)
+    );
+}
+
+#[test]
+fn forward_show_excludes_others() {
+    let input = "@use \"forward_show_excludes_others__a\" as a;\nb {\n color: a.$b;\n}";
+    tempfile!(
+        "forward_show_excludes_others__a.scss",
+        "@forward \"forward_show_excludes_others__b\" show $a;"
+    );
+    tempfile!("forward_show_excludes_others__b.scss", "$a: red; $b: blue;");
+    assert_err!("Error: Undefined variable.", input);
+}
+
+#[test]
+fn forward_hide() {
+    let input = "@use \"forward_hide__a\" as a;\nb {\n color: a.$b;\n}";
+    tempfile!(
+        "forward_hide__a.scss",
+        "@forward \"forward_hide__b\" hide $b;"
+    );
+    tempfile!("forward_hide__b.scss", "$a: red; $b: blue;");
+    assert_err!("Error: Undefined variable.", input);
+}
+
+#[test]
+fn forward_hide_uses_prefixed_name() {
+    let input = "@use \"forward_hide_uses_prefixed_name__a\" as a;\nb {\n color: a.$foo-a;\n}";
+    tempfile!(
+        "forward_hide_uses_prefixed_name__a.scss",
+        "@forward \"forward_hide_uses_prefixed_name__b\" as foo-* hide $a;"
+    );
+    tempfile!("forward_hide_uses_prefixed_name__b.scss", "$a: red;");
+    assert_eq!(
+        "b {\n  color: red;\n}\n",
+        &grass::from_string(input.to_string(), &grass::Options::default()).expect(input)
+    );
+}
+
+#[test]
+fn forward_with_config() {
+    let input = "@use \"forward_with_config__a\" as a;\nb {\n color: a.$a;\n}";
+    tempfile!(
+        "forward_with_config__a.scss",
+        "@forward \"forward_with_config__b\" with ($a: red);"
+    );
+    tempfile!("forward_with_config__b.scss", "$a: green !default;");
+    assert_eq!(
+        "b {\n  color: red;\n}\n",
+        &grass::from_string(input.to_string(), &grass::Options::default()).expect(input)
+    );
+}
+
+#[test]
+fn forward_with_default_config_overridden_by_use() {
+    let input = "@use \"forward_with_default_config_overridden_by_use__a\" as a with ($a: blue);\nb {\n color: a.$a;\n}";
+    tempfile!(
+        "forward_with_default_config_overridden_by_use__a.scss",
+        "@forward \"forward_with_default_config_overridden_by_use__b\" with ($a: red !default);"
+    );
+    tempfile!(
+        "forward_with_default_config_overridden_by_use__b.scss",
+        "$a: green !default;"
+    );
+    assert_eq!(
+        "b {\n  color: blue;\n}\n",
+        &grass::from_string(input.to_string(), &grass::Options::default()).expect(input)
+    );
+}
+
+#[test]
+fn forward_with_default_config_not_overridden() {
+    let input =
+        "@use \"forward_with_default_config_not_overridden__a\" as a;\nb {\n color: a.$a;\n}";
+    tempfile!(
+        "forward_with_default_config_not_overridden__a.scss",
+        "@forward \"forward_with_default_config_not_overridden__b\" with ($a: red !default);"
+    );
+    tempfile!(
+        "forward_with_default_config_not_overridden__b.scss",
+        "$a: green !default;"
+    );
+    assert_eq!(
+        "b {\n  color: red;\n}\n",
+        &grass::from_string(input.to_string(), &grass::Options::default()).expect(input)
+    );
+}
+
+#[test]
+fn forward_use_config_passes_through_prefix() {
+    let input = "@use \"forward_use_config_passes_through_prefix__a\" as a with ($foo-a: blue);\nb {\n color: a.$foo-a;\n}";
+    tempfile!(
+        "forward_use_config_passes_through_prefix__a.scss",
+        "@forward \"forward_use_config_passes_through_prefix__b\" as foo-*;"
+    );
+    tempfile!(
+        "forward_use_config_passes_through_prefix__b.scss",
+        "$a: green !default;"
+    );
+    assert_eq!(
+        "b {\n  color: blue;\n}\n",
+        &grass::from_string(input.to_string(), &grass::Options::default()).expect(input)
+    );
+}
+
+#[test]
+fn forward_use_config_variable_never_declared() {
+    let input = "@use \"forward_use_config_variable_never_declared__a\" with ($b: blue);";
+    tempfile!(
+        "forward_use_config_variable_never_declared__a.scss",
+        "@forward \"forward_use_config_variable_never_declared__b\";"
+    );
+    tempfile!(
+        "forward_use_config_variable_never_declared__b.scss",
+        "$a: green !default;"
+    );
+    assert_err!(
+        "Error: This variable was not declared with !default in the @used module.",
+        input
+    );
+}
+
+#[test]
+fn forward_with_builtin_module() {
+    let input = "@forward \"sass:math\" with ($e: 2.7);";
+
+    assert_err!("Error: Built-in modules can't be configured.", input);
+}
+
+#[test]
+fn forward_from_index_file() {
+    let input = "@use \"forward_from_index_file\" as lib;\na {\n color: lib.$a;\n}";
+    tempfile!(
+        "_index.scss",
+        "@forward \"../forward_from_index_file__colors\";",
+        dir = "forward_from_index_file"
+    );
+    tempfile!("forward_from_index_file__colors.scss", "$a: red;");
+    assert_eq!(
+        "a {\n  color: red;\n}\n",
+        &grass::from_string(input.to_string(), &grass::Options::default()).expect(input)
+    );
+}
@@ -328,3 +328,29 @@ test!(
     }",
     "a {\n  color: nul;\n}\n"
 );
+test!(
+    return_within_loops,
+    "@function foo($a) {
+        @for $i from 1 through 10 {
+            @if $i == $a {
+                @return $i * 10;
+            }
+        }
+        @while $a < 20 {
+            $a: $a + 1;
+        }
+        @return $a;
+    }
+
+    a {
+        color: foo(3) foo(11) foo(3);
+    }",
+    "a {\n  color: 30 20 30;\n}\n"
+);
+error!(
+    space_separated_params,
+    "@function foo($a $b) {
+        @return $a;
+    }",
+    "Error: expected \")\"."
+);
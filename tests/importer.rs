@@ -0,0 +1,196 @@
+use std::{
+    collections::HashMap,
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+#[macro_use]
+mod macros;
+
+/// Serves stylesheets from memory, resolving urls relative to the importing file
+#[derive(Debug)]
+struct MapImporter(HashMap<&'static str, &'static str>);
+
+impl MapImporter {
+    fn new(files: &[(&'static str, &'static str)]) -> Self {
+        MapImporter(files.iter().copied().collect())
+    }
+}
+
+impl grass::Importer for MapImporter {
+    fn canonicalize(&self, url: &str, from: &Path) -> Option<PathBuf> {
+        let path = from.parent().unwrap_or_else(|| Path::new("")).join(url);
+        if self.0.contains_key(path.to_str()?) {
+            Some(path)
+        } else {
+            None
+        }
+    }
+
+    fn load(&self, canonical: &Path) -> grass::Result<String> {
+        Ok(self.0[canonical.to_str().unwrap()].to_string())
+    }
+}
+
+/// Resolves `pkg:name` to `packages/name.scss` within the map importer
+#[derive(Debug)]
+struct PackageImporter(MapImporter);
+
+impl grass::Importer for PackageImporter {
+    fn canonicalize(&self, url: &str, _from: &Path) -> Option<PathBuf> {
+        let name = url.strip_prefix("pkg:")?;
+        self.0
+            .canonicalize(&format!("packages/{}.scss", name), Path::new(""))
+    }
+
+    fn load(&self, canonical: &Path) -> grass::Result<String> {
+        self.0.load(canonical)
+    }
+}
+
+#[derive(Debug)]
+struct FailingImporter;
+
+impl grass::Importer for FailingImporter {
+    fn canonicalize(&self, url: &str, _from: &Path) -> Option<PathBuf> {
+        Some(PathBuf::from(url))
+    }
+
+    fn load(&self, _canonical: &Path) -> grass::Result<String> {
+        Err(std::io::Error::new(std::io::ErrorKind::NotFound, "missing").into())
+    }
+}
+
+#[test]
+fn use_from_memory() {
+    let importer = MapImporter::new(&[("a", "$a: red;")]);
+    assert_eq!(
+        "b {\n  color: red;\n}\n",
+        &grass::from_string(
+            "@use \"a\";\nb {\n color: a.$a;\n}".to_string(),
+            &grass::Options::default().importer(&importer)
+        )
+        .unwrap()
+    );
+}
+
+#[test]
+fn import_from_memory() {
+    let importer = MapImporter::new(&[("a", "a { color: red; }")]);
+    assert_eq!(
+        "a {\n  color: red;\n}\n",
+        &grass::from_string(
+            "@import \"a\";".to_string(),
+            &grass::Options::default().importer(&importer)
+        )
+        .unwrap()
+    );
+}
+
+#[test]
+fn relative_to_imported_file() {
+    let importer = MapImporter::new(&[("dir/a", "@forward \"b\";"), ("dir/b", "$b: green;")]);
+    assert_eq!(
+        "c {\n  color: green;\n}\n",
+        &grass::from_string(
+            "@use \"dir/a\";\nc {\n color: a.$b;\n}".to_string(),
+            &grass::Options::default().importer(&importer)
+        )
+        .unwrap()
+    );
+}
+
+#[test]
+fn package_url() {
+    let importer = PackageImporter(MapImporter::new(&[(
+        "packages/colors.scss",
+        "$primary: blue;",
+    )]));
+    assert_eq!(
+        "a {\n  color: blue;\n}\n",
+        &grass::from_string(
+            "@use \"pkg:colors\";\na {\n color: colors.$primary;\n}".to_string(),
+            &grass::Options::default().importer(&importer)
+        )
+        .unwrap()
+    );
+}
+
+#[test]
+fn importers_are_tried_in_order() {
+    let first = MapImporter::new(&[("a", "$a: red;")]);
+    let second = MapImporter::new(&[("a", "$a: blue;"), ("b", "$b: green;")]);
+    assert_eq!(
+        "c {\n  color: red;\n  color: green;\n}\n",
+        &grass::from_string(
+            "@use \"a\";\n@use \"b\";\nc {\n color: a.$a;\n color: b.$b;\n}".to_string(),
+            &grass::Options::default().importer(&first).importer(&second)
+        )
+        .unwrap()
+    );
+}
+
+#[test]
+fn indented_syntax_from_canonical_extension() {
+    let importer = MapImporter::new(&[("a.sass", "a\n  color: red\n")]);
+    assert_eq!(
+        "a {\n  color: red;\n}\n",
+        &grass::from_string(
+            "@import \"a.sass\";".to_string(),
+            &grass::Options::default().importer(&importer)
+        )
+        .unwrap()
+    );
+}
+
+#[test]
+fn falls_back_to_filesystem() {
+    let input = "@use \"falls_back_to_filesystem\" as a;\nb {\n color: a.$a;\n}";
+    tempfile!("falls_back_to_filesystem.scss", "$a: red;");
+    let importer = MapImporter::new(&[]);
+    assert_eq!(
+        "b {\n  color: red;\n}\n",
+        &grass::from_string(
+            input.to_string(),
+            &grass::Options::default().importer(&importer)
+        )
+        .unwrap()
+    );
+}
+
+#[test]
+fn builtin_modules_are_not_passed_to_importers() {
+    assert_eq!(
+        "a {\n  color: 1;\n}\n",
+        &grass::from_string(
+            "@use \"sass:math\";\na {\n color: math.abs(-1);\n}".to_string(),
+            &grass::Options::default().importer(&FailingImporter)
+        )
+        .unwrap()
+    );
+}
+
+#[test]
+fn load_error() {
+    match grass::from_string(
+        "@import \"a\";".to_string(),
+        &grass::Options::default().importer(&FailingImporter),
+    ) {
+        Ok(..) => panic!("did not fail"),
+        Err(e) => assert!(e.to_string().starts_with("Error: missing\n")),
+    }
+}
+
+#[test]
+fn not_found() {
+    let importer = MapImporter::new(&[]);
+    match grass::from_string(
+        "@import \"not_found__a\";".to_string(),
+        &grass::Options::default().importer(&importer),
+    ) {
+        Ok(..) => panic!("did not fail"),
+        Err(e) => assert!(e
+            .to_string()
+            .starts_with("Error: Can't find stylesheet to import.")),
+    }
+}
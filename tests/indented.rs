@@ -0,0 +1,257 @@
+use std::io::Write;
+
+#[macro_use]
+mod macros;
+
+macro_rules! test_indented {
+    ($func:ident, $input:expr, $output:expr) => {
+        test!(
+            $func,
+            $input,
+            $output,
+            grass::Options::default().indented_syntax(true)
+        );
+    };
+}
+
+macro_rules! error_indented {
+    ($func:ident, $input:expr, $err:expr) => {
+        #[test]
+        fn $func() {
+            match grass::from_string(
+                $input.to_string(),
+                &grass::Options::default().indented_syntax(true),
+            ) {
+                Ok(..) => panic!("did not fail"),
+                Err(e) => assert_eq!(
+                    $err,
+                    e.to_string()
+                        .chars()
+                        .take_while(|c| *c != '\n')
+                        .collect::<String>()
+                        .as_str()
+                ),
+            }
+        }
+    };
+}
+
+test_indented!(style_rule, "a\n  color: red\n", "a {\n  color: red;\n}\n");
+test_indented!(
+    nested_style_rules,
+    "a\n  color: red\n  b\n    color: blue\n",
+    "a {\n  color: red;\n}\na b {\n  color: blue;\n}\n"
+);
+test_indented!(
+    dedent_multiple_levels,
+    "a\n  b\n    c\n      d: e\nf\n  g: h\n",
+    "a b c {\n  d: e;\n}\n\nf {\n  g: h;\n}\n"
+);
+test_indented!(tabs, "a\n\tb\n\t\tc: d\n", "a b {\n  c: d;\n}\n");
+test_indented!(
+    no_trailing_newline,
+    "a\n  color: red",
+    "a {\n  color: red;\n}\n"
+);
+test_indented!(crlf, "a\r\n  color: red\r\n", "a {\n  color: red;\n}\n");
+test_indented!(
+    blank_lines_are_ignored,
+    "a\n\n  color: red\n\n\n  b: c\n",
+    "a {\n  color: red;\n  b: c;\n}\n"
+);
+test_indented!(
+    trailing_semicolon_is_ignored,
+    "a\n  color: red;\n",
+    "a {\n  color: red;\n}\n"
+);
+test_indented!(
+    variable_declaration,
+    "$a: red\na\n  color: $a\n",
+    "a {\n  color: red;\n}\n"
+);
+test_indented!(
+    silent_comment,
+    "// foo\na\n  // bar\n  color: red // baz\n",
+    "a {\n  color: red;\n}\n"
+);
+test_indented!(
+    silent_comment_indented_lines,
+    "// foo\n   bar\n  baz\na\n  color: red\n",
+    "a {\n  color: red;\n}\n"
+);
+test_indented!(
+    loud_comment,
+    "/* foo */\na\n  color: red\n",
+    "/* foo */\na {\n  color: red;\n}\n"
+);
+test_indented!(
+    loud_comment_is_closed,
+    "/* foo\n   bar\na\n  color: red\n",
+    "/* foo\n   bar */\na {\n  color: red;\n}\n"
+);
+test_indented!(
+    url_containing_double_slash,
+    "a\n  background: url(http://foo.com/bar.png)\n",
+    "a {\n  background: url(http://foo.com/bar.png);\n}\n"
+);
+test_indented!(
+    string_containing_double_slash,
+    "a\n  content: \"//\"\n",
+    "a {\n  content: \"//\";\n}\n"
+);
+test_indented!(
+    selector_list_across_lines,
+    "a,\nb\n  color: red\n",
+    "a,\nb {\n  color: red;\n}\n"
+);
+test_indented!(
+    parentheses_across_lines,
+    "$map: (a: 1,\n  b: 2)\na\n  color: map-get($map, b)\n",
+    "a {\n  color: 2;\n}\n"
+);
+test_indented!(
+    mixin_shorthand,
+    "=foo($a)\n  color: $a\na\n  +foo(red)\n",
+    "a {\n  color: red;\n}\n"
+);
+test_indented!(
+    mixin_shorthand_whitespace,
+    "= foo\n  color: red\na\n  +foo\n",
+    "a {\n  color: red;\n}\n"
+);
+test_indented!(
+    include_with_content_block,
+    "=foo\n  a\n    @content\n+foo\n  color: red\n",
+    "a {\n  color: red;\n}\n"
+);
+test_indented!(
+    include_shorthand_does_not_affect_sibling_combinator,
+    "a\n  + b\n    color: red\n",
+    "a + b {\n  color: red;\n}\n"
+);
+test_indented!(
+    at_rule_keywords,
+    "@mixin foo\n  color: red\na\n  @include foo\n",
+    "a {\n  color: red;\n}\n"
+);
+test_indented!(
+    function,
+    "@function double($x)\n  @return $x * 2\na\n  b: double(2)\n",
+    "a {\n  b: 4;\n}\n"
+);
+test_indented!(
+    if_else,
+    "a\n  @if false\n    b: c\n  @else if false\n    b: d\n  @else\n    b: e\n",
+    "a {\n  b: e;\n}\n"
+);
+test_indented!(
+    each,
+    "@each $i in 1, 2\n  .a-#{$i}\n    b: $i\n",
+    ".a-1 {\n  b: 1;\n}\n\n.a-2 {\n  b: 2;\n}\n"
+);
+test_indented!(
+    nested_properties,
+    "a\n  font:\n    family: b\n    size: 12px\n",
+    "a {\n  font-family: b;\n  font-size: 12px;\n}\n"
+);
+test_indented!(
+    media_query,
+    "a\n  @media screen\n    b: c\n",
+    "@media screen {\n  a {\n    b: c;\n  }\n}\n"
+);
+test_indented!(
+    use_builtin_module,
+    "@use \"sass:math\"\na\n  b: math.abs(-1)\n",
+    "a {\n  b: 1;\n}\n"
+);
+test_indented!(
+    plain_css_import_unquoted,
+    "@import foo.css\n",
+    "@import \"foo.css\";\n"
+);
+error_indented!(
+    indented_first_line,
+    "  a\n    b: c\n",
+    "Error: Indenting at the beginning of the document is illegal."
+);
+error_indented!(
+    indented_beneath_comment,
+    "/* a */\n  b\n",
+    "Error: Nothing may be indented beneath a comment."
+);
+error_indented!(
+    mixed_tabs_and_spaces_in_line,
+    "a\n \tb: c\n",
+    "Error: Indentation can't use both tabs and spaces."
+);
+error_indented!(
+    tabs_after_spaces,
+    "a\n  b: c\nd\n\te: f\n",
+    "Error: Expected spaces, was tabs."
+);
+error_indented!(
+    inconsistent_indentation,
+    "a\n    b: c\n  d: e\n",
+    "Error: Inconsistent indentation, expected 0 spaces."
+);
+
+#[test]
+fn scss_is_default_for_strings() {
+    assert!(
+        grass::from_string("a\n  color: red\n".to_string(), &grass::Options::default()).is_err()
+    );
+}
+
+#[test]
+fn import_sass_partial_from_scss() {
+    let input = "@import \"import_sass_partial_from_scss\";";
+    tempfile!("_import_sass_partial_from_scss.sass", "a\n  color: red\n");
+    assert_eq!(
+        "a {\n  color: red;\n}\n",
+        &grass::from_string(input.to_string(), &grass::Options::default()).expect(input)
+    );
+}
+
+#[test]
+fn import_unquoted_from_sass() {
+    let input = "@import import_unquoted_from_sass__a, import_unquoted_from_sass__b\n";
+    tempfile!("import_unquoted_from_sass__a.scss", "a { color: red; }");
+    tempfile!("import_unquoted_from_sass__b.sass", "b\n  color: blue\n");
+    assert_eq!(
+        "a {\n  color: red;\n}\n\nb {\n  color: blue;\n}\n",
+        &grass::from_string(
+            input.to_string(),
+            &grass::Options::default().indented_syntax(true)
+        )
+        .expect(input)
+    );
+}
+
+#[test]
+fn use_sass_module() {
+    let input = "@use \"use_sass_module\";\na { color: use_sass_module.$a; }";
+    tempfile!("use_sass_module.sass", "$a: red\n");
+    assert_eq!(
+        "a {\n  color: red;\n}\n",
+        &grass::from_string(input.to_string(), &grass::Options::default()).expect(input)
+    );
+}
+
+#[test]
+fn sass_index_file() {
+    let input = "@import \"sass_index_file\";";
+    tempfile!("_index.sass", "a\n  color: red\n", dir = "sass_index_file");
+    assert_eq!(
+        "a {\n  color: red;\n}\n",
+        &grass::from_string(input.to_string(), &grass::Options::default()).expect(input)
+    );
+}
+
+#[test]
+fn from_path_uses_extension() {
+    tempfile!("from_path_uses_extension.sass", "a\n  color: red\n");
+    assert_eq!(
+        "a {\n  color: red;\n}\n",
+        &grass::from_path("from_path_uses_extension.sass", &grass::Options::default()).unwrap()
+    );
+}
@@ -338,3 +338,78 @@ error!(
     invalid_item_in_comma_separated_list_inside_interpolation,
     "a {\n  color: #{red, color * #abc};\n}\n", "Error: Undefined operation \"color * #abc\"."
 );
+test!(
+    slash_list,
+    "@use \"sass:list\";\na {\n  font: list.slash(12px, 1.5);\n}\n",
+    "a {\n  font: 12px / 1.5;\n}\n"
+);
+test!(
+    slash_list_three_elements,
+    "@use \"sass:list\";\na {\n  grid-area: list.slash(1, 2, 3);\n}\n",
+    "a {\n  grid-area: 1 / 2 / 3;\n}\n"
+);
+test!(
+    slash_list_of_space_separated_lists,
+    "@use \"sass:list\";\na {\n  color: list.slash(a b, c d);\n}\n",
+    "a {\n  color: a b / c d;\n}\n"
+);
+test!(
+    slash_list_separator,
+    "@use \"sass:list\";\na {\n  color: list.separator(list.slash(a, b));\n}\n",
+    "a {\n  color: slash;\n}\n"
+);
+test!(
+    slash_list_length,
+    "@use \"sass:list\";\na {\n  color: list.length(list.slash(a, b, c));\n}\n",
+    "a {\n  color: 3;\n}\n"
+);
+test!(
+    slash_list_nth,
+    "@use \"sass:list\";\na {\n  color: list.nth(list.slash(a, b, c), 2);\n}\n",
+    "a {\n  color: b;\n}\n"
+);
+test!(
+    slash_list_inspect,
+    "@use \"sass:list\";\na {\n  color: inspect(list.slash(a, b));\n}\n",
+    "a {\n  color: a / b;\n}\n"
+);
+test!(
+    slash_list_single_element_inspect,
+    "a {\n  color: inspect(join((), a, $separator: slash));\n}\n",
+    "a {\n  color: (a/);\n}\n"
+);
+test!(
+    slash_list_is_not_equal_to_space_list,
+    "@use \"sass:list\";\na {\n  color: list.slash(a, b) == (a b);\n}\n",
+    "a {\n  color: false;\n}\n"
+);
+test!(
+    join_separator_slash,
+    "a {\n  color: join(a b, c d, $separator: slash);\n}\n",
+    "a {\n  color: a / b / c / d;\n}\n"
+);
+test!(
+    join_auto_keeps_slash,
+    "@use \"sass:list\";\na {\n  color: list.separator(join(list.slash(a, b), c));\n}\n",
+    "a {\n  color: slash;\n}\n"
+);
+test!(
+    append_separator_slash,
+    "a {\n  color: append(a b, c, $separator: slash);\n}\n",
+    "a {\n  color: a / b / c;\n}\n"
+);
+error!(
+    slash_list_one_element,
+    "@use \"sass:list\";\na {\n  color: list.slash(a);\n}\n",
+    "Error: At least two elements are required."
+);
+error!(
+    slash_list_no_elements,
+    "@use \"sass:list\";\na {\n  color: list.slash();\n}\n",
+    "Error: At least two elements are required."
+);
+error!(
+    join_invalid_separator,
+    "a {\n  color: join(a, b, $separator: foo);\n}\n",
+    "Error: $separator: Must be \"space\", \"comma\", \"slash\", or \"auto\"."
+);
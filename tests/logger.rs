@@ -0,0 +1,175 @@
+use std::{cell::RefCell, io::Write};
+
+use grass::{Logger, Options, StackFrame};
+
+#[macro_use]
+mod macros;
+
+/// The message, whether it is a deprecation, and the name and line of each frame
+type Warning = (String, bool, Vec<(String, usize)>);
+
+#[derive(Debug, Default)]
+struct Collect {
+    warnings: RefCell<Vec<Warning>>,
+    debugs: RefCell<Vec<(String, usize, usize)>>,
+}
+
+impl Logger for Collect {
+    fn warn(&self, message: &str, deprecation: bool, trace: &[StackFrame]) {
+        self.warnings.borrow_mut().push((
+            message.to_owned(),
+            deprecation,
+            trace
+                .iter()
+                .map(|frame| (frame.name().to_owned(), frame.start().line))
+                .collect(),
+        ));
+    }
+
+    fn debug(&self, message: &str, location: &StackFrame) {
+        self.debugs.borrow_mut().push((
+            message.to_owned(),
+            location.start().line,
+            location.start().column,
+        ));
+    }
+}
+
+fn compile(input: &str, logger: &Collect) -> String {
+    grass::from_string(input.to_string(), &Options::default().logger(logger)).expect(input)
+}
+
+#[test]
+fn warn() {
+    let logger = Collect::default();
+    compile("a {\n  @warn \"foo\";\n}\n", &logger);
+    assert_eq!(
+        vec![(
+            "foo".to_owned(),
+            false,
+            vec![("root stylesheet".to_owned(), 2)]
+        )],
+        logger.warnings.into_inner()
+    );
+}
+
+#[test]
+fn debug() {
+    let logger = Collect::default();
+    compile("a {\n  @debug \"foo\";\n}\n", &logger);
+    assert_eq!(
+        vec![("\"foo\"".to_owned(), 2, 10)],
+        logger.debugs.into_inner()
+    );
+}
+
+#[test]
+fn messages_do_not_affect_output() {
+    let logger = Collect::default();
+    assert_eq!(
+        "a {\n  color: red;\n}\n",
+        compile(
+            "a {\n  @warn foo;\n  @debug bar;\n  color: red;\n}\n",
+            &logger
+        )
+    );
+    assert_eq!(1, logger.warnings.borrow().len());
+    assert_eq!(1, logger.debugs.borrow().len());
+}
+
+#[test]
+fn warn_within_mixin_has_trace() {
+    let logger = Collect::default();
+    compile(
+        "@mixin foo {\n  @warn \"bar\";\n}\n\na {\n  @include foo;\n}\n",
+        &logger,
+    );
+    assert_eq!(
+        vec![("foo()".to_owned(), 2), ("root stylesheet".to_owned(), 6)],
+        logger.warnings.into_inner()[0].2
+    );
+}
+
+#[test]
+fn warn_within_import_has_trace() {
+    let input = "@import \"warn_within_import_has_trace__a\";";
+    tempfile!("warn_within_import_has_trace__a.scss", "@warn \"foo\";");
+    let logger = Collect::default();
+    compile(input, &logger);
+    assert_eq!(
+        vec![("@import".to_owned(), 1), ("root stylesheet".to_owned(), 1)],
+        logger.warnings.into_inner()[0].2
+    );
+}
+
+#[test]
+fn quiet_silences_logger() {
+    let logger = Collect::default();
+    grass::from_string(
+        "@warn \"foo\";\n@debug \"bar\";".to_string(),
+        &Options::default().logger(&logger).quiet(true),
+    )
+    .unwrap();
+    assert!(logger.warnings.borrow().is_empty());
+    assert!(logger.debugs.borrow().is_empty());
+}
+
+#[test]
+fn slash_division_warning() {
+    let logger = Collect::default();
+    grass::from_string(
+        "a {\n  $half: 10px / 2;\n  width: $half;\n  font: 12px/30px;\n}\n".to_string(),
+        &Options::default().logger(&logger).warn_slash_division(true),
+    )
+    .unwrap();
+    let warnings = logger.warnings.into_inner();
+    assert_eq!(1, warnings.len());
+    assert!(warnings[0].0.contains("math.div(10px, 2)"));
+    assert!(warnings[0].1);
+    assert_eq!(vec![("root stylesheet".to_owned(), 2)], warnings[0].2);
+}
+
+#[test]
+fn slash_division_warning_for_nested_division() {
+    let logger = Collect::default();
+    grass::from_string(
+        "a {\n  width: nth(4px/2/2 3px, 1) + 1;\n}\n".to_string(),
+        &Options::default().logger(&logger).warn_slash_division(true),
+    )
+    .unwrap();
+    let warnings = logger.warnings.into_inner();
+    assert_eq!(1, warnings.len());
+    assert!(warnings[0]
+        .0
+        .contains("math.div(math.div(4px, 2), 2) or calc(4px / 2 / 2)"));
+}
+
+#[test]
+fn no_slash_division_warning_without_arithmetic() {
+    let logger = Collect::default();
+    grass::from_string(
+        "a {\n  b: foo(4px/2);\n  c: nth(4px/2 3px, 1);\n}\n".to_string(),
+        &Options::default().logger(&logger).warn_slash_division(true),
+    )
+    .unwrap();
+    assert!(logger.warnings.borrow().is_empty());
+}
+
+#[test]
+fn slash_division_warning_is_opt_in() {
+    let logger = Collect::default();
+    compile("a {\n  width: (10px / 2);\n}\n", &logger);
+    assert!(logger.warnings.borrow().is_empty());
+}
+
+#[test]
+fn math_div_non_number_is_deprecated() {
+    let logger = Collect::default();
+    compile(
+        "@use \"sass:math\";\na {\n  b: math.div(\"a\", 2);\n}\n",
+        &logger,
+    );
+    let warnings = logger.warnings.into_inner();
+    assert_eq!(1, warnings.len());
+    assert!(warnings[0].1);
+}
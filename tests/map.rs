@@ -225,3 +225,107 @@ error!(
     first_map_value_missing_closing_paren,
     "$a: (a: b", "Error: expected \")\"."
 );
+test!(
+    map_get_nested,
+    "a {\n  color: map-get((a: (b: (c: d))), a, b, c);\n}\n",
+    "a {\n  color: d;\n}\n"
+);
+test!(
+    map_get_nested_missing_key,
+    "a {\n  color: inspect(map-get((a: (b: c)), a, d, e));\n}\n",
+    "a {\n  color: null;\n}\n"
+);
+test!(
+    map_get_nested_through_non_map,
+    "a {\n  color: inspect(map-get((a: b), a, b));\n}\n",
+    "a {\n  color: null;\n}\n"
+);
+test!(
+    map_has_key_nested,
+    "a {\n  color: map-has-key((a: (b: (c: d))), a, b, c);\n}\n",
+    "a {\n  color: true;\n}\n"
+);
+test!(
+    map_has_key_nested_missing_key,
+    "a {\n  color: map-has-key((a: (b: (c: d))), a, c);\n}\n",
+    "a {\n  color: false;\n}\n"
+);
+test!(
+    map_merge_nested,
+    "a {\n  color: inspect(map-merge((a: (b: (c: d))), a, b, (e: f)));\n}\n",
+    "a {\n  color: (a: (b: (c: d, e: f)));\n}\n"
+);
+test!(
+    map_merge_nested_replaces_non_map,
+    "a {\n  color: inspect(map-merge((a: b), a, (c: d)));\n}\n",
+    "a {\n  color: (a: (c: d));\n}\n"
+);
+test!(
+    map_merge_nested_adds_missing_maps,
+    "a {\n  color: inspect(map-merge((a: b), c, d, (e: f)));\n}\n",
+    "a {\n  color: (a: b, c: (d: (e: f)));\n}\n"
+);
+error!(
+    map_merge_nested_last_arg_not_map,
+    "a {\n  color: map-merge((a: b), a, c);\n}\n", "Error: $map2: c is not a map."
+);
+test!(
+    map_module_set,
+    "@use 'sass:map';\na {\n  color: inspect(map.set((a: b), a, c));\n}\n",
+    "a {\n  color: (a: c);\n}\n"
+);
+test!(
+    map_module_set_new_key,
+    "@use 'sass:map';\na {\n  color: inspect(map.set((a: b), c, d));\n}\n",
+    "a {\n  color: (a: b, c: d);\n}\n"
+);
+test!(
+    map_module_set_nested,
+    "@use 'sass:map';\na {\n  color: inspect(map.set((a: (b: c)), a, b, d));\n}\n",
+    "a {\n  color: (a: (b: d));\n}\n"
+);
+test!(
+    map_module_set_named,
+    "@use 'sass:map';\na {\n  color: inspect(map.set((a: b), $key: a, $value: c));\n}\n",
+    "a {\n  color: (a: c);\n}\n"
+);
+error!(
+    map_module_set_no_value,
+    "@use 'sass:map';\na {\n  color: map.set((a: b), a);\n}\n",
+    "Error: Expected $args to contain a value."
+);
+error!(
+    map_module_set_no_key,
+    "@use 'sass:map';\na {\n  color: map.set((a: b));\n}\n",
+    "Error: Expected $args to contain a key."
+);
+test!(
+    map_module_deep_merge,
+    "@use 'sass:map';\na {\n  color: inspect(map.deep-merge((a: (b: c, d: e)), (a: (b: f), g: h)));\n}\n",
+    "a {\n  color: (a: (b: f, d: e), g: h);\n}\n"
+);
+test!(
+    map_module_deep_merge_replaces_non_map,
+    "@use 'sass:map';\na {\n  color: inspect(map.deep-merge((a: b), (a: (c: d))));\n}\n",
+    "a {\n  color: (a: (c: d));\n}\n"
+);
+error!(
+    map_module_deep_merge_non_map,
+    "@use 'sass:map';\na {\n  color: map.deep-merge((a: b), c);\n}\n",
+    "Error: $map2: c is not a map."
+);
+test!(
+    map_module_deep_remove,
+    "@use 'sass:map';\na {\n  color: inspect(map.deep-remove((a: (b: c, d: e)), a, b));\n}\n",
+    "a {\n  color: (a: (d: e));\n}\n"
+);
+test!(
+    map_module_deep_remove_single_key,
+    "@use 'sass:map';\na {\n  color: inspect(map.deep-remove((a: b, c: d), a));\n}\n",
+    "a {\n  color: (c: d);\n}\n"
+);
+test!(
+    map_module_deep_remove_missing_path,
+    "@use 'sass:map';\na {\n  color: inspect(map.deep-remove((a: b), a, b, c));\n}\n",
+    "a {\n  color: (a: b);\n}\n"
+);
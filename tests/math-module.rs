@@ -588,3 +588,83 @@ test!(
     "@use 'sass:math';\na {\n  color: math.atan2(math.acos(2), 3deg);\n}\n",
     "a {\n  color: NaNdeg;\n}\n"
 );
+test!(
+    div_unitless,
+    "@use 'sass:math';\na {\n  color: math.div(1, 2);\n}\n",
+    "a {\n  color: 0.5;\n}\n"
+);
+test!(
+    div_unit_by_unitless,
+    "@use 'sass:math';\na {\n  color: math.div(10px, 4);\n}\n",
+    "a {\n  color: 2.5px;\n}\n"
+);
+test!(
+    div_same_units,
+    "@use 'sass:math';\na {\n  color: math.div(10px, 2px);\n}\n",
+    "a {\n  color: 5;\n}\n"
+);
+test!(
+    div_comparable_units,
+    "@use 'sass:math';\na {\n  color: math.div(1in, 1px);\n}\n",
+    "a {\n  color: 96;\n}\n"
+);
+test!(
+    div_non_comparable_units,
+    "@use 'sass:math';\na {\n  color: math.unit(math.div(1em, 1px));\n}\n",
+    "a {\n  color: \"em/px\";\n}\n"
+);
+test!(
+    div_unitless_by_unit,
+    "@use 'sass:math';\na {\n  color: math.unit(math.div(1, 1px));\n}\n",
+    "a {\n  color: \"px^-1\";\n}\n"
+);
+test!(
+    div_result_multiplied_by_denominator,
+    "@use 'sass:math';\na {\n  color: math.div(1em, 1px) * 2px;\n}\n",
+    "a {\n  color: 2em;\n}\n"
+);
+test!(
+    div_zero_by_zero,
+    "@use 'sass:math';\na {\n  color: math.div(0, 0);\n}\n",
+    "a {\n  color: NaN;\n}\n"
+);
+test!(
+    div_by_zero,
+    "@use 'sass:math';\na {\n  color: math.div(1, 0);\n}\n",
+    "a {\n  color: Infinity;\n}\n"
+);
+test!(
+    div_negative_by_zero_keeps_units,
+    "@use 'sass:math';\na {\n  color: math.div(-1px, 0);\n}\n",
+    "a {\n  color: -Infinitypx;\n}\n"
+);
+test!(
+    div_zero_by_zero_keeps_units,
+    "@use 'sass:math';\na {\n  color: math.div(0px, 0);\n}\n",
+    "a {\n  color: NaNpx;\n}\n"
+);
+test!(
+    div_infinity_by_infinity,
+    "@use 'sass:math';\na {\n  color: math.div(math.div(1, 0), math.div(1, 0));\n}\n",
+    "a {\n  color: NaN;\n}\n"
+);
+test!(
+    div_by_zero_is_greater_than_finite_numbers,
+    "@use 'sass:math';\na {\n  color: math.div(1, 0) > 100000;\n}\n",
+    "a {\n  color: true;\n}\n"
+);
+test!(
+    div_is_not_slash_separated,
+    "@use 'sass:math';\na {\n  color: math.div(12px, 30px);\n}\n",
+    "a {\n  color: 0.4;\n}\n"
+);
+test!(
+    div_non_number,
+    "@use 'sass:math';\na {\n  color: math.div(\"a\", 2);\n}\n",
+    "a {\n  color: \"a\"/2;\n}\n"
+);
+error!(
+    div_too_many_args,
+    "@use 'sass:math';\na {\n  color: math.div(1, 2, 3);\n}\n",
+    "Error: Only 2 arguments allowed, but 3 were passed."
+);
@@ -96,3 +96,152 @@ error!(
     media_feature_missing_curly_brace_after_hash,
     "@media foo and # {}", "Error: expected \"{\"."
 );
+test!(
+    nested_media_queries_are_merged,
+    "@media screen {
+        @media (min-width: 10px) {
+            a {
+                color: red;
+            }
+        }
+    }",
+    "@media screen and (min-width: 10px) {\n  a {\n    color: red;\n  }\n}\n"
+);
+test!(
+    nested_media_query_is_emitted_after_parent,
+    "@media screen {
+        a {
+            color: red;
+        }
+        @media (color) {
+            b {
+                color: red;
+            }
+        }
+        c {
+            color: red;
+        }
+    }",
+    "@media screen {\n  a {\n    color: red;\n  }\n  c {\n    color: red;\n  }\n}\n@media screen and (color) {\n  b {\n    color: red;\n  }\n}\n"
+);
+test!(
+    nested_media_query_lists_are_merged,
+    "@media screen, print {
+        @media (color) {
+            a {
+                color: red;
+            }
+        }
+    }",
+    "@media screen and (color), print and (color) {\n  a {\n    color: red;\n  }\n}\n"
+);
+test!(
+    nested_media_queries_in_style_rule_are_merged,
+    "a {
+        @media screen {
+            color: red;
+            @media (color) {
+                color: green;
+            }
+        }
+    }",
+    "@media screen {\n  a {\n    color: red;\n  }\n}\n@media screen and (color) {\n  a {\n    color: green;\n  }\n}\n"
+);
+test!(
+    nested_media_query_keeps_modifier,
+    "@media only screen {
+        @media (color) {
+            a {
+                color: red;
+            }
+        }
+    }",
+    "@media only screen and (color) {\n  a {\n    color: red;\n  }\n}\n"
+);
+test!(
+    nested_media_query_all_is_omitted,
+    "@media all {
+        @media (color) {
+            a {
+                color: red;
+            }
+        }
+    }",
+    "@media (color) {\n  a {\n    color: red;\n  }\n}\n"
+);
+test!(
+    nested_media_queries_with_different_types_are_removed,
+    "@media screen {
+        @media print {
+            a {
+                color: red;
+            }
+        }
+    }",
+    ""
+);
+test!(
+    nested_media_query_contradicting_negation_is_removed,
+    "@media not screen {
+        @media screen and (color) {
+            a {
+                color: red;
+            }
+        }
+    }",
+    ""
+);
+test!(
+    nested_media_queries_that_cant_be_merged_stay_nested,
+    "@media not screen {
+        @media not print {
+            a {
+                color: red;
+            }
+        }
+    }",
+    "@media not screen {\n  @media not print {\n    a {\n      color: red;\n    }\n  }\n}\n"
+);
+test!(
+    nested_media_query_within_supports_is_not_moved,
+    "@media screen {
+        @supports (a: b) {
+            @media (color) {
+                a {
+                    color: red;
+                }
+            }
+        }
+    }",
+    "@media screen {\n  @supports (a: b) {\n    @media screen and (color) {\n      a {\n        color: red;\n      }\n    }\n  }\n}\n"
+);
+test!(
+    query_is_normalized,
+    "@media   screen   AND   (color) ,print {
+        a {
+            color: red;
+        }
+    }",
+    "@media screen and (color), print {\n  a {\n    color: red;\n  }\n}\n"
+);
+test!(
+    interpolated_query_list_is_parsed,
+    "$query: \"screen, print\";
+    @media #{$query} {
+        @media (color) {
+            a {
+                color: red;
+            }
+        }
+    }",
+    "@media screen and (color), print and (color) {\n  a {\n    color: red;\n  }\n}\n"
+);
+test!(
+    only_invisible_children,
+    "@media screen {
+        %a {
+            color: red;
+        }
+    }",
+    ""
+);
@@ -565,3 +565,32 @@ error!(
     }",
     "Error: expected \"{\"."
 );
+test!(
+    mixin_body_evaluated_each_include,
+    "@mixin foo($a) {
+        $b: $a * 2;
+        color: $b;
+        a:hover { color: $a; }
+        @if $a == 1 { one: $a; } @else if $a == 2 { two: $a; } @else { other: $a; }
+        font: { family: $a; }
+    }
+
+    a {
+        @include foo(1);
+        @include foo(2);
+        @include foo(3);
+    }",
+    "a {\n  color: 2;\n  one: 1;\n  font-family: 1;\n  color: 4;\n  two: 2;\n  font-family: 2;\n  color: 6;\n  other: 3;\n  font-family: 3;\n}\na a:hover {\n  color: 1;\n}\na a:hover {\n  color: 2;\n}\na a:hover {\n  color: 3;\n}\n"
+);
+test!(
+    mixin_with_keyframes_blocks,
+    "@mixin foo {
+        from { color: red; }
+        50% { color: blue; }
+    }
+
+    @keyframes bar {
+        @include foo;
+    }",
+    "@keyframes bar {\n  from {\n    color: red;\n  }\n  50% {\n    color: blue;\n  }\n}\n"
+);
@@ -0,0 +1,234 @@
+use std::io::Write;
+
+use grass::{Options, SassList, SassValue};
+
+#[macro_use]
+mod macros;
+
+fn brand() -> SassValue {
+    SassValue::string("#639", false)
+}
+
+fn error_message(input: &str, options: &Options) -> String {
+    match grass::from_string(input.to_string(), options) {
+        Ok(..) => panic!("did not fail"),
+        Err(e) => e.to_string().chars().take_while(|c| *c != '\n').collect(),
+    }
+}
+
+test!(
+    global_variable,
+    "a {\n  color: $brand;\n}\n",
+    "a {\n  color: #639;\n}\n",
+    grass::Options::default().global_variable("brand", brand())
+);
+test!(
+    global_variable_with_dollar,
+    "a {\n  color: $brand;\n}\n",
+    "a {\n  color: #639;\n}\n",
+    grass::Options::default().global_variable("$brand", brand())
+);
+test!(
+    global_variable_underscore_and_hyphen_are_equivalent,
+    "a {\n  color: $brand-color;\n}\n",
+    "a {\n  color: #639;\n}\n",
+    grass::Options::default().global_variable("brand_color", brand())
+);
+test!(
+    global_variable_not_overridden_by_default,
+    "$brand: red !default;\na {\n  color: $brand;\n}\n",
+    "a {\n  color: #639;\n}\n",
+    grass::Options::default().global_variable("brand", brand())
+);
+test!(
+    global_variable_overridden_by_declaration,
+    "$brand: red;\na {\n  color: $brand;\n}\n",
+    "a {\n  color: red;\n}\n",
+    grass::Options::default().global_variable("brand", brand())
+);
+test!(
+    global_variable_exists,
+    "a {\n  color: global-variable-exists(brand);\n}\n",
+    "a {\n  color: true;\n}\n",
+    grass::Options::default().global_variable("brand", brand())
+);
+test!(
+    global_variables,
+    "a {\n  color: $a $b;\n}\n",
+    "a {\n  color: 1 2;\n}\n",
+    grass::Options::default().global_variables(vec![
+        ("a", SassValue::number(1.0, "")),
+        ("b", SassValue::number(2.0, "")),
+    ])
+);
+test!(
+    global_variable_list,
+    "a {\n  margin: $spacing;\n}\n",
+    "a {\n  margin: 1px 2px;\n}\n",
+    grass::Options::default().global_variable(
+        "spacing",
+        SassValue::List(SassList::new(
+            vec![SassValue::number(1.0, "px"), SassValue::number(2.0, "px")],
+            grass::ListSeparator::Space,
+            false,
+        ))
+    )
+);
+
+#[test]
+fn global_variable_visible_to_imports() {
+    let input = "@import \"global_variable_visible_to_imports__a\";";
+    tempfile!(
+        "global_variable_visible_to_imports__a.scss",
+        "a { color: $brand; }"
+    );
+    assert_eq!(
+        "a {\n  color: #639;\n}\n",
+        &grass::from_string(
+            input.to_string(),
+            &Options::default().global_variable("brand", brand())
+        )
+        .expect(input)
+    );
+}
+
+#[test]
+fn global_variable_not_visible_to_modules() {
+    let input = "@use \"global_variable_not_visible_to_modules__a\" as a;\nb { color: a.$c; }";
+    tempfile!(
+        "global_variable_not_visible_to_modules__a.scss",
+        "$brand: red !default;\n$c: $brand;"
+    );
+    assert_eq!(
+        "b {\n  color: red;\n}\n",
+        &grass::from_string(
+            input.to_string(),
+            &Options::default().global_variable("brand", brand())
+        )
+        .expect(input)
+    );
+}
+
+#[test]
+fn module_config() {
+    let input = "@use \"module_config__a\" as a;\nb { color: a.$brand; }";
+    tempfile!("module_config__a.scss", "$brand: red !default;");
+    assert_eq!(
+        "b {\n  color: #639;\n}\n",
+        &grass::from_string(
+            input.to_string(),
+            &Options::default().module_config("module_config__a", "brand", brand())
+        )
+        .expect(input)
+    );
+}
+
+#[test]
+fn module_config_only_applies_to_matching_url() {
+    let input =
+        "@use \"module_config_only_applies_to_matching_url__a\" as a;\nb { color: a.$brand; }";
+    tempfile!(
+        "module_config_only_applies_to_matching_url__a.scss",
+        "$brand: red !default;"
+    );
+    assert_eq!(
+        "b {\n  color: red;\n}\n",
+        &grass::from_string(
+            input.to_string(),
+            &Options::default().module_config("other", "brand", brand())
+        )
+        .expect(input)
+    );
+}
+
+#[test]
+fn module_config_with_clause_takes_precedence() {
+    let input = "@use \"module_config_with_clause_takes_precedence__a\" as a with ($brand: blue);\nb { color: a.$brand a.$accent; }";
+    tempfile!(
+        "module_config_with_clause_takes_precedence__a.scss",
+        "$brand: red !default;\n$accent: red !default;"
+    );
+    assert_eq!(
+        "b {\n  color: blue #639;\n}\n",
+        &grass::from_string(
+            input.to_string(),
+            &Options::default()
+                .module_config(
+                    "module_config_with_clause_takes_precedence__a",
+                    "brand",
+                    brand()
+                )
+                .module_config(
+                    "module_config_with_clause_takes_precedence__a",
+                    "accent",
+                    brand()
+                )
+        )
+        .expect(input)
+    );
+}
+
+#[test]
+fn module_config_last_value_wins() {
+    let input = "@use \"module_config_last_value_wins__a\" as a;\nb { color: a.$brand; }";
+    tempfile!(
+        "module_config_last_value_wins__a.scss",
+        "$brand: red !default;"
+    );
+    assert_eq!(
+        "b {\n  color: #639;\n}\n",
+        &grass::from_string(
+            input.to_string(),
+            &Options::default()
+                .module_config(
+                    "module_config_last_value_wins__a",
+                    "brand",
+                    SassValue::string("blue", false)
+                )
+                .module_config("module_config_last_value_wins__a", "brand", brand())
+        )
+        .expect(input)
+    );
+}
+
+#[test]
+fn module_config_forward() {
+    let input = "@use \"module_config_forward__a\" as a;\nb { color: a.$brand; }";
+    tempfile!(
+        "module_config_forward__a.scss",
+        "@forward \"module_config_forward__b\";"
+    );
+    tempfile!("module_config_forward__b.scss", "$brand: red !default;");
+    assert_eq!(
+        "b {\n  color: #639;\n}\n",
+        &grass::from_string(
+            input.to_string(),
+            &Options::default().module_config("module_config_forward__b", "brand", brand())
+        )
+        .expect(input)
+    );
+}
+
+#[test]
+fn module_config_not_default() {
+    let input = "@use \"module_config_not_default__a\" as a;";
+    tempfile!("module_config_not_default__a.scss", "$brand: red;");
+    assert_eq!(
+        "Error: This variable was not declared with !default in the @used module.",
+        error_message(
+            input,
+            &Options::default().module_config("module_config_not_default__a", "brand", brand())
+        )
+    );
+}
+
+#[test]
+fn module_config_builtin_module() {
+    assert_eq!(
+        "Error: Built-in modules can't be configured.",
+        error_message(
+            "@use \"sass:math\";",
+            &Options::default().module_config("sass:math", "pi", brand())
+        )
+    );
+}
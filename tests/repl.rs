@@ -0,0 +1,84 @@
+use grass::{Options, Repl, SassValue};
+
+fn evaluate(repl: &mut Repl, line: &str) -> Option<String> {
+    repl.evaluate(line).expect(line)
+}
+
+fn error_message(repl: &mut Repl, line: &str) -> String {
+    match repl.evaluate(line) {
+        Ok(..) => panic!("did not fail"),
+        Err(e) => e.to_string().chars().take_while(|c| *c != '\n').collect(),
+    }
+}
+
+#[test]
+fn expression() {
+    let options = Options::default();
+    let mut repl = Repl::new(&options);
+    assert_eq!(Some("3".to_string()), evaluate(&mut repl, "1 + 2"));
+    assert_eq!(Some("\"a\"".to_string()), evaluate(&mut repl, "\"a\""));
+    assert_eq!(
+        Some("1px 2px, 3px".to_string()),
+        evaluate(&mut repl, "1px 2px, 3px")
+    );
+    assert_eq!(Some("(a: 1)".to_string()), evaluate(&mut repl, "(a: 1)"));
+}
+
+#[test]
+fn variables_persist() {
+    let options = Options::default();
+    let mut repl = Repl::new(&options);
+    assert_eq!(Some("10px".to_string()), evaluate(&mut repl, "$x: 10px"));
+    assert_eq!(
+        Some("20px".to_string()),
+        evaluate(&mut repl, "$y : $x * 2;")
+    );
+    assert_eq!(Some("30px".to_string()), evaluate(&mut repl, "$x + $y"));
+}
+
+#[test]
+fn default_variable_keeps_value() {
+    let options = Options::default();
+    let mut repl = Repl::new(&options);
+    evaluate(&mut repl, "$x: red");
+    assert_eq!(
+        Some("red".to_string()),
+        evaluate(&mut repl, "$x: blue !default")
+    );
+}
+
+#[test]
+fn use_builtin_module() {
+    let options = Options::default();
+    let mut repl = Repl::new(&options);
+    assert_eq!(None, evaluate(&mut repl, "@use \"sass:math\""));
+    assert_eq!(
+        Some("3".to_string()),
+        evaluate(&mut repl, "math.max(1, 3, 2)")
+    );
+    assert_eq!(None, evaluate(&mut repl, "@use \"sass:color\" as c;"));
+    assert_eq!(
+        Some("#004d99".to_string()),
+        evaluate(&mut repl, "c.adjust(#036, $lightness: 10%)")
+    );
+}
+
+#[test]
+fn global_variables_from_options() {
+    let options = Options::default().global_variable("brand", SassValue::string("#639", false));
+    let mut repl = Repl::new(&options);
+    assert_eq!(Some("#639".to_string()), evaluate(&mut repl, "$brand"));
+}
+
+#[test]
+fn error_does_not_lose_state() {
+    let options = Options::default();
+    let mut repl = Repl::new(&options);
+    evaluate(&mut repl, "$x: 1");
+    assert_eq!(
+        "Error: Expected expression.",
+        error_message(&mut repl, "$x + ")
+    );
+    assert_eq!("Error: Undefined variable.", error_message(&mut repl, "$y"));
+    assert_eq!(Some("1".to_string()), evaluate(&mut repl, "$x"));
+}
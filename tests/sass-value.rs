@@ -0,0 +1,365 @@
+use grass::{
+    ListSeparator, Options, SassColor, SassList, SassMap, SassNumber, SassString, SassValue,
+};
+
+fn compile(input: &str, options: &Options) -> String {
+    grass::from_string(input.to_string(), options).expect(input)
+}
+
+/// Compile `input`, recording the argument passed to `capture($value)`
+fn received(input: &str) -> SassValue {
+    use std::sync::{Arc, Mutex};
+
+    let value = Arc::new(Mutex::new(SassValue::Null));
+    let captured = Arc::clone(&value);
+    let options = Options::default().add_function("capture($value)", move |args| {
+        *captured.lock().unwrap() = args[0].clone();
+        Ok(SassValue::Null)
+    });
+    compile(input, &options);
+    let value = value.lock().unwrap().clone();
+    value
+}
+
+#[test]
+fn number_with_unit() {
+    let value = received("a { b: capture(1.5px) }");
+    match value {
+        SassValue::Number(n) => {
+            assert_eq!(1.5, n.value());
+            assert_eq!("px", n.unit());
+            assert!(!n.is_unitless());
+            assert!(!n.is_int());
+        }
+        v => panic!("expected a number, found {:?}", v),
+    }
+}
+
+#[test]
+fn number_with_compound_unit() {
+    let value = received("a { b: capture(2px * 3em) }");
+    assert_eq!(Some((6.0, "px*em".to_string())), value.as_number());
+}
+
+#[test]
+fn number_equality_converts_units() {
+    assert_eq!(SassNumber::new(1.0, "in"), SassNumber::new(96.0, "px"));
+    assert_ne!(SassNumber::new(1.0, "px"), SassNumber::unitless(1.0));
+    assert_ne!(SassNumber::new(1.0, "px"), SassNumber::new(1.0, "em"));
+}
+
+#[test]
+fn number_convert() {
+    let n = SassNumber::new(1.0, "in").convert("px").unwrap();
+    assert_eq!(96.0, n.value());
+    assert_eq!("px", n.unit());
+    assert!(SassNumber::new(1.0, "px").convert("s").is_none());
+    assert!(SassNumber::new(1.0, "px").convert("").is_none());
+    assert!(SassNumber::unitless(1.0).convert("px").is_none());
+    assert_eq!(
+        Some(SassNumber::unitless(1.0)),
+        SassNumber::unitless(1.0).convert("")
+    );
+    assert!(SassNumber::new(1.0, "em").is_comparable_to("em"));
+    assert!(!SassNumber::new(1.0, "em").is_comparable_to("rem"));
+}
+
+#[test]
+fn number_infinity() {
+    assert_eq!(f64::INFINITY, SassNumber::new(f64::INFINITY, "px").value());
+    assert_eq!(
+        f64::NEG_INFINITY,
+        SassNumber::unitless(f64::NEG_INFINITY).value()
+    );
+    assert!(SassNumber::unitless(f64::NAN).value().is_nan());
+}
+
+#[test]
+fn quoted_string() {
+    match received("a { b: capture(\"c\") }") {
+        SassValue::String(s) => {
+            assert_eq!("c", s.text());
+            assert!(s.is_quoted());
+        }
+        v => panic!("expected a string, found {:?}", v),
+    }
+}
+
+#[test]
+fn string_equality_ignores_quotes() {
+    assert_eq!(SassString::new("a", true), SassString::new("a", false));
+    assert_eq!(
+        SassValue::string("a", true),
+        received("a { b: capture(a) }")
+    );
+}
+
+#[test]
+fn bracketed_comma_list() {
+    match received("a { b: capture([1, 2]) }") {
+        SassValue::List(list) => {
+            assert_eq!(ListSeparator::Comma, list.separator());
+            assert!(list.has_brackets());
+            assert_eq!(
+                &[SassValue::number(1.0, ""), SassValue::number(2.0, "")],
+                list.elements()
+            );
+        }
+        v => panic!("expected a list, found {:?}", v),
+    }
+}
+
+#[test]
+fn list_equality_respects_separator_and_brackets() {
+    let elements = vec![SassValue::bool(true), SassValue::Null];
+    let list = SassList::new(elements.clone(), ListSeparator::Space, false);
+    assert_eq!(
+        list,
+        SassList::new(elements.clone(), ListSeparator::Space, false)
+    );
+    assert_ne!(
+        list,
+        SassList::new(elements.clone(), ListSeparator::Comma, false)
+    );
+    assert_ne!(list, SassList::new(elements, ListSeparator::Space, true));
+}
+
+#[test]
+fn slash_list() {
+    match received("@use \"sass:list\";\na { b: capture(list.slash(1, 2)) }") {
+        SassValue::List(list) => {
+            assert_eq!(ListSeparator::Slash, list.separator());
+            assert!(!list.has_brackets());
+        }
+        v => panic!("expected a list, found {:?}", v),
+    }
+    let options = Options::default().add_function("font()", |_| {
+        Ok(SassValue::List(SassList::new(
+            vec![SassValue::number(12.0, "px"), SassValue::number(1.5, "")],
+            ListSeparator::Slash,
+            false,
+        )))
+    });
+    assert_eq!(
+        "a {\n  font: 12px / 1.5;\n}\n",
+        compile("a { font: font() }", &options)
+    );
+}
+
+#[test]
+fn calculation() {
+    match received("a { b: capture(clamp(1px, 50%, 3em)) }") {
+        SassValue::Calculation(calc) => {
+            assert_eq!("clamp", calc.name());
+            assert_eq!(
+                "clamp(1px, 50%, 3em)",
+                SassValue::Calculation(calc).inspect()
+            );
+        }
+        v => panic!("expected a calculation, found {:?}", v),
+    }
+    assert_eq!(
+        SassValue::number(3.0, "px"),
+        received("a { b: capture(calc(1px + 2px)) }")
+    );
+}
+
+#[test]
+fn arglist_is_comma_list() {
+    let options = Options::default().add_function("args($args...)", |args| {
+        Ok(SassValue::bool(
+            args[0]
+                == SassValue::List(SassList::new(
+                    vec![SassValue::number(1.0, ""), SassValue::number(2.0, "")],
+                    ListSeparator::Comma,
+                    false,
+                )),
+        ))
+    });
+    assert_eq!(
+        "a {\n  b: true;\n}\n",
+        compile("a { b: args(1, 2) }", &options)
+    );
+}
+
+#[test]
+fn map() {
+    match received("a { b: capture((c: 1, d: 2)) }") {
+        SassValue::Map(map) => {
+            assert_eq!(2, map.len());
+            assert_eq!(
+                Some(&SassValue::number(2.0, "")),
+                map.get(&SassValue::string("d", true))
+            );
+            assert_eq!(None, map.get(&SassValue::string("e", false)));
+        }
+        v => panic!("expected a map, found {:?}", v),
+    }
+}
+
+#[test]
+fn map_insert_uses_sass_equality() {
+    let mut map = SassMap::new();
+    assert_eq!(
+        None,
+        map.insert(SassValue::number(96.0, "px"), SassValue::bool(true))
+    );
+    assert_eq!(
+        Some(SassValue::bool(true)),
+        map.insert(SassValue::number(1.0, "in"), SassValue::bool(false))
+    );
+    assert_eq!(1, map.len());
+    assert_eq!(
+        Some(SassValue::bool(false)),
+        map.remove(&SassValue::number(96.0, "px"))
+    );
+    assert!(map.is_empty());
+}
+
+#[test]
+fn map_equality_ignores_order() {
+    let mut a = SassMap::new();
+    a.insert(SassValue::string("a", false), SassValue::Null);
+    a.insert(SassValue::string("b", false), SassValue::Null);
+    let mut b = SassMap::new();
+    b.insert(SassValue::string("b", false), SassValue::Null);
+    b.insert(SassValue::string("a", false), SassValue::Null);
+    assert_eq!(a, b);
+}
+
+#[test]
+fn color_channels() {
+    match received("a { b: capture(rgba(255, 0, 0, 0.5)) }") {
+        SassValue::Color(color) => {
+            assert_eq!(255.0, color.red());
+            assert_eq!(0.0, color.green());
+            assert_eq!(0.0, color.blue());
+            assert_eq!(0.5, color.alpha());
+            assert_eq!(0.0, color.hue());
+            assert_eq!(100.0, color.saturation());
+            assert_eq!(50.0, color.lightness());
+        }
+        v => panic!("expected a color, found {:?}", v),
+    }
+}
+
+#[test]
+fn color_equality() {
+    assert_eq!(
+        SassColor::rgb(255.0, 0.0, 0.0, 1.0),
+        SassColor::hsl(0.0, 100.0, 50.0, 1.0)
+    );
+    assert_eq!(
+        SassValue::Color(SassColor::rgb(255.0, 0.0, 0.0, 1.0)),
+        received("a { b: capture(red) }")
+    );
+}
+
+#[test]
+fn booleans_and_null() {
+    assert_eq!(SassValue::bool(true), received("a { b: capture(true) }"));
+    assert_eq!(SassValue::bool(false), received("a { b: capture(false) }"));
+    assert_eq!(SassValue::Null, received("a { b: capture(null) }"));
+    assert!(!SassValue::Null.is_truthy());
+    assert!(SassValue::number(0.0, "").is_truthy());
+}
+
+#[test]
+fn function_ref() {
+    match received("a { b: capture(get-function(\"rgb\")) }") {
+        SassValue::Function(f) => assert_eq!("rgb", f.name()),
+        v => panic!("expected a function, found {:?}", v),
+    }
+}
+
+#[test]
+fn kind() {
+    assert_eq!("number", SassValue::number(1.0, "px").kind());
+    assert_eq!("string", SassValue::string("a", true).kind());
+    assert_eq!("map", SassValue::Map(SassMap::new()).kind());
+    assert_eq!("bool", SassValue::bool(false).kind());
+    assert_eq!("null", SassValue::Null.kind());
+}
+
+#[test]
+fn inspect() {
+    assert_eq!("1.5px", SassValue::number(1.5, "px").inspect());
+    assert_eq!("\"a\"", SassValue::string("a", true).inspect());
+    assert_eq!("a", SassValue::string("a", false).inspect());
+    assert_eq!("null", SassValue::Null.inspect());
+    assert_eq!(
+        "red",
+        SassValue::from(SassColor::rgb(255.0, 0.0, 0.0, 1.0)).inspect()
+    );
+    assert_eq!(
+        "[1, 2]",
+        SassValue::List(SassList::new(
+            vec![SassValue::number(1.0, ""), SassValue::number(2.0, "")],
+            ListSeparator::Comma,
+            true
+        ))
+        .inspect()
+    );
+    assert_eq!(
+        "(1,)",
+        SassValue::List(SassList::new(
+            vec![SassValue::number(1.0, "")],
+            ListSeparator::Comma,
+            false
+        ))
+        .inspect()
+    );
+    let mut map = SassMap::new();
+    map.insert(SassValue::string("a", false), SassValue::string("b", true));
+    assert_eq!("(a: \"b\")", SassValue::Map(map).inspect());
+}
+
+#[test]
+fn inspect_matches_builtin() {
+    for input in &[
+        "(a: (1 2), b: [3])",
+        "(1px 2px, 3px)",
+        "\"a\" b",
+        "()",
+        "#abc",
+    ] {
+        let options = Options::default().add_function("inspect-value($value)", |args| {
+            Ok(SassValue::string(&args[0].inspect(), false))
+        });
+        assert_eq!(
+            compile(
+                &format!("a {{ b: inspect({}) }}", input),
+                &Options::default()
+            ),
+            compile(&format!("a {{ b: inspect-value({}) }}", input), &options),
+        );
+    }
+}
+
+#[test]
+fn values_round_trip() {
+    let options = Options::default().add_function("identity($value)", |args| Ok(args[0].clone()));
+    assert_eq!(
+        "a {\n  b: red;\n  c: #AbC;\n  d: 0.3333333333;\n  e: [a, \"b\"];\n}\n",
+        compile(
+            "a { b: identity(red); c: identity(#AbC); d: identity(1/3); e: identity([a, \"b\"]) }",
+            &options
+        )
+    );
+}
+
+#[test]
+fn returns_map() {
+    let options = Options::default().add_function("theme()", |_| {
+        let mut map = SassMap::new();
+        map.insert(
+            SassValue::string("primary", true),
+            SassColor::rgb(0.0, 0.0, 255.0, 1.0).into(),
+        );
+        Ok(map.into())
+    });
+    assert_eq!(
+        "a {\n  color: blue;\n}\n",
+        compile("a { color: map-get(theme(), primary) }", &options)
+    );
+}
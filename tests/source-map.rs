@@ -0,0 +1,183 @@
+use std::io::Write;
+
+#[macro_use]
+mod macros;
+
+fn mappings(input: &str, options: &grass::Options) -> String {
+    grass::from_string_with_source_map(input.to_string(), options)
+        .expect(input)
+        .1
+        .mappings()
+        .to_string()
+}
+
+#[test]
+fn single_rule() {
+    assert_eq!(
+        "AAAA;EAAI",
+        mappings("a { color: red; }", &grass::Options::default())
+    );
+}
+
+#[test]
+fn css_is_unchanged() {
+    let input = "a {\n  color: red;\n  b { c: d; }\n}\n";
+    assert_eq!(
+        grass::from_string(input.to_string(), &grass::Options::default()).unwrap(),
+        grass::from_string_with_source_map(input.to_string(), &grass::Options::default())
+            .unwrap()
+            .0
+    );
+}
+
+#[test]
+fn nested_rule_maps_to_inner_selector() {
+    assert_eq!(
+        "AAAA;EACE;;AACA;EAAI",
+        mappings(
+            "a {\n  color: red;\n  b { c: d; }\n}",
+            &grass::Options::default()
+        )
+    );
+}
+
+#[test]
+fn media_query_is_indented() {
+    assert_eq!(
+        "AAAA;EAAa;IAAI",
+        mappings("@media foo { a { b: c; } }", &grass::Options::default())
+    );
+}
+
+#[test]
+fn at_rules_are_mapped() {
+    assert_eq!(
+        "AAAA;EAAmB;IAAI;;;AACvB;EAAW",
+        mappings(
+            "@supports (a: b) { c { d: e; } }\n@foo bar { f: g; }",
+            &grass::Options::default()
+        )
+    );
+}
+
+#[test]
+fn columns_are_utf16_code_units() {
+    // the BOM takes up the first column, and the emoji two more
+    assert_eq!(
+        "CAAA,EAAI,OAAS",
+        mappings(
+            "a { b: \"\u{1F600}\"; c: d; }",
+            &grass::Options::default().style(grass::OutputStyle::Compressed)
+        )
+    );
+}
+
+#[test]
+fn charset_shifts_lines() {
+    assert_eq!(
+        ";AAAA;EAAI",
+        mappings("a { color: \"é\"; }", &grass::Options::default())
+    );
+}
+
+#[test]
+fn compressed() {
+    assert_eq!(
+        "AAAA,EAAI,UACJ,EAAI",
+        mappings(
+            "a { color: red; }\nb { c: d; }",
+            &grass::Options::default().style(grass::OutputStyle::Compressed)
+        )
+    );
+}
+
+#[test]
+fn no_output() {
+    assert_eq!("", mappings("$a: red;", &grass::Options::default()));
+}
+
+#[test]
+fn stdin_source_name() {
+    let (_, source_map) =
+        grass::from_string_with_source_map("a { b: c; }".to_string(), &grass::Options::default())
+            .unwrap();
+    assert_eq!(vec!["stdin"], source_map.sources());
+    assert_eq!(
+        "{\"version\":3,\"sources\":[\"stdin\"],\"names\":[],\"mappings\":\"AAAA;EAAI\"}",
+        source_map.to_json()
+    );
+}
+
+#[test]
+fn embed_sources_and_file() {
+    let (_, source_map) = grass::from_string_with_source_map(
+        "a {\n  b: \"c\";\n}".to_string(),
+        &grass::Options::default(),
+    )
+    .unwrap();
+    assert_eq!(
+        "{\"version\":3,\"file\":\"out.css\",\"sources\":[\"stdin\"],\"sourcesContent\":[\"a {\\n  b: \\\"c\\\";\\n}\"],\"names\":[],\"mappings\":\"AAAA;EACE\"}",
+        source_map
+            .file("out.css".to_string())
+            .embed_sources(true)
+            .to_json()
+    );
+}
+
+#[test]
+fn data_url() {
+    let (_, source_map) =
+        grass::from_string_with_source_map("a { b: c; }".to_string(), &grass::Options::default())
+            .unwrap();
+    assert_eq!(
+        "data:application/json;charset=utf-8;base64,eyJ2ZXJzaW9uIjozLCJzb3VyY2VzIjpbInN0ZGluIl0sIm5hbWVzIjpbXSwibWFwcGluZ3MiOiJBQUFBO0VBQUkifQ==",
+        source_map.to_data_url()
+    );
+}
+
+#[test]
+fn imported_file_is_separate_source() {
+    let input = "@import \"imported_file_is_separate_source__a\";\nb { c: d; }";
+    tempfile!(
+        "imported_file_is_separate_source__a.scss",
+        "a { color: red; }"
+    );
+    let (_, source_map) =
+        grass::from_string_with_source_map(input.to_string(), &grass::Options::default()).unwrap();
+    assert_eq!(
+        vec!["imported_file_is_separate_source__a.scss", "stdin"],
+        source_map.sources()
+    );
+    assert_eq!("AAAA;EAAI;;;ACCJ;EAAI", source_map.mappings());
+}
+
+#[test]
+fn relative_source_urls() {
+    tempfile!("relative_source_urls.scss", "a { color: red; }");
+    let (_, source_map) =
+        grass::from_path_with_source_map("relative_source_urls.scss", &grass::Options::default())
+            .unwrap();
+    let source_map = source_map.source_urls(
+        grass::SourceMapUrls::Relative,
+        &std::env::current_dir()
+            .unwrap()
+            .join("relative_source_urls_out"),
+    );
+    assert_eq!(vec!["../relative_source_urls.scss"], source_map.sources());
+}
+
+#[test]
+fn absolute_source_urls() {
+    tempfile!("absolute_source_urls.scss", "a { color: red; }");
+    let (_, source_map) =
+        grass::from_path_with_source_map("absolute_source_urls.scss", &grass::Options::default())
+            .unwrap();
+    let source_map = source_map.source_urls(
+        grass::SourceMapUrls::Absolute,
+        &std::env::current_dir().unwrap(),
+    );
+    let sources = source_map.sources();
+    assert_eq!(1, sources.len());
+    assert!(sources[0].starts_with("file:///"));
+    assert!(sources[0].ends_with("/absolute_source_urls.scss"));
+}
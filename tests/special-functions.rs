@@ -3,21 +3,22 @@ mod macros;
 
 test!(
     calc_whitespace,
-    "a {\n  color: calc(       1      );\n}\n",
-    "a {\n  color: calc( 1 );\n}\n"
+    "a {\n  color: calc(       1px    +   1em   );\n}\n",
+    "a {\n  color: calc(1px + 1em);\n}\n"
 );
-test!(
+error!(
     calc_newline,
-    "a {\n  color: calc(\n);\n}\n",
-    "a {\n  color: calc( );\n}\n"
+    "a {\n  color: calc(\n);\n}\n", "Error: Expected number, variable, function, or calculation."
 );
-test!(
+error!(
     calc_multiple_args,
-    "a {\n  color: calc(1, 2, a, b, c);\n}\n"
+    "a {\n  color: calc(1, 2, a, b, c);\n}\n",
+    "Error: expected \"+\", \"-\", \"*\", \"/\", or \")\"."
 );
 test!(
-    calc_does_not_evaluate_arithmetic,
-    "a {\n  color: calc(1 + 2);\n}\n"
+    calc_evaluates_arithmetic,
+    "a {\n  color: calc(1 + 2);\n}\n",
+    "a {\n  color: 3;\n}\n"
 );
 test!(
     calc_evaluates_interpolated_arithmetic,
@@ -29,20 +30,25 @@ test!(
     calc_retains_multiline_comment,
     "a {\n  color: calc(/**/);\n}\n"
 );
-test!(calc_nested_parens, "a {\n  color: calc((((()))));\n}\n");
+error!(
+    calc_nested_parens,
+    "a {\n  color: calc((((()))));\n}\n",
+    "Error: Expected number, variable, function, or calculation."
+);
 test!(
-    calc_invalid_arithmetic,
-    "a {\n  color: calc(2px + 2px + 5%);\n}\n"
+    calc_partially_simplified_arithmetic,
+    "a {\n  color: calc(2px + 2px + 5%);\n}\n",
+    "a {\n  color: calc(4px + 5%);\n}\n"
 );
 test!(
     calc_uppercase,
-    "a {\n  color: CALC(1 + 1);\n}\n",
-    "a {\n  color: calc(1 + 1);\n}\n"
+    "a {\n  color: CALC(1px + 1em);\n}\n",
+    "a {\n  color: calc(1px + 1em);\n}\n"
 );
 test!(
     calc_mixed_casing,
-    "a {\n  color: cAlC(1 + 1);\n}\n",
-    "a {\n  color: calc(1 + 1);\n}\n"
+    "a {\n  color: cAlC(1px + 1em);\n}\n",
+    "a {\n  color: calc(1px + 1em);\n}\n"
 );
 test!(
     calc_browser_prefixed,
@@ -14,3 +14,145 @@ test!(
     }",
     "@supports (a: b) {\n  a {\n    color: red;\n  }\n}\na {\n  color: green;\n}\n"
 );
+test!(
+    evaluates_declaration_value,
+    "@supports (a: 1 + 2) {
+        a {
+            color: red;
+        }
+    }",
+    "@supports (a: 3) {\n  a {\n    color: red;\n  }\n}\n"
+);
+test!(
+    interpolated_declaration,
+    "$name: display;
+    $value: flex;
+
+    @supports (#{$name}: $value) {
+        a {
+            color: red;
+        }
+    }",
+    "@supports (display: flex) {\n  a {\n    color: red;\n  }\n}\n"
+);
+test!(
+    custom_property_value_is_not_evaluated,
+    "@supports (--foo: 1 + 2) {
+        a {
+            color: red;
+        }
+    }",
+    "@supports (--foo: 1 + 2) {\n  a {\n    color: red;\n  }\n}\n"
+);
+test!(
+    negation,
+    "@supports not (a: b) {
+        a {
+            color: red;
+        }
+    }",
+    "@supports not (a: b) {\n  a {\n    color: red;\n  }\n}\n"
+);
+test!(
+    nested_negation_is_parenthesized,
+    "@supports (a: b) and (not (c: d)) {
+        a {
+            color: red;
+        }
+    }",
+    "@supports (a: b) and (not (c: d)) {\n  a {\n    color: red;\n  }\n}\n"
+);
+test!(
+    uppercase_operators,
+    "@supports (a: b) AND (c: d) And (e: f) {
+        a {
+            color: red;
+        }
+    }",
+    "@supports (a: b) and (c: d) and (e: f) {\n  a {\n    color: red;\n  }\n}\n"
+);
+test!(
+    uppercase_or_and_not,
+    "@supports NOT (a: b) {
+        a {
+            color: red;
+        }
+    }
+    @supports (a: b) OR (c: d) {
+        a {
+            color: red;
+        }
+    }",
+    "@supports not (a: b) {\n  a {\n    color: red;\n  }\n}\n@supports (a: b) or (c: d) {\n  a {\n    color: red;\n  }\n}\n"
+);
+test!(
+    mixed_operators_in_parens,
+    "@supports ((a: b) or (c: d)) and (e:f) {
+        a {
+            color: red;
+        }
+    }",
+    "@supports ((a: b) or (c: d)) and (e: f) {\n  a {\n    color: red;\n  }\n}\n"
+);
+test!(
+    chained_or,
+    "@supports (a:b) or (c:d) or (e:f) {
+        a {
+            color: red;
+        }
+    }",
+    "@supports (a: b) or (c: d) or (e: f) {\n  a {\n    color: red;\n  }\n}\n"
+);
+test!(
+    function_condition,
+    "@supports selector(a   >   b) {
+        a {
+            color: red;
+        }
+    }",
+    "@supports selector(a > b) {\n  a {\n    color: red;\n  }\n}\n"
+);
+test!(
+    interpolated_condition,
+    "$condition: \"(a: b)\";
+
+    @supports #{$condition} {
+        a {
+            color: red;
+        }
+    }",
+    "@supports (a: b) {\n  a {\n    color: red;\n  }\n}\n"
+);
+test!(
+    nested_in_style_rule,
+    "a {
+        @supports (a: b) {
+            color: red;
+        }
+    }",
+    "@supports (a: b) {\n  a {\n    color: red;\n  }\n}\n"
+);
+test!(empty_body_is_omitted, "@supports (a: b) {}", "");
+test!(
+    invisible_body_is_omitted,
+    "@supports (a: b) {
+        a {}
+    }",
+    ""
+);
+error!(
+    double_not,
+    "@supports not not (a: b) {}", "Error: \"not\" is not a valid identifier here."
+);
+error!(
+    mixed_operators_without_parens,
+    "@supports (a: b) and (c: d) or (e: f) {}", "Error: Expected \"and\"."
+);
+error!(
+    identifier_without_parens,
+    "@supports foo {}", "Error: Expected @supports condition."
+);
+error!(
+    unclosed_declaration,
+    "@supports (a: b {}", "Error: expected \")\"."
+);
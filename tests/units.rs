@@ -127,12 +127,41 @@ test!(
     "a {\n  color: unit((1 / 1in) * 1in);\n}\n",
     "a {\n  color: \"\";\n}\n"
 );
+test!(
+    unit_div_non_comparable,
+    "a {\n  color: unit((1px / 1em));\n}\n",
+    "a {\n  color: \"px/em\";\n}\n"
+);
+test!(
+    unit_div_non_comparable_times_denominator,
+    "a {\n  color: (1px / 1em) * 1em;\n}\n",
+    "a {\n  color: 1px;\n}\n"
+);
+test!(
+    unit_div_mul_cancels_comparable_units,
+    "a {\n  color: (1px * 1em) / 1in;\n}\n",
+    "a {\n  color: 0.0104166667em;\n}\n"
+);
+test!(
+    unit_div_mul_by_single,
+    "a {\n  color: unit((1px * 1em) / 1s);\n}\n",
+    "a {\n  color: \"px*em/s\";\n}\n"
+);
+test!(
+    unit_div_cancels_one_of_mul,
+    "a {\n  color: ((1px * 1em) / 1em);\n}\n",
+    "a {\n  color: 1px;\n}\n"
+);
+test!(
+    unit_div_none_by_mul,
+    "a {\n  color: unit(1 / (1px * 1em));\n}\n",
+    "a {\n  color: \"(px*em)^-1\";\n}\n"
+);
 error!(
     display_single_div_with_none_numerator,
     "a {\n  color: (1 / 1em);\n}\n", "Error: 1em^-1 isn't a valid CSS value."
 );
 error!(
-    #[ignore = "non-comparable inverse units"]
     display_single_div_with_non_comparable_numerator,
     "a {\n  color: (1px / 1em);\n}\n", "Error: 1px/em isn't a valid CSS value."
 );
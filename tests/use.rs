@@ -329,3 +329,17 @@ fn use_variable_declaration_between_use() {
         &grass::from_string(input.to_string(), &grass::Options::default()).expect(input)
     );
 }
+
+#[test]
+fn use_default_namespace_strips_underscore_and_extension() {
+    let input = "@use \"_use_default_namespace_strips_underscore_and_extension.scss\";\na {\n color: use_default_namespace_strips_underscore_and_extension.$a;\n}";
+    tempfile!(
+        "_use_default_namespace_strips_underscore_and_extension.scss",
+        "$a: red;"
+    );
+
+    assert_eq!(
+        "a {\n  color: red;\n}\n",
+        &grass::from_string(input.to_string(), &grass::Options::default()).expect(input)
+    );
+}